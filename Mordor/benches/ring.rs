@@ -0,0 +1,143 @@
+// Сравнение RingBuffer с PodRing, VecDeque и std::sync::mpsc: cargo bench
+
+use std::collections::VecDeque;
+use std::hint::black_box;
+use std::ops::ControlFlow;
+use std::sync::mpsc;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use mordor::{ByteRing, PodRing, RingBuffer};
+
+const CAPACITIES: [usize; 3] = [64, 4096, 65536];
+const BATCHES: [usize; 3] = [1, 16, 256];
+
+// 1. Поштучная запись и чтение до заполнения и обратно
+fn push_pop(c: &mut Criterion) {
+    let mut group = c.benchmark_group("push_pop");
+    for capacity in CAPACITIES {
+        group.throughput(Throughput::Elements(capacity as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("RingBuffer", capacity),
+            &capacity,
+            |b, &cap| {
+                let mut ring = RingBuffer::new(cap);
+                b.iter(|| {
+                    for i in 0..cap {
+                        let _ = ring.push(i as u8);
+                    }
+                    while let Some(v) = ring.pop() {
+                        black_box(v);
+                    }
+                });
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("VecDeque", capacity),
+            &capacity,
+            |b, &cap| {
+                let mut deque = VecDeque::with_capacity(cap);
+                b.iter(|| {
+                    for i in 0..cap {
+                        deque.push_back(i as u8);
+                    }
+                    while let Some(v) = deque.pop_front() {
+                        black_box(v);
+                    }
+                });
+            },
+        );
+
+        group.bench_with_input(BenchmarkId::new("mpsc", capacity), &capacity, |b, &cap| {
+            let (tx, rx) = mpsc::sync_channel(cap);
+            b.iter(|| {
+                for i in 0..cap {
+                    tx.send(i as u8).unwrap();
+                }
+                while let Ok(v) = rx.try_recv() {
+                    black_box(v);
+                }
+            });
+        });
+    }
+    group.finish();
+}
+
+// 2. Пакетная запись и чтение срезами разного размера
+fn extend_drain(c: &mut Criterion) {
+    let mut group = c.benchmark_group("extend_drain");
+    let capacity = 4096;
+    for batch in BATCHES {
+        let data = vec![0xAB_u8; batch];
+        group.throughput(Throughput::Bytes(capacity as u64));
+
+        group.bench_with_input(BenchmarkId::new("RingBuffer", batch), &data, |b, data| {
+            let mut ring = RingBuffer::new(capacity);
+            b.iter(|| {
+                for _ in 0..capacity / data.len() {
+                    ring.extend(data);
+                    black_box(ring.drain(data.len()));
+                }
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("pop_each", batch), &data, |b, data| {
+            let mut ring = RingBuffer::new(capacity);
+            b.iter(|| {
+                for _ in 0..capacity / data.len() {
+                    ring.extend(data);
+                    ring.pop_each(data.len(), |byte| {
+                        black_box(byte);
+                        ControlFlow::Continue(())
+                    });
+                }
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("PodRing", batch), &data, |b, data| {
+            let mut ring = PodRing::new(capacity);
+            let mut out = vec![0_u8; data.len()];
+            b.iter(|| {
+                for _ in 0..capacity / data.len() {
+                    ring.extend_from_slice(data);
+                    black_box(ring.drain_into_slice(&mut out));
+                }
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("VecDeque", batch), &data, |b, data| {
+            let mut deque = VecDeque::with_capacity(capacity);
+            b.iter(|| {
+                for _ in 0..capacity / data.len() {
+                    deque.extend(data.iter().copied());
+                    black_box(deque.drain(..data.len()).collect::<Vec<_>>());
+                }
+            });
+        });
+    }
+    group.finish();
+}
+
+// 3. Большие переносы байт через ByteRing (сравнить с --features prefetch)
+fn byte_transfer(c: &mut Criterion) {
+    let mut group = c.benchmark_group("byte_transfer");
+    let capacity = 4 << 20;
+    for chunk in [64 << 10, 1 << 20] {
+        let data = vec![0xCD_u8; chunk];
+        let mut out = vec![0_u8; chunk];
+        group.throughput(Throughput::Bytes(chunk as u64));
+
+        group.bench_with_input(BenchmarkId::new("ByteRing", chunk), &data, |b, data| {
+            let mut ring = ByteRing::new(capacity);
+            b.iter(|| {
+                ring.extend_from_slice(data);
+                black_box(ring.drain_into(&mut out));
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, push_pop, extend_drain, byte_transfer);
+criterion_main!(benches);
@@ -0,0 +1,73 @@
+// TCP-прокси (или эхо-сервер) на двух ByteRing: по кольцу на каждое направление.
+// Данные читаются прямо в свободное место кольца (fill_from) и пишутся прямо
+// из его содержимого (flush_into), поэтому запись постоянно переходит через границу массива.
+//
+// Эхо:    cargo run --example tcp_proxy -- 127.0.0.1:7000
+// Прокси: cargo run --example tcp_proxy -- 127.0.0.1:7000 example.com:80
+
+use std::io;
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::thread;
+
+use mordor::ByteRing;
+
+// Небольшая ёмкость, не кратная типичным размерам пакетов, чтобы чаще попадать на шов
+const RING_CAPACITY: usize = 4093;
+
+// 1. Одно направление: читаем из from, пишем в to, пока from не закроется
+fn shuttle(mut from: TcpStream, mut to: TcpStream) -> io::Result<u64> {
+    let mut ring = ByteRing::new(RING_CAPACITY);
+    let mut total = 0;
+    loop {
+        let read = ring.fill_from(&mut from)?;
+        if read == 0 && !ring.is_full() {
+            break; // Собеседник закрыл запись
+        }
+        total += ring.flush_into(&mut to)? as u64;
+    }
+    to.shutdown(Shutdown::Write)?; // Передаём конец потока дальше
+    Ok(total)
+}
+
+// 2. Соединение: эхо через одно кольцо или прокси через два
+fn serve(client: TcpStream, upstream: Option<&str>) -> io::Result<()> {
+    let peer = client.peer_addr()?;
+    let Some(upstream) = upstream else {
+        let bytes = shuttle(client.try_clone()?, client)?;
+        println!("{}: эхо {} байт", peer, bytes);
+        return Ok(());
+    };
+
+    let server = TcpStream::connect(upstream)?;
+    let outbound = {
+        let (client, server) = (client.try_clone()?, server.try_clone()?);
+        thread::spawn(move || shuttle(client, server))
+    };
+    let inbound = shuttle(server, client)?;
+    let outbound = outbound.join().expect("Поток прокси запаниковал")?;
+    println!("{}: {} байт туда, {} байт обратно", peer, outbound, inbound);
+    Ok(())
+}
+
+fn main() -> io::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let listen = args.next().unwrap_or_else(|| "127.0.0.1:7000".to_string());
+    let upstream = args.next();
+
+    let listener = TcpListener::bind(&listen)?;
+    match &upstream {
+        Some(upstream) => println!("Прокси {} -> {}", listen, upstream),
+        None => println!("Эхо на {}", listen),
+    }
+
+    for client in listener.incoming() {
+        let upstream = upstream.clone();
+        let client = client?;
+        thread::spawn(move || {
+            if let Err(e) = serve(client, upstream.as_deref()) {
+                eprintln!("Ошибка соединения: {}", e);
+            }
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,36 @@
+// Произвольные байтовые срезы через extend/drain и строковый буфер
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use mordor::{RingBuffer, StrRingBuffer};
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    capacity: u8,
+    chunks: Vec<(Vec<u8>, u8)>, // Что записать и сколько прочитать после
+    text: String,
+}
+
+fuzz_target!(|input: Input| {
+    let capacity = input.capacity as usize % 128 + 1;
+
+    // Байты выходят в том же порядке, в каком вошли
+    let mut ring = RingBuffer::new(capacity);
+    let mut written = Vec::new();
+    let mut read = Vec::new();
+    for (chunk, take) in &input.chunks {
+        let n = ring.extend(chunk);
+        written.extend_from_slice(&chunk[..n]);
+        read.extend(ring.drain(*take as usize));
+    }
+    read.extend(ring.drain(capacity));
+    assert_eq!(read, written);
+
+    // Строковый буфер никогда не разрывает символ
+    let mut text = StrRingBuffer::new(capacity);
+    let pushed = text.push_str(&input.text);
+    assert!(input.text.is_char_boundary(pushed));
+    assert_eq!(text.drain_all(), &input.text[..pushed]);
+});
@@ -0,0 +1,81 @@
+// Случайные последовательности операций над RingBuffer<u8> в сравнении с VecDeque
+
+#![no_main]
+
+use std::collections::VecDeque;
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use mordor::RingBuffer;
+
+#[derive(Debug, Arbitrary)]
+enum Op {
+    Push(u8),
+    PushOverwrite(u8),
+    Pop,
+    PopBack,
+    Extend(Vec<u8>),
+    Drain(u8),
+    Remove(u8),
+    Clear,
+    Resize(u8),
+}
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    capacity: u8,
+    ops: Vec<Op>,
+}
+
+fuzz_target!(|input: Input| {
+    let mut capacity = input.capacity as usize % 64 + 1;
+    let mut ring = RingBuffer::new(capacity);
+    let mut model = VecDeque::new();
+
+    for op in input.ops {
+        match op {
+            Op::Push(v) => {
+                let accepted = model.len() < capacity;
+                if accepted {
+                    model.push_back(v);
+                }
+                assert_eq!(ring.push(v).is_ok(), accepted);
+            }
+            Op::PushOverwrite(v) => {
+                let evicted = if model.len() == capacity { model.pop_front() } else { None };
+                model.push_back(v);
+                assert_eq!(ring.push_overwrite(v), evicted);
+            }
+            Op::Pop => assert_eq!(ring.pop(), model.pop_front()),
+            Op::PopBack => assert_eq!(ring.pop_back(), model.pop_back()),
+            Op::Extend(data) => {
+                let accepted = data.len().min(capacity - model.len());
+                model.extend(&data[..accepted]);
+                assert_eq!(ring.extend(&data), accepted);
+            }
+            Op::Drain(n) => {
+                let n = n as usize;
+                let expected: Vec<u8> = model.drain(..n.min(model.len())).collect();
+                assert_eq!(ring.drain(n), expected);
+            }
+            Op::Remove(i) => assert_eq!(ring.remove(i as usize), model.remove(i as usize)),
+            Op::Clear => {
+                ring.clear();
+                model.clear();
+            }
+            Op::Resize(n) => {
+                capacity = n as usize % 64 + 1;
+                while model.len() > capacity {
+                    model.pop_front();
+                }
+                ring.resize(capacity);
+            }
+        }
+
+        // Инварианты после каждой операции
+        assert!(ring.len() <= ring.capacity());
+        assert_eq!(ring.len(), model.len());
+        assert!(ring.iter().eq(model.iter()));
+        assert_eq!(ring.validate(), Ok(()));
+    }
+});
@@ -0,0 +1,149 @@
+// Советчик по ёмкости: гистограмма заполнения после каждой записи, по ней -
+// рекомендуемая ёмкость (p99 заполнения плюс запас), а в режиме авторасширения
+// буфер сам растёт до рекомендации, когда упирается в потолок
+
+const BUCKETS: usize = 64;
+
+// 1. Корзина b считает записи, после которых заполнение было в доле [b/64, (b+1)/64)
+// ёмкости; последняя корзина - полностью заполненный буфер
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapacityAdvisor {
+    buckets: [u64; BUCKETS],
+    samples: u64,
+    capacity: usize,             // Ёмкость, к которой относятся корзины
+    max_capacity: Option<usize>, // Предел авторасширения (None - только советы)
+}
+
+impl CapacityAdvisor {
+    pub(crate) fn new(capacity: usize, max_capacity: Option<usize>) -> Self {
+        CapacityAdvisor {
+            buckets: [0; BUCKETS],
+            samples: 0,
+            capacity,
+            max_capacity,
+        }
+    }
+
+    // 2. Учёт заполнения после записи
+    #[inline]
+    pub(crate) fn record(&mut self, len: usize) {
+        let bucket = (len * BUCKETS / self.capacity).min(BUCKETS - 1);
+        self.buckets[bucket] += 1;
+        self.samples += 1;
+    }
+
+    // 2.1. После смены ёмкости старые корзины теряют смысл - начинаем заново
+    pub(crate) fn reset(&mut self, capacity: usize) {
+        *self = CapacityAdvisor::new(capacity, self.max_capacity);
+    }
+
+    // 3. Сколько записей учтено
+    pub fn samples(&self) -> u64 {
+        self.samples
+    }
+
+    pub fn max_capacity(&self) -> Option<usize> {
+        self.max_capacity
+    }
+
+    // 3.1. Заполнение, которого не превышали permille тысячных всех записей
+    // (с точностью до корзины, с округлением вверх); 0, если записей не было
+    pub fn percentile(&self, permille: u32) -> usize {
+        assert!(permille <= 1000, "Перцентиль задаётся в тысячных!");
+        if self.samples == 0 {
+            return 0;
+        }
+
+        let target = (self.samples * u64::from(permille)).div_ceil(1000).max(1);
+        let mut seen = 0;
+        for (bucket, &count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= target {
+                return self.bucket_top(bucket);
+            }
+        }
+        self.capacity
+    }
+
+    // 3.2. Рекомендуемая ёмкость: p99 заполнения и ещё четверть сверху;
+    // без записей - текущая ёмкость
+    pub fn suggested_capacity(&self) -> usize {
+        if self.samples == 0 {
+            return self.capacity;
+        }
+        let p99 = self.percentile(990);
+        (p99 + p99.div_ceil(4)).max(1)
+    }
+
+    // 4. Куда расширить заполненный буфер (если авторасширение включено и есть куда)
+    pub(crate) fn grow_to(&self) -> Option<usize> {
+        let max = self.max_capacity.filter(|&max| max > self.capacity)?;
+        Some(self.suggested_capacity().clamp(self.capacity + 1, max))
+    }
+
+    // 4.1. Наибольшее заполнение, попадающее в корзину
+    fn bucket_top(&self, bucket: usize) -> usize {
+        if bucket == BUCKETS - 1 {
+            self.capacity
+        } else {
+            ((bucket + 1) * self.capacity).div_ceil(BUCKETS) - 1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CapacityAdvisor;
+    use crate::{OverflowPolicy, RingBuffer};
+
+    #[test]
+    fn test_percentiles() {
+        let mut advisor = CapacityAdvisor::new(1000, None);
+        assert_eq!(advisor.suggested_capacity(), 1000); // Пока не с чем работать
+
+        for len in 1..=100 {
+            advisor.record(len);
+        }
+        assert_eq!(advisor.samples(), 100);
+        assert_eq!(advisor.percentile(500), 62); // Точность - корзина в 1/64 ёмкости
+        assert_eq!(advisor.percentile(990), 109);
+        assert_eq!(advisor.suggested_capacity(), 137);
+    }
+
+    #[test]
+    fn test_suggestion_from_ring() {
+        let mut rb = RingBuffer::builder()
+            .capacity(256)
+            .policy(OverflowPolicy::Overwrite)
+            .with_advisor()
+            .build();
+        assert!(RingBuffer::<u8>::new(4).suggested_capacity().is_none());
+
+        // После записи в буфере всегда 11-13 элементов
+        for i in 0..1000 {
+            rb.push(i).unwrap();
+            if rb.len() > 12 {
+                rb.skip(3);
+            }
+        }
+        let suggested = rb.suggested_capacity().unwrap();
+        assert!((13..=20).contains(&suggested), "{}", suggested);
+        assert_eq!(rb.capacity(), 256); // Без авторасширения - только совет
+    }
+
+    #[test]
+    fn test_auto_grow() {
+        let mut rb = RingBuffer::builder().capacity(4).auto_grow(16).build();
+        for i in 0..16 {
+            assert!(rb.push(i).is_ok());
+        }
+        assert_eq!(rb.capacity(), 16);
+        assert_eq!(
+            rb.iter().copied().collect::<Vec<_>>(),
+            (0..16).collect::<Vec<_>>()
+        );
+
+        assert!(rb.push(16).is_err()); // Предел достигнут - действует политика
+        assert_eq!(rb.capacity_advisor().unwrap().max_capacity(), Some(16));
+    }
+}
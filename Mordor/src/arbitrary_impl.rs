@@ -0,0 +1,74 @@
+// Генерация случайных буферов для фаззинга (arbitrary) и проверки свойств (quickcheck):
+// случайные ёмкость, положение головы и заполнение, в том числе через границу массива
+
+use crate::RingBuffer;
+
+// Ёмкость генерируемых буферов ограничена, чтобы входы оставались небольшими
+const MAX_CAPACITY: usize = 64;
+
+#[cfg(feature = "arbitrary")]
+impl<'a, T: arbitrary::Arbitrary<'a>> arbitrary::Arbitrary<'a> for RingBuffer<T> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let capacity = u.int_in_range(1..=MAX_CAPACITY)?;
+        let start = u.int_in_range(0..=capacity - 1)?;
+        let len = u.int_in_range(0..=capacity)?;
+
+        let mut ring = RingBuffer::with_start(capacity, start);
+        for _ in 0..len {
+            let _ = ring.push(T::arbitrary(u)?); // Места хватает: len <= capacity
+        }
+        Ok(ring)
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+impl<T: quickcheck::Arbitrary> quickcheck::Arbitrary for RingBuffer<T> {
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        let capacity = usize::arbitrary(g) % MAX_CAPACITY + 1;
+        let start = usize::arbitrary(g) % capacity;
+        let len = usize::arbitrary(g) % (capacity + 1);
+
+        let mut ring = RingBuffer::with_start(capacity, start);
+        for _ in 0..len {
+            let _ = ring.push(T::arbitrary(g));
+        }
+        ring
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RingBuffer;
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_arbitrary_rings_stay_consistent() {
+        use crate::rng::{RandomSource, XorShift64};
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let mut rng = XorShift64::new(7);
+        for _ in 0..200 {
+            let bytes: Vec<u8> = (0..256).map(|_| rng.next_u64() as u8).collect();
+            let mut ring = RingBuffer::<u8>::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+            assert!(ring.len() <= ring.capacity());
+
+            // Дописываем до заполнения: запись идёт с произвольной позиции хвоста
+            let mut expected: Vec<u8> = ring.iter().copied().collect();
+            let free = vec![0xEE; ring.capacity() - ring.len()];
+            assert_eq!(ring.extend(&free), free.len());
+            expected.extend(free);
+            assert_eq!(ring.drain(ring.capacity()), expected);
+        }
+    }
+
+    #[cfg(feature = "quickcheck")]
+    #[test]
+    fn test_quickcheck_pop_order() {
+        fn prop(mut ring: RingBuffer<u8>) -> bool {
+            let expected: Vec<u8> = ring.iter().copied().collect();
+            ring.drain(ring.len()) == expected
+        }
+
+        quickcheck::quickcheck(prop as fn(RingBuffer<u8>) -> bool);
+    }
+}
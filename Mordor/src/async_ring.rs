@@ -0,0 +1,899 @@
+// Асинхронное кольцо без привязки к рантайму: push().await и pop().await,
+// а для своих будущих и select! - опрос poll_pop и poll_ready.
+//
+// Будим только на переходах: писателя - когда полное кольцо перестало быть полным,
+// читателя - когда пустое перестало быть пустым. Проснувшийся, забрав своё, передаёт
+// эстафету следующему ожидающему, если работа для него ещё есть, - так очередь
+// ожидающих расходуется по одному, без лавины пробуждений, и ни один не теряется.
+//
+// Отмена безопасна для всех операций: элемент переходит из кольца в результат
+// за один опрос под блокировкой, резерва между опросами нет. Брошенный pop()
+// ничего не забирает, брошенный push() не записывает, а значение можно вернуть
+// через Push::into_value; доставшееся брошенному будущему пробуждение передаётся дальше.
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll, Waker};
+
+use crate::{BlockingError, RingBuffer, StatsSnapshot};
+
+// 1. Очередь ожидающих задач в порядке прихода; need - сколько элементов
+// (данных или места) нужно ожидающему, для одиночных операций 1
+#[derive(Debug, Default)]
+struct Waiters {
+    next_id: u64,
+    queue: VecDeque<(u64, usize, Waker)>,
+}
+
+impl Waiters {
+    // 1.1. Встаём в очередь (или обновляем waker, если уже стоим)
+    fn register(&mut self, slot: &mut Option<u64>, waker: &Waker) {
+        self.register_need(slot, 1, waker);
+    }
+
+    fn register_need(&mut self, slot: &mut Option<u64>, need: usize, waker: &Waker) {
+        if let Some(id) = *slot {
+            if let Some((_, _, queued)) = self.queue.iter_mut().find(|(i, _, _)| *i == id) {
+                queued.clone_from(waker);
+                return;
+            }
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        self.queue.push_back((id, need, waker.clone()));
+        *slot = Some(id);
+    }
+
+    // 1.2. Уходим из очереди; false - нас уже разбудили и вынули
+    fn remove(&mut self, id: u64) -> bool {
+        let before = self.queue.len();
+        self.queue.retain(|(i, _, _)| *i != id);
+        self.queue.len() != before
+    }
+
+    // 1.3. То же для опроса без будущего: ожидающего узнаём по самому waker
+    fn register_waker(&mut self, waker: &Waker) {
+        if !self
+            .queue
+            .iter()
+            .any(|(_, _, queued)| queued.will_wake(waker))
+        {
+            let id = self.next_id;
+            self.next_id += 1;
+            self.queue.push_back((id, 1, waker.clone()));
+        }
+    }
+
+    fn remove_waker(&mut self, waker: &Waker) -> bool {
+        let before = self.queue.len();
+        self.queue.retain(|(_, _, queued)| !queued.will_wake(waker));
+        self.queue.len() != before
+    }
+
+    // 1.4. Будим самого давнего ожидающего
+    fn wake_one(&mut self) {
+        if let Some((_, _, waker)) = self.queue.pop_front() {
+            waker.wake();
+        }
+    }
+
+    // 1.5. Будим самого давнего из тех, кому хватит available: стоящий впереди
+    // большой порции не должен задерживать маленькие
+    fn wake_fitting(&mut self, available: usize) {
+        if let Some(index) = self
+            .queue
+            .iter()
+            .position(|(_, need, _)| *need <= available)
+        {
+            if let Some((_, _, waker)) = self.queue.remove(index) {
+                waker.wake();
+            }
+        }
+    }
+
+    fn wake_all(&mut self) {
+        for (_, _, waker) in self.queue.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+// 2. Кольцо и очереди ожидающих под одним мьютексом
+#[derive(Debug)]
+pub struct AsyncRing<T> {
+    state: Mutex<State<T>>,
+}
+
+#[derive(Debug)]
+struct State<T> {
+    ring: RingBuffer<T>,
+    closed: bool,
+    readers: Waiters,       // Ждут данных
+    writers: Waiters,       // Ждут места
+    chunk_readers: Waiters, // Ждут порции данных
+    chunk_writers: Waiters, // Ждут места под порцию
+}
+
+impl<T> State<T> {
+    // 2.1. Запись с пробуждением читателя на переходе "пусто -> не пусто"
+    fn push(&mut self, value: T) -> Result<(), (BlockingError, T)> {
+        if self.closed {
+            return Err((BlockingError::Closed, value));
+        }
+        if self.ring.is_full() {
+            return Err((BlockingError::Full, value));
+        }
+
+        let was_empty = self.ring.is_empty();
+        let _ = self.ring.push(value); // Место проверено выше
+        if was_empty {
+            self.readers.wake_one();
+        }
+        if !self.ring.is_full() {
+            self.writers.wake_one(); // Эстафета: место есть и для следующего писателя
+        }
+        self.wake_chunks();
+        Ok(())
+    }
+
+    // 2.2. Чтение с пробуждением писателя на переходе "полно -> не полно"
+    fn pop(&mut self) -> Option<T> {
+        let was_full = self.ring.is_full();
+        let value = self.ring.pop()?;
+        if was_full {
+            self.writers.wake_one();
+        }
+        if !self.ring.is_empty() {
+            self.readers.wake_one(); // Эстафета: данные есть и для следующего читателя
+        }
+        self.wake_chunks();
+        Some(value)
+    }
+
+    // 2.3. Запись порции целиком; место проверяет вызывающий
+    fn push_chunk(&mut self, items: Vec<T>) {
+        let was_empty = self.ring.is_empty();
+        for item in items {
+            let _ = self.ring.push(item);
+        }
+        if was_empty && !self.ring.is_empty() {
+            self.readers.wake_one();
+        }
+        if !self.ring.is_full() {
+            self.writers.wake_one();
+        }
+        self.wake_chunks();
+    }
+
+    // 2.4. Чтение до n элементов одной порцией
+    fn pop_chunk(&mut self, n: usize) -> Vec<T> {
+        let was_full = self.ring.is_full();
+        let chunk = self.ring.drain(n);
+        if was_full && !chunk.is_empty() {
+            self.writers.wake_one();
+        }
+        if !self.ring.is_empty() {
+            self.readers.wake_one();
+        }
+        self.wake_chunks();
+        chunk
+    }
+
+    // 2.5. Порционных ожидающих будим не на переходах, а когда им хватает
+    fn wake_chunks(&mut self) {
+        let len = self.ring.len();
+        self.chunk_readers.wake_fitting(len);
+        self.chunk_writers.wake_fitting(self.ring.capacity() - len);
+    }
+}
+
+impl<T> AsyncRing<T> {
+    // 2.6. Создаём кольцо заданного размера
+    pub fn new(capacity: usize) -> Self {
+        AsyncRing {
+            state: Mutex::new(State {
+                ring: RingBuffer::builder()
+                    .capacity(capacity)
+                    .with_stats()
+                    .build(),
+                closed: false,
+                readers: Waiters::default(),
+                writers: Waiters::default(),
+                chunk_readers: Waiters::default(),
+                chunk_writers: Waiters::default(),
+            }),
+        }
+    }
+
+    // 3. Запись с ожиданием места
+    pub fn push(&self, value: T) -> Push<'_, T> {
+        Push {
+            owner: self,
+            value: Some(value),
+            waiter: None,
+        }
+    }
+
+    // 4. Чтение с ожиданием данных; None - кольцо закрыто и пусто
+    pub fn pop(&self) -> Pop<'_, T> {
+        Pop {
+            owner: self,
+            waiter: None,
+        }
+    }
+
+    // 4.1. Порционные операции: ждут, пока освободится место под всю порцию
+    // или накопится n элементов, и переносят порцию за одно пробуждение
+    pub fn push_chunk(&self, items: Vec<T>) -> PushChunk<'_, T> {
+        assert!(
+            items.len() <= self.capacity(),
+            "Порция больше ёмкости кольца!"
+        );
+        PushChunk {
+            owner: self,
+            items: Some(items),
+            waiter: None,
+        }
+    }
+
+    // Закрытое кольцо отдаёт остаток, даже если он меньше n; пустой Vec - данных больше не будет
+    pub fn pop_chunk(&self, n: usize) -> PopChunk<'_, T> {
+        assert!(n <= self.capacity(), "Порция больше ёмкости кольца!");
+        PopChunk {
+            owner: self,
+            n,
+            waiter: None,
+        }
+    }
+
+    // 5. Операции без ожидания
+    pub fn try_push(&self, value: T) -> Result<(), BlockingError> {
+        self.lock().push(value).map_err(|(e, _)| e)
+    }
+
+    pub fn try_pop(&self) -> Option<T> {
+        self.lock().pop()
+    }
+
+    // 6. Закрытие: будим всех, чтобы они увидели флаг
+    pub fn close(&self) {
+        let mut state = self.lock();
+        state.closed = true;
+        state.readers.wake_all();
+        state.writers.wake_all();
+        state.chunk_readers.wake_all();
+        state.chunk_writers.wake_all();
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.lock().closed
+    }
+
+    // 7. Размеры (моментальный снимок)
+    pub fn len(&self) -> usize {
+        self.lock().ring.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().ring.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.lock().ring.capacity()
+    }
+
+    // 7.0. Счётчики и размеры одним снимком под блокировкой
+    pub fn stats(&self) -> StatsSnapshot {
+        self.lock().ring.stats_snapshot().unwrap_or_default()
+    }
+
+    // 7.1. Низкоуровневый опрос для своих будущих и select! без создания Push/Pop.
+    // Ожидающий узнаётся по waker; бросивший опрос после Pending вызывает forget_waker,
+    // иначе доставшееся ему пробуждение пропадёт для остальных
+    pub fn poll_pop(&self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let mut state = self.lock();
+        if let Some(value) = state.pop() {
+            state.readers.remove_waker(cx.waker());
+            return Poll::Ready(Some(value));
+        }
+        if state.closed {
+            return Poll::Ready(None);
+        }
+        state.readers.register_waker(cx.waker());
+        Poll::Pending
+    }
+
+    // 7.2. Готовность к записи: место есть (Ok) или кольцо закрыто (Err).
+    // Место не резервируется - try_push после готовности может и не пройти
+    pub fn poll_ready(&self, cx: &mut Context<'_>) -> Poll<Result<(), BlockingError>> {
+        let mut state = self.lock();
+        if state.closed {
+            return Poll::Ready(Err(BlockingError::Closed));
+        }
+        if state.ring.is_full() {
+            state.writers.register_waker(cx.waker());
+            return Poll::Pending;
+        }
+        // Запишет ли вызывающий - неизвестно: передаём эстафету, лишнее пробуждение безвредно
+        state.writers.remove_waker(cx.waker());
+        state.writers.wake_one();
+        Poll::Ready(Ok(()))
+    }
+
+    // 7.3. Отказ от опроса: уходим из очередей и передаём пробуждение, если работа есть
+    pub fn forget_waker(&self, waker: &Waker) {
+        let mut state = self.lock();
+        if !state.readers.remove_waker(waker) && !state.ring.is_empty() {
+            state.readers.wake_one();
+        }
+        if !state.writers.remove_waker(waker) && !state.ring.is_full() {
+            state.writers.wake_one();
+        }
+    }
+
+    // Под блокировкой не вызывается пользовательский код, кроме wake; состояние всегда целое
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+// 8. Будущее записи
+#[derive(Debug)]
+pub struct Push<'a, T> {
+    owner: &'a AsyncRing<T>,
+    value: Option<T>,    // Забирается при успешной записи
+    waiter: Option<u64>, // Место в очереди писателей
+}
+
+// Значение никогда не закрепляется по месту - его только перемещают
+impl<T> Unpin for Push<'_, T> {}
+
+impl<T> Future for Push<'_, T> {
+    type Output = Result<(), BlockingError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let value = this.value.take().expect("Push опрошен после завершения");
+        let mut state = this.owner.lock();
+        match state.push(value) {
+            Ok(()) => Poll::Ready(Ok(())),
+            Err((BlockingError::Full, value)) => {
+                this.value = Some(value);
+                state.writers.register(&mut this.waiter, cx.waker());
+                Poll::Pending
+            }
+            Err((e, _)) => Poll::Ready(Err(e)),
+        }
+    }
+}
+
+impl<'a, T> Push<'a, T> {
+    // 8.1. Отмена записи с возвратом значения; None - значение уже записано
+    pub fn into_value(mut self) -> Option<T> {
+        self.value.take() // Очередь ожидающих покинем в Drop
+    }
+}
+
+impl<T> Drop for Push<'_, T> {
+    fn drop(&mut self) {
+        if let Some(id) = self.waiter {
+            let mut state = self.owner.lock();
+            // Разбудили, а мы уходим: передаём пробуждение, если место ещё есть
+            if !state.writers.remove(id) && !state.ring.is_full() {
+                state.writers.wake_one();
+            }
+        }
+    }
+}
+
+// 9. Будущее чтения
+#[derive(Debug)]
+pub struct Pop<'a, T> {
+    owner: &'a AsyncRing<T>,
+    waiter: Option<u64>, // Место в очереди читателей
+}
+
+impl<T> Future for Pop<'_, T> {
+    type Output = Option<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let this = &mut *self;
+        let mut state = this.owner.lock();
+        if let Some(value) = state.pop() {
+            return Poll::Ready(Some(value));
+        }
+        if state.closed {
+            return Poll::Ready(None);
+        }
+        state.readers.register(&mut this.waiter, cx.waker());
+        Poll::Pending
+    }
+}
+
+impl<T> Drop for Pop<'_, T> {
+    fn drop(&mut self) {
+        if let Some(id) = self.waiter {
+            let mut state = self.owner.lock();
+            // Разбудили, а мы уходим: передаём пробуждение, если данные ещё есть
+            if !state.readers.remove(id) && !state.ring.is_empty() {
+                state.readers.wake_one();
+            }
+        }
+    }
+}
+
+// 10. Будущее порционной записи
+#[derive(Debug)]
+pub struct PushChunk<'a, T> {
+    owner: &'a AsyncRing<T>,
+    items: Option<Vec<T>>, // Забирается при успешной записи
+    waiter: Option<u64>,   // Место в очереди порционных писателей
+}
+
+// Порция только перемещается целиком, как и значение в Push
+impl<T> Unpin for PushChunk<'_, T> {}
+
+impl<T> Future for PushChunk<'_, T> {
+    type Output = Result<(), BlockingError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let mut state = this.owner.lock();
+        if state.closed {
+            return Poll::Ready(Err(BlockingError::Closed));
+        }
+
+        let need = this.items.as_ref().map_or(0, Vec::len);
+        if state.ring.capacity() - state.ring.len() < need {
+            state
+                .chunk_writers
+                .register_need(&mut this.waiter, need, cx.waker());
+            return Poll::Pending;
+        }
+        let items = this
+            .items
+            .take()
+            .expect("PushChunk опрошен после завершения");
+        state.push_chunk(items);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T> Drop for PushChunk<'_, T> {
+    fn drop(&mut self) {
+        if let Some(id) = self.waiter {
+            let mut state = self.owner.lock();
+            if !state.chunk_writers.remove(id) {
+                state.wake_chunks(); // Разбудили, а мы уходим: место достанется другому
+            }
+        }
+    }
+}
+
+// 11. Будущее порционного чтения
+#[derive(Debug)]
+pub struct PopChunk<'a, T> {
+    owner: &'a AsyncRing<T>,
+    n: usize,
+    waiter: Option<u64>, // Место в очереди порционных читателей
+}
+
+impl<T> Future for PopChunk<'_, T> {
+    type Output = Vec<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Vec<T>> {
+        let this = &mut *self;
+        let mut state = this.owner.lock();
+        if state.ring.len() >= this.n || state.closed {
+            return Poll::Ready(state.pop_chunk(this.n));
+        }
+        state
+            .chunk_readers
+            .register_need(&mut this.waiter, this.n, cx.waker());
+        Poll::Pending
+    }
+}
+
+impl<T> Drop for PopChunk<'_, T> {
+    fn drop(&mut self) {
+        if let Some(id) = self.waiter {
+            let mut state = self.owner.lock();
+            if !state.chunk_readers.remove(id) {
+                state.wake_chunks();
+            }
+        }
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::AsyncRing;
+    use crate::BlockingError;
+    use std::future::Future;
+    use std::pin::{pin, Pin};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+    use std::thread::{self, Thread};
+
+    // Waker, считающий пробуждения
+    #[derive(Default)]
+    struct Counter(AtomicUsize);
+
+    impl Wake for Counter {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn counting() -> (Arc<Counter>, Waker) {
+        let counter = Arc::new(Counter::default());
+        (Arc::clone(&counter), Waker::from(counter))
+    }
+
+    // Простейший исполнитель: паркуем поток до пробуждения
+    struct Unpark(Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let waker = Waker::from(Arc::new(Unpark(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+            thread::park();
+        }
+    }
+
+    #[test]
+    fn test_readers_woken_on_edge_only() {
+        let ring = AsyncRing::new(4);
+        let wakers: Vec<_> = (0..3).map(|_| counting()).collect();
+        let mut readers: Vec<_> = (0..3).map(|_| Box::pin(ring.pop())).collect();
+        for (reader, (_, waker)) in readers.iter_mut().zip(&wakers) {
+            let mut cx = Context::from_waker(waker);
+            assert!(reader.as_mut().poll(&mut cx).is_pending());
+        }
+        let woken = || {
+            wakers
+                .iter()
+                .map(|(c, _)| c.0.load(Ordering::SeqCst))
+                .collect::<Vec<_>>()
+        };
+
+        ring.try_push(1).unwrap();
+        assert_eq!(woken(), [1, 0, 0]); // Пусто -> не пусто: будим одного
+        ring.try_push(2).unwrap();
+        assert_eq!(woken(), [1, 0, 0]); // Не переход - никого не будим
+
+        // Первый забирает своё и передаёт эстафету второму
+        let mut cx = Context::from_waker(&wakers[0].1);
+        assert_eq!(readers[0].as_mut().poll(&mut cx), Poll::Ready(Some(1)));
+        assert_eq!(woken(), [1, 1, 0]);
+    }
+
+    #[test]
+    fn test_writers_woken_on_edge_only() {
+        let ring = AsyncRing::new(1);
+        ring.try_push(0).unwrap();
+        let (first, first_waker) = counting();
+        let (second, second_waker) = counting();
+        let mut writers = [Box::pin(ring.push(1)), Box::pin(ring.push(2))];
+        assert!(writers[0]
+            .as_mut()
+            .poll(&mut Context::from_waker(&first_waker))
+            .is_pending());
+        assert!(writers[1]
+            .as_mut()
+            .poll(&mut Context::from_waker(&second_waker))
+            .is_pending());
+
+        assert_eq!(ring.try_pop(), Some(0)); // Полно -> не полно: будим только первого
+        assert_eq!(first.0.load(Ordering::SeqCst), 1);
+        assert_eq!(second.0.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_cancelled_waiter_passes_wakeup() {
+        let ring = AsyncRing::new(2);
+        let (first, first_waker) = counting();
+        let (second, second_waker) = counting();
+        let mut a = Box::pin(ring.pop());
+        let mut b = Box::pin(ring.pop());
+        assert!(a
+            .as_mut()
+            .poll(&mut Context::from_waker(&first_waker))
+            .is_pending());
+        assert!(b
+            .as_mut()
+            .poll(&mut Context::from_waker(&second_waker))
+            .is_pending());
+
+        ring.try_push(7).unwrap();
+        assert_eq!(first.0.load(Ordering::SeqCst), 1);
+        drop(a); // Разбуженный ушёл, не забрав данные
+        assert_eq!(second.0.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            b.as_mut().poll(&mut Context::from_waker(&second_waker)),
+            Poll::Ready(Some(7))
+        );
+    }
+
+    #[test]
+    fn test_poll_pop_without_future() {
+        let ring = AsyncRing::new(2);
+        let (woken, waker) = counting();
+        let mut cx = Context::from_waker(&waker);
+        assert!(ring.poll_pop(&mut cx).is_pending());
+        assert!(ring.poll_pop(&mut cx).is_pending()); // Повторный опрос не дублирует ожидающего
+
+        ring.try_push(5).unwrap();
+        assert_eq!(woken.0.load(Ordering::SeqCst), 1);
+        assert_eq!(ring.poll_pop(&mut cx), Poll::Ready(Some(5)));
+
+        ring.close();
+        assert_eq!(ring.poll_pop(&mut cx), Poll::Ready(None));
+    }
+
+    #[test]
+    fn test_poll_ready() {
+        let ring = AsyncRing::new(1);
+        let (woken, waker) = counting();
+        let mut cx = Context::from_waker(&waker);
+        assert_eq!(ring.poll_ready(&mut cx), Poll::Ready(Ok(())));
+
+        ring.try_push(1).unwrap();
+        assert!(ring.poll_ready(&mut cx).is_pending());
+        assert_eq!(ring.try_pop(), Some(1));
+        assert_eq!(woken.0.load(Ordering::SeqCst), 1);
+        assert_eq!(ring.poll_ready(&mut cx), Poll::Ready(Ok(())));
+
+        ring.close();
+        assert_eq!(
+            ring.poll_ready(&mut cx),
+            Poll::Ready(Err(BlockingError::Closed))
+        );
+    }
+
+    #[test]
+    fn test_forget_waker_passes_wakeup() {
+        let ring = AsyncRing::new(2);
+        let (_, first_waker) = counting();
+        let (second, second_waker) = counting();
+        assert!(ring
+            .poll_pop(&mut Context::from_waker(&first_waker))
+            .is_pending());
+        assert!(ring
+            .poll_pop(&mut Context::from_waker(&second_waker))
+            .is_pending());
+
+        ring.try_push(7).unwrap(); // Будим первого, а он бросает опрос
+        ring.forget_waker(&first_waker);
+        assert_eq!(second.0.load(Ordering::SeqCst), 1);
+    }
+
+    // Своё будущее поверх poll_pop: сумма двух элементов
+    struct PairSum<'a> {
+        ring: &'a AsyncRing<u32>,
+        first: Option<u32>,
+    }
+
+    impl Future for PairSum<'_> {
+        type Output = Option<u32>;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<u32>> {
+            loop {
+                let value = match self.ring.poll_pop(cx) {
+                    Poll::Ready(Some(value)) => value,
+                    Poll::Ready(None) => return Poll::Ready(None),
+                    Poll::Pending => return Poll::Pending,
+                };
+                match self.first.take() {
+                    Some(first) => return Poll::Ready(Some(first + value)),
+                    None => self.first = Some(value),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_future() {
+        let ring = Arc::new(AsyncRing::new(1));
+        let writer = {
+            let ring = Arc::clone(&ring);
+            thread::spawn(move || {
+                block_on(ring.push(20)).unwrap();
+                block_on(ring.push(22)).unwrap();
+            })
+        };
+        let sum = block_on(PairSum {
+            ring: &ring,
+            first: None,
+        });
+        assert_eq!(sum, Some(42));
+        writer.join().unwrap();
+    }
+
+    #[test]
+    fn test_cancelled_pop_loses_nothing() {
+        let ring = AsyncRing::new(2);
+        let (_, waker) = counting();
+        let mut cx = Context::from_waker(&waker);
+
+        // Отмена до опроса, во время ожидания и после пробуждения
+        drop(ring.pop());
+        let mut pending = Box::pin(ring.pop());
+        assert!(pending.as_mut().poll(&mut cx).is_pending());
+        ring.try_push(1).unwrap();
+        ring.try_push(2).unwrap();
+        drop(pending);
+
+        assert_eq!(ring.len(), 2);
+        assert_eq!(ring.try_pop(), Some(1));
+        assert_eq!(ring.try_pop(), Some(2));
+    }
+
+    #[test]
+    fn test_cancelled_push_returns_value() {
+        let ring = AsyncRing::new(1);
+        ring.try_push(String::from("first")).unwrap();
+        let (_, waker) = counting();
+
+        let mut push = ring.push(String::from("second"));
+        assert!(Pin::new(&mut push)
+            .poll(&mut Context::from_waker(&waker))
+            .is_pending());
+        assert_eq!(push.into_value().as_deref(), Some("second")); // Значение не потеряно
+        assert_eq!(ring.len(), 1);
+
+        let mut push = ring.push(String::from("third"));
+        assert_eq!(ring.try_pop().as_deref(), Some("first"));
+        assert!(Pin::new(&mut push)
+            .poll(&mut Context::from_waker(&waker))
+            .is_ready());
+        assert_eq!(push.into_value(), None); // Уже в кольце
+    }
+
+    #[test]
+    fn test_stats() {
+        let ring = AsyncRing::new(4);
+        block_on(ring.push_chunk(vec![1, 2, 3])).unwrap();
+        assert_eq!(ring.try_pop(), Some(1));
+
+        let stats = ring.stats();
+        assert_eq!((stats.len, stats.high_water), (2, 3));
+        assert_eq!((stats.pushes, stats.pops), (3, 1));
+    }
+
+    #[test]
+    fn test_chunk_waits_for_whole_chunk() {
+        let ring = AsyncRing::new(8);
+        let (woken, waker) = counting();
+        let mut cx = Context::from_waker(&waker);
+        let mut reader = Box::pin(ring.pop_chunk(3));
+        assert!(reader.as_mut().poll(&mut cx).is_pending());
+
+        ring.try_push(1).unwrap();
+        ring.try_push(2).unwrap();
+        assert_eq!(woken.0.load(Ordering::SeqCst), 0); // Порции ещё не хватает
+        ring.try_push(3).unwrap();
+        ring.try_push(4).unwrap();
+        assert_eq!(woken.0.load(Ordering::SeqCst), 1); // Одно пробуждение на порцию
+        assert_eq!(reader.as_mut().poll(&mut cx), Poll::Ready(vec![1, 2, 3]));
+        assert_eq!(ring.len(), 1);
+    }
+
+    #[test]
+    fn test_push_chunk_waits_for_space() {
+        let ring = AsyncRing::new(4);
+        ring.try_push(0).unwrap();
+        ring.try_push(0).unwrap();
+        let (woken, waker) = counting();
+        let mut cx = Context::from_waker(&waker);
+        let mut writer = Box::pin(ring.push_chunk(vec![1, 2, 3]));
+        assert!(writer.as_mut().poll(&mut cx).is_pending());
+
+        assert_eq!(ring.try_pop(), Some(0));
+        assert_eq!(woken.0.load(Ordering::SeqCst), 1);
+        assert_eq!(writer.as_mut().poll(&mut cx), Poll::Ready(Ok(())));
+        assert_eq!(block_on(ring.pop_chunk(4)), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_small_chunk_not_blocked_by_large() {
+        let ring = AsyncRing::new(8);
+        let (large, large_waker) = counting();
+        let (small, small_waker) = counting();
+        let mut big = Box::pin(ring.pop_chunk(6));
+        let mut little = Box::pin(ring.pop_chunk(2));
+        assert!(big
+            .as_mut()
+            .poll(&mut Context::from_waker(&large_waker))
+            .is_pending());
+        assert!(little
+            .as_mut()
+            .poll(&mut Context::from_waker(&small_waker))
+            .is_pending());
+
+        ring.try_push(1).unwrap();
+        ring.try_push(2).unwrap();
+        assert_eq!(large.0.load(Ordering::SeqCst), 0);
+        assert_eq!(small.0.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_chunks_across_threads() {
+        let ring = Arc::new(AsyncRing::new(16));
+        let writer = {
+            let ring = Arc::clone(&ring);
+            thread::spawn(move || {
+                for start in (0..1000).step_by(10) {
+                    block_on(ring.push_chunk((start..start + 10).collect())).unwrap();
+                }
+                ring.close();
+            })
+        };
+
+        let mut received = Vec::new();
+        loop {
+            let chunk = block_on(ring.pop_chunk(7));
+            if chunk.is_empty() {
+                break;
+            }
+            received.extend(chunk);
+        }
+        writer.join().unwrap();
+        assert_eq!(received, (0..1000).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_threads_deliver_everything() {
+        let ring = Arc::new(AsyncRing::new(2));
+        let producers: Vec<_> = (0..3)
+            .map(|p| {
+                let ring = Arc::clone(&ring);
+                thread::spawn(move || {
+                    for i in 0..100 {
+                        block_on(ring.push(p * 1000 + i)).unwrap();
+                    }
+                })
+            })
+            .collect();
+        let consumers: Vec<_> = (0..2)
+            .map(|_| {
+                let ring = Arc::clone(&ring);
+                thread::spawn(move || {
+                    let mut received = Vec::new();
+                    while let Some(value) = block_on(ring.pop()) {
+                        received.push(value);
+                    }
+                    received
+                })
+            })
+            .collect();
+
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        ring.close();
+        let mut all: Vec<_> = consumers
+            .into_iter()
+            .flat_map(|c| c.join().unwrap())
+            .collect();
+        all.sort();
+        let mut expected: Vec<_> = (0..3)
+            .flat_map(|p| (0..100).map(move |i| p * 1000 + i))
+            .collect();
+        expected.sort();
+        assert_eq!(all, expected);
+    }
+}
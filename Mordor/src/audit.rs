@@ -0,0 +1,121 @@
+// Журнал последних операций над RingBuffer (возможность audit): помогает восстановить,
+// как буфер пришёл в своё состояние, когда к отчёту об ошибке приложен только он сам
+
+use core::fmt;
+
+use crate::RingBuffer;
+
+// 1. Вид операции
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditOp {
+    Push,     // push, push_overwrite, push_with
+    PushAll,  // Транзакционная запись
+    Fill,     // fill_with, fill_while
+    Extend,   // Запись среза
+    Pop,      // Чтение из головы
+    PopBack,  // Чтение из хвоста
+    Insert,   // Вставка по индексу
+    Remove,   // Удаление по индексу
+    Retain,   // Отбор по условию
+    Drain,    // Чтение нескольких элементов
+    Skip,     // Пропуск без возврата
+    Truncate, // truncate_front, truncate_back
+    Clear,    // Очистка
+    Resize,   // Смена ёмкости
+}
+
+// 1.1. Запись журнала: операция, сколько элементов она затронула и размер после неё
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuditEntry {
+    pub op: AuditOp,
+    pub count: usize,
+    pub len: usize,
+}
+
+impl fmt::Display for AuditEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} x{} -> {}", self.op, self.count, self.len)
+    }
+}
+
+// 2. Журнал - своё кольцо с вытеснением самых старых записей
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    entries: RingBuffer<AuditEntry>,
+}
+
+impl AuditLog {
+    // 2.1. Журнал на depth последних операций
+    pub fn new(depth: usize) -> Self {
+        AuditLog {
+            entries: RingBuffer::new(depth),
+        }
+    }
+
+    pub(crate) fn record(&mut self, op: AuditOp, count: usize, len: usize) {
+        self.entries.push_overwrite(AuditEntry { op, count, len });
+    }
+
+    // 3. Записи от самой старой к самой новой
+    pub fn iter(&self) -> impl Iterator<Item = &AuditEntry> + '_ {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // 4. Память журнала в куче
+    pub fn heap_bytes(&self) -> usize {
+        self.entries.heap_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AuditEntry, AuditOp};
+    use crate::RingBuffer;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_records_operations() {
+        let mut rb = RingBuffer::builder().capacity(4).with_audit(8).build();
+        assert!(rb.push(1).is_ok());
+        rb.extend(&[2, 3, 4]);
+        assert_eq!(rb.drain(3).len(), 3);
+        rb.retain(|&v| v != 4);
+
+        let log: Vec<_> = rb.audit_log().unwrap().iter().copied().collect();
+        let entry = |op, count, len| AuditEntry { op, count, len };
+        assert_eq!(
+            log,
+            [
+                entry(AuditOp::Push, 1, 1),
+                entry(AuditOp::Extend, 3, 4), // Составные операции - одной записью
+                entry(AuditOp::Drain, 3, 1),
+                entry(AuditOp::Retain, 1, 0),
+            ]
+        );
+        assert_eq!(log[1].to_string(), "Extend x3 -> 4");
+    }
+
+    #[test]
+    fn test_keeps_last_entries() {
+        let mut rb = RingBuffer::builder().capacity(2).with_audit(3).build();
+        for i in 0..10 {
+            rb.push_overwrite(i);
+        }
+        rb.clear();
+
+        let log = rb.audit_log().unwrap();
+        assert_eq!(log.len(), 3);
+        let ops: Vec<_> = log.iter().map(|e| e.op).collect();
+        assert_eq!(ops, [AuditOp::Push, AuditOp::Push, AuditOp::Clear]);
+        assert!(RingBuffer::<u8>::new(2).audit_log().is_none()); // По умолчанию выключен
+    }
+}
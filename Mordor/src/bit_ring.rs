@@ -0,0 +1,188 @@
+// Кольцо битов: по 8 флагов в байте (RingBuffer<bool> тратит байт на флаг) -
+// для протоколов с битовыми полями и фронтов сигналов. Биты в байте идут
+// от младшего к старшему
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::ring::full_error;
+
+// 1. Упакованное хранилище и логическое окно [head, head + len) в битах
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitRing {
+    bytes: Vec<u8>,
+    capacity: usize, // В битах
+    head: usize,
+    len: usize,
+}
+
+impl BitRing {
+    // 1.1. Кольцо на capacity битов
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "В буфере есть место!");
+
+        BitRing {
+            bytes: vec![0; capacity.div_ceil(8)],
+            capacity,
+            head: 0,
+            len: 0,
+        }
+    }
+
+    // 2. Размеры
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    // 2.1. Память в куче
+    pub fn heap_bytes(&self) -> usize {
+        self.bytes.capacity()
+    }
+
+    // 3. Запись одного бита
+    pub fn push(&mut self, bit: bool) -> Result<(), String> {
+        if self.is_full() {
+            return Err(full_error());
+        }
+
+        self.set(self.wrap(self.head + self.len), bit);
+        self.len += 1;
+        Ok(())
+    }
+
+    // 3.1. Запись первых count битов из bytes (от младшего бита первого байта);
+    // возвращает, сколько битов поместилось
+    pub fn extend_from_bits(&mut self, bytes: &[u8], count: usize) -> usize {
+        assert!(count <= bytes.len() * 8, "Битов меньше, чем count!");
+
+        let n = count.min(self.capacity - self.len);
+        for i in 0..n {
+            let _ = self.push(bytes[i / 8] >> (i % 8) & 1 == 1); // Место есть
+        }
+        n
+    }
+
+    // 4. Чтение самого старого бита
+    pub fn pop(&mut self) -> Option<bool> {
+        let bit = self.get(0)?;
+        self.head = self.wrap(self.head + 1);
+        self.len -= 1;
+        Some(bit)
+    }
+
+    // 4.1. Чтение до out.len() * 8 битов упакованными в out (как в extend_from_bits);
+    // возвращает, сколько битов прочитано. Хвост последнего байта обнуляется
+    pub fn drain_into_bits(&mut self, out: &mut [u8]) -> usize {
+        let n = self.len.min(out.len() * 8);
+        out[..n.div_ceil(8)].fill(0);
+        for i in 0..n {
+            if self.pop() == Some(true) {
+                out[i / 8] |= 1 << (i % 8);
+            }
+        }
+        n
+    }
+
+    // 5. Бит по логическому индексу (0 - самый старый)
+    pub fn get(&self, index: usize) -> Option<bool> {
+        if index >= self.len {
+            return None;
+        }
+        let pos = self.wrap(self.head + index);
+        Some(self.bytes[pos / 8] >> (pos % 8) & 1 == 1)
+    }
+
+    // 5.1. Биты от старых к новым
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).filter_map(move |index| self.get(index))
+    }
+
+    // 5.2. Сколько единиц в окне
+    pub fn count_ones(&self) -> usize {
+        self.iter().filter(|&bit| bit).count()
+    }
+
+    pub fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+    }
+
+    // 6. Установка бита в хранилище по физической позиции
+    fn set(&mut self, pos: usize, bit: bool) {
+        let mask = 1 << (pos % 8);
+        if bit {
+            self.bytes[pos / 8] |= mask;
+        } else {
+            self.bytes[pos / 8] &= !mask;
+        }
+    }
+
+    #[inline]
+    fn wrap(&self, pos: usize) -> usize {
+        if pos >= self.capacity {
+            pos - self.capacity
+        } else {
+            pos
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BitRing;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_push_pop() {
+        let mut ring = BitRing::new(10);
+        for bit in [true, false, true] {
+            ring.push(bit).unwrap();
+        }
+        assert_eq!(ring.pop(), Some(true));
+        assert_eq!(ring.get(1), Some(true));
+        assert_eq!(ring.len(), 2);
+        assert_eq!(ring.heap_bytes(), 2); // 10 битов в двух байтах
+    }
+
+    #[test]
+    fn test_bits_across_seam() {
+        let mut ring = BitRing::new(12);
+        assert_eq!(ring.extend_from_bits(&[0xFF], 8), 8);
+        let mut skip = [0; 1];
+        assert_eq!(ring.drain_into_bits(&mut skip), 8);
+
+        // 0b1010_0101, затем 0b11: 10 битов, с 8-го по 12-й и с начала хранилища
+        assert_eq!(ring.extend_from_bits(&[0xA5, 0x03], 10), 10);
+        assert!(ring.push(true).is_ok());
+        assert!(ring.push(true).is_ok());
+        assert!(ring.push(true).is_err()); // Полон
+        assert_eq!(ring.count_ones(), 8);
+
+        let mut out = [0xFF; 2];
+        assert_eq!(ring.drain_into_bits(&mut out), 12);
+        assert_eq!(out, [0xA5, 0x0F]);
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    fn test_iter() {
+        let mut ring = BitRing::new(4);
+        assert_eq!(ring.extend_from_bits(&[0b0110_1001], 8), 4); // Поместилось 4
+        assert_eq!(ring.iter().collect::<Vec<_>>(), [true, false, false, true]);
+        ring.clear();
+        assert_eq!(ring.pop(), None);
+    }
+}
@@ -0,0 +1,649 @@
+// Потокобезопасное блокирующее кольцо (много писателей, много читателей)
+
+use core::fmt;
+#[cfg(all(feature = "eventfd", target_os = "linux"))]
+use std::io;
+#[cfg(all(feature = "eventfd", target_os = "linux"))]
+use std::os::fd::{AsFd, BorrowedFd};
+use std::sync::PoisonError;
+use std::time::Instant;
+
+use crate::shim::{Arc, Condvar, Mutex, MutexGuard};
+#[cfg(all(feature = "eventfd", target_os = "linux"))]
+use crate::EventFd;
+use crate::{RingBuffer, StatsSnapshot};
+
+// 0. Ошибки операций над кольцом
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockingError {
+    Full,          // Нет места (только для try_push)
+    Closed,        // Кольцо закрыто, запись запрещена
+    Poisoned,      // Поток запаниковал, удерживая блокировку
+    QuotaExceeded, // Писатель уже занял свою долю ёмкости
+    Timeout,       // Срок ожидания истёк
+}
+
+impl fmt::Display for BlockingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlockingError::Full => write!(f, "Буфер Заполнен!"),
+            BlockingError::Closed => write!(f, "Кольцо закрыто!"),
+            BlockingError::Poisoned => write!(f, "Блокировка отравлена паникой!"),
+            BlockingError::QuotaExceeded => write!(f, "Писатель исчерпал свою долю!"),
+            BlockingError::Timeout => write!(f, "Время ожидания истекло!"),
+        }
+    }
+}
+
+impl core::error::Error for BlockingError {}
+
+// 1. Кольцо под мьютексом и условные переменные для ожидания
+#[derive(Debug)]
+pub struct BlockingRing<T> {
+    state: Mutex<State<T>>,
+    not_empty: Condvar, // Будим читателей, когда появились данные
+    not_full: Condvar,  // Будим писателей, когда освободилось место
+    #[cfg(all(feature = "eventfd", target_os = "linux"))]
+    event: Option<EventFd>, // Дескриптор для epoll: читаем, пока есть данные или кольцо закрыто
+}
+
+#[derive(Debug)]
+struct State<T> {
+    ring: RingBuffer<Entry<T>>,
+    closed: bool,          // После закрытия запись запрещена, чтение дочитывает остаток
+    in_flight: Vec<usize>, // Сколько непрочитанных элементов у каждого писателя с долей
+    next_ticket: u64,      // Номер для следующего ждущего писателя
+    serving: u64,          // Чей номер сейчас может писать
+    abandoned: Vec<u64>,   // Номера писателей, которые ушли по сроку, не дождавшись очереди
+}
+
+// 1.1. Элемент и писатель с долей, который его записал
+#[derive(Debug)]
+struct Entry<T> {
+    owner: Option<usize>,
+    value: T,
+}
+
+impl<T> State<T> {
+    // 1.2. Чтение с возвратом доли писателю
+    fn take(&mut self) -> Option<T> {
+        let entry = self.ring.pop()?;
+        if let Some(owner) = entry.owner {
+            self.in_flight[owner] -= 1;
+        }
+        Some(entry.value)
+    }
+
+    // 1.3. Сколько писателей стоит в очереди за местом
+    fn queued(&self) -> u64 {
+        self.next_ticket - self.serving
+    }
+
+    // 1.3.1. Очередь переходит к следующему номеру, пропуская ушедших
+    fn advance_serving(&mut self) {
+        self.serving += 1;
+        while let Some(i) = self.abandoned.iter().position(|&t| t == self.serving) {
+            self.abandoned.swap_remove(i);
+            self.serving += 1;
+        }
+    }
+
+    // 1.3.2. Писатель с номером ticket уходит по сроку
+    fn abandon(&mut self, ticket: u64) {
+        if self.serving == ticket {
+            self.advance_serving();
+        } else {
+            self.abandoned.push(ticket);
+        }
+    }
+}
+
+// 1.3.3. Сколько ждать: не ждать, без срока или до момента времени
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Wait {
+    No,
+    Forever,
+    Until(Instant),
+}
+
+impl Wait {
+    fn deadline(self) -> Option<Instant> {
+        match self {
+            Wait::Until(deadline) => Some(deadline),
+            _ => None,
+        }
+    }
+
+    fn expired(self) -> bool {
+        self.deadline()
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+impl<T> BlockingRing<T> {
+    // 1.4. Создаём кольцо заданного размера
+    pub fn new(capacity: usize) -> Self {
+        BlockingRing {
+            state: Mutex::new(State {
+                ring: RingBuffer::builder()
+                    .capacity(capacity)
+                    .with_stats()
+                    .build(),
+                closed: false,
+                in_flight: Vec::new(),
+                next_ticket: 0,
+                serving: 0,
+                abandoned: Vec::new(),
+            }),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+            #[cfg(all(feature = "eventfd", target_os = "linux"))]
+            event: None,
+        }
+    }
+
+    // 1.4.1. Кольцо с eventfd для циклов событий (epoll, mio)
+    #[cfg(all(feature = "eventfd", target_os = "linux"))]
+    pub fn with_event_fd(capacity: usize) -> io::Result<Self> {
+        Ok(BlockingRing {
+            event: Some(EventFd::new()?),
+            ..Self::new(capacity)
+        })
+    }
+
+    // 1.4.2. Дескриптор уведомлений, если кольцо создано с ним
+    #[cfg(all(feature = "eventfd", target_os = "linux"))]
+    pub fn event_fd(&self) -> Option<BorrowedFd<'_>> {
+        self.event.as_ref().map(|event| event.as_fd())
+    }
+
+    // 2. Запись с ожиданием свободного места
+    pub fn push(&self, value: T) -> Result<(), BlockingError> {
+        self.insert(value, None, Wait::Forever).map_err(|(e, _)| e)
+    }
+
+    // 2.1. Запись с ожиданием места не дольше deadline; по сроку - Timeout
+    pub fn push_deadline(&self, value: T, deadline: Instant) -> Result<(), BlockingError> {
+        self.insert(value, None, Wait::Until(deadline))
+            .map_err(|(e, _)| e)
+    }
+
+    // 3. Запись без ожидания
+    pub fn try_push(&self, value: T) -> Result<(), BlockingError> {
+        self.insert(value, None, Wait::No).map_err(|(e, _)| e)
+    }
+
+    // 3.0.1. Запись, которая при ошибке возвращает значение (для каналов)
+    pub(crate) fn push_or_return(&self, value: T, wait: Wait) -> Result<(), (BlockingError, T)> {
+        self.insert(value, None, wait)
+    }
+
+    // 3.1. Общая запись; quota - (номер писателя, его доля), wait - ждать ли места и до какого срока.
+    // Ждущие писатели встают в очередь по номерам и пишут строго в порядке прихода,
+    // новый писатель не обгоняет очередь, даже если место уже есть. При ошибке значение
+    // возвращается вызывающему
+    fn insert(
+        &self,
+        value: T,
+        quota: Option<(usize, usize)>,
+        wait: Wait,
+    ) -> Result<(), (BlockingError, T)> {
+        let mut state = match self.lock() {
+            Ok(state) => state,
+            Err(e) => return Err((e, value)),
+        };
+        if state.closed {
+            return Err((BlockingError::Closed, value));
+        }
+        if let Err(e) = Self::check_quota(&state, quota) {
+            return Err((e, value));
+        }
+
+        if state.ring.is_full() || state.queued() > 0 {
+            if let Wait::No = wait {
+                return Err((BlockingError::Full, value));
+            }
+
+            // 3.1.1. Берём номер и ждём своей очереди и свободного места.
+            // По сроку уходим, отдав свой номер, чтобы не задержать очередь
+            let ticket = state.next_ticket;
+            state.next_ticket += 1;
+            while !state.closed && (state.serving != ticket || state.ring.is_full()) {
+                if wait.expired() {
+                    state.abandon(ticket);
+                    self.not_full.notify_all();
+                    return Err((BlockingError::Timeout, value));
+                }
+                state = match Self::wait_on(&self.not_full, state, wait.deadline()) {
+                    Ok(state) => state,
+                    Err(e) => return Err((e, value)),
+                };
+            }
+            if state.closed {
+                return Err((BlockingError::Closed, value));
+            }
+
+            // 3.1.2. Передаём очередь следующему, даже если сами не запишем
+            state.advance_serving();
+            if state.queued() > 0 {
+                self.not_full.notify_all();
+            }
+            // Пока ждали, тот же писатель мог записать из другого потока
+            if let Err(e) = Self::check_quota(&state, quota) {
+                return Err((e, value));
+            }
+        }
+
+        let owner = quota.map(|(owner, _)| owner);
+        if let Some(owner) = owner {
+            state.in_flight[owner] += 1;
+        }
+        let _ = state.ring.push(Entry { owner, value }); // Место проверено выше
+        self.not_empty.notify_one();
+        self.signal(&state);
+        Ok(())
+    }
+
+    // 3.2. Сверх доли не ждём: своё место занял сам писатель
+    fn check_quota(state: &State<T>, quota: Option<(usize, usize)>) -> Result<(), BlockingError> {
+        match quota {
+            Some((owner, limit)) if state.in_flight[owner] >= limit => {
+                Err(BlockingError::QuotaExceeded)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // 4. Чтение с ожиданием; Ok(None) - кольцо закрыто и пусто
+    pub fn pop(&self) -> Result<Option<T>, BlockingError> {
+        self.take_wait(None)
+    }
+
+    // 4.1. Чтение с ожиданием не дольше deadline; по сроку - Timeout
+    pub fn pop_deadline(&self, deadline: Instant) -> Result<Option<T>, BlockingError> {
+        self.take_wait(Some(deadline))
+    }
+
+    fn take_wait(&self, deadline: Option<Instant>) -> Result<Option<T>, BlockingError> {
+        let mut state = self.lock()?;
+        while state.ring.is_empty() && !state.closed {
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return Err(BlockingError::Timeout);
+            }
+            state = Self::wait_on(&self.not_empty, state, deadline)?; // Ждём данных
+        }
+
+        let value = state.take();
+        self.wake_writers(&state, value.is_some());
+        self.signal(&state);
+        Ok(value)
+    }
+
+    // 5. Чтение без ожидания; Ok(None) - кольцо пусто
+    pub fn try_pop(&self) -> Result<Option<T>, BlockingError> {
+        let mut state = self.lock()?;
+        let value = state.take();
+        self.wake_writers(&state, value.is_some());
+        self.signal(&state);
+        Ok(value)
+    }
+
+    // 5.1. Будим всю очередь писателей: Condvar будит в произвольном порядке,
+    // а писать должен только первый по номеру
+    fn wake_writers(&self, state: &State<T>, freed: bool) {
+        if freed && state.queued() > 0 {
+            self.not_full.notify_all();
+        }
+    }
+
+    // 5.2. Готовность для цикла событий: дескриптор читаем, пока есть что читать
+    // (или узнать о закрытии). Вызывается под блокировкой
+    #[cfg_attr(
+        not(all(feature = "eventfd", target_os = "linux")),
+        allow(unused_variables)
+    )]
+    fn signal(&self, state: &State<T>) {
+        #[cfg(all(feature = "eventfd", target_os = "linux"))]
+        if let Some(event) = &self.event {
+            event.set_readable(!state.ring.is_empty() || state.closed);
+        }
+    }
+
+    // 6. Закрытие: будим всех, чтобы они увидели флаг.
+    // Работает и после отравления, чтобы ожидающие потоки не зависли навсегда
+    pub fn close(&self) {
+        {
+            let mut state = self.lock_anyway();
+            state.closed = true;
+            self.signal(&state);
+        }
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.lock_anyway().closed
+    }
+
+    // 6.1. Состояние здоровья: запаниковал ли кто-то под блокировкой
+    pub fn is_poisoned(&self) -> bool {
+        self.lock().is_err()
+    }
+
+    // 7. Размеры (моментальный снимок, доступен и после отравления)
+    pub fn len(&self) -> usize {
+        self.lock_anyway().ring.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock_anyway().ring.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.lock_anyway().ring.capacity()
+    }
+
+    // 7.1. Счётчики и размеры одним снимком под блокировкой
+    pub fn stats(&self) -> StatsSnapshot {
+        let state = self.lock_anyway();
+        state.ring.stats_snapshot().unwrap_or_default()
+    }
+
+    // 8. Блокировка: отравление превращается в типизированную ошибку
+    fn lock(&self) -> Result<MutexGuard<'_, State<T>>, BlockingError> {
+        self.state.lock().map_err(|_| BlockingError::Poisoned)
+    }
+
+    // 8.0.1. Ожидание на условной переменной, не дольше срока (если он есть)
+    fn wait_on<'a>(
+        condvar: &Condvar,
+        state: MutexGuard<'a, State<T>>,
+        deadline: Option<Instant>,
+    ) -> Result<MutexGuard<'a, State<T>>, BlockingError> {
+        match deadline {
+            None => condvar.wait(state).map_err(|_| BlockingError::Poisoned),
+            Some(deadline) => {
+                let left = deadline.saturating_duration_since(Instant::now());
+                condvar
+                    .wait_timeout(state, left)
+                    .map(|(state, _)| state)
+                    .map_err(|_| BlockingError::Poisoned)
+            }
+        }
+    }
+
+    // 8.1. Явное восстановление для операций, которые только читают флаги и размеры
+    fn lock_anyway(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+// 9. Писатель с долей ёмкости: не больше quota непрочитанных элементов,
+// чтобы один болтливый писатель не вытеснил остальных
+#[derive(Debug)]
+pub struct QuotaProducer<T> {
+    ring: Arc<BlockingRing<T>>,
+    id: usize,
+    quota: usize,
+}
+
+impl<T> QuotaProducer<T> {
+    // 9.1. Регистрируем нового писателя кольца
+    pub fn new(ring: &Arc<BlockingRing<T>>, quota: usize) -> Self {
+        assert!(quota > 0, "Доля писателя должна быть положительной!");
+
+        let mut state = ring.lock_anyway();
+        state.in_flight.push(0);
+        QuotaProducer {
+            ring: Arc::clone(ring),
+            id: state.in_flight.len() - 1,
+            quota,
+        }
+    }
+
+    // 9.2. Запись с ожиданием места в кольце; сверх доли - QuotaExceeded сразу
+    pub fn push(&self, value: T) -> Result<(), BlockingError> {
+        self.ring
+            .insert(value, Some((self.id, self.quota)), Wait::Forever)
+            .map_err(|(e, _)| e)
+    }
+
+    pub fn try_push(&self, value: T) -> Result<(), BlockingError> {
+        self.ring
+            .insert(value, Some((self.id, self.quota)), Wait::No)
+            .map_err(|(e, _)| e)
+    }
+
+    // 9.3. Сколько записанных этим писателем элементов ещё не прочитано
+    pub fn in_flight(&self) -> usize {
+        self.ring.lock_anyway().in_flight[self.id]
+    }
+
+    pub fn quota(&self) -> usize {
+        self.quota
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::{BlockingError, BlockingRing, QuotaProducer};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_try_operations() {
+        let ring = BlockingRing::new(1);
+
+        assert!(ring.try_push(1).is_ok());
+        assert_eq!(ring.try_push(2), Err(BlockingError::Full));
+        assert_eq!(ring.try_pop(), Ok(Some(1)));
+        assert_eq!(ring.try_pop(), Ok(None));
+    }
+
+    #[test]
+    fn test_producer_consumer() {
+        let ring = Arc::new(BlockingRing::new(2));
+        let producer = {
+            let ring = Arc::clone(&ring);
+            thread::spawn(move || {
+                for i in 0..100 {
+                    ring.push(i).unwrap(); // Блокируется, пока читатель не освободит место
+                }
+                ring.close();
+            })
+        };
+
+        let mut received = Vec::new();
+        while let Some(value) = ring.pop().unwrap() {
+            received.push(value);
+        }
+        producer.join().unwrap();
+
+        assert_eq!(received, (0..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_close_keeps_remaining() {
+        let ring = BlockingRing::new(3);
+        ring.push(1).unwrap();
+        ring.close();
+
+        assert_eq!(ring.push(2), Err(BlockingError::Closed));
+        assert_eq!(ring.pop(), Ok(Some(1))); // Остаток дочитывается
+        assert_eq!(ring.pop(), Ok(None));
+    }
+
+    #[test]
+    fn test_stats() {
+        let ring = BlockingRing::new(2);
+        for i in 0..3 {
+            ring.push(i).unwrap();
+            assert_eq!(ring.pop(), Ok(Some(i)));
+        }
+        ring.push(3).unwrap();
+
+        let stats = ring.stats();
+        assert_eq!((stats.len, stats.capacity, stats.high_water), (1, 2, 1));
+        assert_eq!((stats.pushes, stats.pops, stats.laps), (4, 3, 2));
+    }
+
+    #[test]
+    fn test_quota() {
+        let ring = Arc::new(BlockingRing::new(4));
+        let chatty = QuotaProducer::new(&ring, 2);
+        let quiet = QuotaProducer::new(&ring, 2);
+
+        assert!(chatty.push(1).is_ok());
+        assert!(chatty.push(2).is_ok());
+        assert_eq!(chatty.push(3), Err(BlockingError::QuotaExceeded)); // Не ждёт
+        assert!(quiet.try_push(10).is_ok()); // Места остальным хватает
+        assert_eq!(chatty.in_flight(), 2);
+
+        assert_eq!(ring.pop(), Ok(Some(1)));
+        assert_eq!(chatty.in_flight(), 1); // Доля вернулась после чтения
+        assert!(chatty.try_push(3).is_ok());
+        assert!(ring.try_push(20).is_ok()); // Обычная запись долями не ограничена
+        assert_eq!(quiet.try_push(11), Err(BlockingError::Full));
+    }
+
+    #[test]
+    fn test_writers_wake_in_arrival_order() {
+        let ring = Arc::new(BlockingRing::new(1));
+        ring.push(0).unwrap();
+
+        // Писатели встают в очередь строго по одному, в известном порядке
+        let mut writers = Vec::new();
+        for i in 1..=8 {
+            let r = Arc::clone(&ring);
+            writers.push(thread::spawn(move || r.push(i)));
+            while ring.lock_anyway().queued() < i as u64 {
+                thread::yield_now();
+            }
+        }
+
+        // Каждый освобождённый слот достаётся следующему по очереди
+        for i in 0..=8 {
+            assert_eq!(ring.pop(), Ok(Some(i)));
+        }
+        for writer in writers {
+            assert!(writer.join().unwrap().is_ok());
+        }
+    }
+
+    #[test]
+    fn test_try_push_does_not_jump_queue() {
+        let ring = Arc::new(BlockingRing::new(1));
+        ring.push(1).unwrap();
+
+        let r = Arc::clone(&ring);
+        let writer = thread::spawn(move || r.push(2));
+        while ring.lock_anyway().queued() == 0 {
+            thread::yield_now();
+        }
+
+        let mut state = ring.lock_anyway();
+        state.take(); // Место есть, но ждущий писатель ещё не проснулся
+        drop(state);
+        assert_eq!(ring.try_push(3), Err(BlockingError::Full));
+        ring.wake_writers(&ring.lock_anyway(), true);
+
+        assert!(writer.join().unwrap().is_ok());
+        assert_eq!(ring.pop(), Ok(Some(2)));
+    }
+
+    #[test]
+    fn test_deadlines() {
+        let ring = BlockingRing::new(1);
+        let soon = Instant::now() + Duration::from_millis(10);
+        assert_eq!(ring.pop_deadline(soon), Err(BlockingError::Timeout));
+
+        ring.push(1).unwrap();
+        assert_eq!(ring.push_deadline(2, soon), Err(BlockingError::Timeout));
+        assert_eq!(ring.len(), 1);
+    }
+
+    #[test]
+    fn test_expired_writer_leaves_queue() {
+        let ring = Arc::new(BlockingRing::new(1));
+        ring.push(0).unwrap();
+
+        // Первый в очереди уходит по сроку, второй ждёт без срока
+        let r = Arc::clone(&ring);
+        let hasty =
+            thread::spawn(move || r.push_deadline(1, Instant::now() + Duration::from_millis(20)));
+        while ring.lock_anyway().queued() < 1 {
+            thread::yield_now();
+        }
+        let r = Arc::clone(&ring);
+        let patient = thread::spawn(move || r.push(2));
+
+        assert_eq!(hasty.join().unwrap(), Err(BlockingError::Timeout));
+        assert_eq!(ring.pop(), Ok(Some(0)));
+        assert_eq!(ring.pop(), Ok(Some(2))); // Брошенный билет очередь не держит
+        assert!(patient.join().unwrap().is_ok());
+    }
+
+    #[test]
+    fn test_poisoned() {
+        let ring = Arc::new(BlockingRing::new(2));
+        ring.push(1).unwrap();
+
+        let panicker = {
+            let ring = Arc::clone(&ring);
+            thread::spawn(move || {
+                let _state = ring.state.lock().unwrap();
+                panic!("Паника под блокировкой");
+            })
+        };
+        assert!(panicker.join().is_err());
+
+        assert!(ring.is_poisoned());
+        assert_eq!(ring.push(2), Err(BlockingError::Poisoned));
+        assert_eq!(ring.try_pop(), Err(BlockingError::Poisoned));
+        assert_eq!(ring.len(), 1); // Снимки размеров по-прежнему доступны
+
+        ring.close();
+        assert!(ring.is_closed());
+    }
+}
+
+// Проверка чередований под мьютексом: RUSTFLAGS="--cfg loom" cargo test --release blocking
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::BlockingRing;
+    use loom::sync::Arc;
+    use loom::thread;
+
+    #[test]
+    fn test_blocking_push_pop() {
+        loom::model(|| {
+            let ring = Arc::new(BlockingRing::new(1));
+            let producer = {
+                let ring = Arc::clone(&ring);
+                thread::spawn(move || {
+                    ring.push(1).unwrap();
+                    ring.push(2).unwrap(); // Ждёт, пока читатель заберёт первый
+                })
+            };
+
+            assert_eq!(ring.pop(), Ok(Some(1)));
+            assert_eq!(ring.pop(), Ok(Some(2)));
+            producer.join().unwrap();
+        });
+    }
+
+    #[test]
+    fn test_close_wakes_reader() {
+        loom::model(|| {
+            let ring = Arc::new(BlockingRing::<u8>::new(1));
+            let closer = {
+                let ring = Arc::clone(&ring);
+                thread::spawn(move || ring.close())
+            };
+
+            assert_eq!(ring.pop(), Ok(None)); // Не зависает навсегда
+            closer.join().unwrap();
+        });
+    }
+}
@@ -0,0 +1,152 @@
+// Построитель кольцевого буфера с дополнительными настройками
+
+use core::marker::PhantomData;
+
+#[cfg(feature = "audit")]
+use crate::AuditLog;
+use crate::{CapacityAdvisor, OverflowPolicy, RingBuffer, Stats};
+use alloc::boxed::Box;
+
+// 1. Накопленные настройки будущего буфера
+#[derive(Debug, Clone)]
+pub struct RingBufferBuilder<T> {
+    capacity: Option<usize>,
+    policy: OverflowPolicy,
+    stats: bool,
+    advisor: bool,
+    auto_grow: Option<usize>,
+    #[cfg(feature = "audit")]
+    audit: Option<usize>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> RingBufferBuilder<T> {
+    // 1.1. Пустой набор настроек
+    pub fn new() -> Self {
+        RingBufferBuilder {
+            capacity: None,
+            policy: OverflowPolicy::Reject,
+            stats: false,
+            advisor: false,
+            auto_grow: None,
+            #[cfg(feature = "audit")]
+            audit: None,
+            _marker: PhantomData,
+        }
+    }
+
+    // 2. Ёмкость буфера (обязательна)
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = Some(capacity);
+        self
+    }
+
+    // 3. Поведение при переполнении
+    pub fn policy(mut self, policy: OverflowPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    // 4. Включить счётчики операций
+    pub fn with_stats(mut self) -> Self {
+        self.stats = true;
+        self
+    }
+
+    // 4.0.1. Включить гистограмму заполнения и suggested_capacity
+    pub fn with_advisor(mut self) -> Self {
+        self.advisor = true;
+        self
+    }
+
+    // 4.0.2. Расширять заполненный буфер до рекомендуемой ёмкости, но не больше
+    // max_capacity (включает советчик); дальше действует политика переполнения
+    pub fn auto_grow(mut self, max_capacity: usize) -> Self {
+        self.auto_grow = Some(max_capacity);
+        self
+    }
+
+    // 4.1. Включить журнал последних depth операций
+    #[cfg(feature = "audit")]
+    pub fn with_audit(mut self, depth: usize) -> Self {
+        self.audit = Some(depth);
+        self
+    }
+
+    // 5. Создание буфера по настройкам
+    pub fn build(self) -> RingBuffer<T> {
+        let capacity = self.capacity.expect("Не задана ёмкость буфера!");
+
+        let mut ring = RingBuffer::new(capacity);
+        ring.policy = self.policy;
+        ring.stats = self.stats.then(Stats::default);
+        if self.advisor || self.auto_grow.is_some() {
+            ring.advisor = Some(Box::new(CapacityAdvisor::new(capacity, self.auto_grow)));
+        }
+        #[cfg(feature = "audit")]
+        {
+            ring.audit = self.audit.map(|depth| Box::new(AuditLog::new(depth)));
+        }
+        ring
+    }
+}
+
+impl<T> Default for RingBufferBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{OverflowPolicy, RingBuffer, Stats};
+
+    #[test]
+    fn test_defaults_match_new() {
+        let mut rb = RingBuffer::builder().capacity(2).build();
+
+        assert_eq!(rb.policy(), OverflowPolicy::Reject);
+        assert!(rb.stats().is_none());
+        assert!(rb.push(1).is_ok());
+        assert!(rb.push(2).is_ok());
+        assert!(rb.push(3).is_err());
+    }
+
+    #[test]
+    fn test_overwrite_policy() {
+        let mut rb = RingBuffer::builder()
+            .capacity(2)
+            .policy(OverflowPolicy::Overwrite)
+            .build();
+
+        assert_eq!(rb.extend(&[1, 2, 3]), 3); // Все записи успешны
+        assert_eq!(rb.drain(2), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut rb = RingBuffer::builder()
+            .capacity(3)
+            .policy(OverflowPolicy::Overwrite)
+            .with_stats()
+            .build();
+
+        rb.extend(&[1, 2, 3, 4]);
+        rb.pop();
+
+        let expected = Stats {
+            pushes: 4,
+            pops: 1,
+            overwritten: 1,
+            high_water: 3,
+            laps: 1,
+        };
+        assert_eq!(rb.stats(), Some(&expected));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_missing_capacity() {
+        let _ = RingBuffer::<u8>::builder().build();
+    }
+}
@@ -0,0 +1,1352 @@
+// Байтовое кольцо с непрерывным хранилищем: содержимое и свободное место
+// выдаются срезами, поэтому байты можно копировать и кодировать без посредников
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::mem::MaybeUninit;
+use core::ops::{Deref, DerefMut, Range};
+
+use crate::checksum;
+use crate::partial::PartialWrite;
+use crate::ring::full_error;
+
+// 1. Хранилище и логическое окно [head, head + len) с закольцовыванием.
+// Хранилище не обнуляется при создании: байты [0, init) хоть раз записаны,
+// остальные обнуляются лениво, когда их впервые выдают как &mut [u8]
+#[derive(Debug, Clone)]
+pub struct ByteRing {
+    buf: Storage, // Хранилище (байты вне окна не имеют смысла)
+    head: usize,  // Индекс самого старого байта
+    len: usize,   // Сколько байт записано
+    init: usize,  // Граница инициализированной части хранилища
+}
+
+impl ByteRing {
+    // 1.1. Создаём кольцо на capacity байт
+    pub fn new(capacity: usize) -> Self {
+        Self::with_alignment(capacity, 1)
+    }
+
+    // 1.2. Хранилище начинается на адресе, кратном align (степень двойки, например 32
+    // или 64 для DMA). Ёмкость кратна align, поэтому выровнен и каждый кусок,
+    // который начинается на кратном align логическом индексе
+    pub fn with_alignment(capacity: usize, align: usize) -> Self {
+        assert!(capacity > 0, "В буфере есть место!");
+        assert!(align.is_power_of_two(), "Выравнивание - степень двойки!");
+        assert!(
+            capacity.is_multiple_of(align),
+            "Ёмкость должна быть кратна выравниванию!"
+        );
+
+        ByteRing {
+            buf: Storage::new(capacity, align),
+            head: 0,
+            len: 0,
+            init: 0,
+        }
+    }
+
+    // 1.3. Кольцо, если хватает памяти: для ёмкости из недоверенного источника
+    // (например, заголовка файла), где ByteRing::new аварийно завершил бы процесс
+    pub fn try_new(capacity: usize) -> Option<Self> {
+        assert!(capacity > 0, "В буфере есть место!");
+
+        Some(ByteRing {
+            buf: Storage::try_new(capacity, 1)?,
+            head: 0,
+            len: 0,
+            init: 0,
+        })
+    }
+
+    pub fn alignment(&self) -> usize {
+        self.buf.align
+    }
+
+    // 2. Размеры
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == self.buf.len()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn free(&self) -> usize {
+        self.buf.len() - self.len
+    }
+
+    // 2.1. Память в куче: хранилище вместе с запасом на выравнивание
+    pub fn heap_bytes(&self) -> usize {
+        self.buf.raw.capacity()
+    }
+
+    // 3. Запись одного байта
+    #[inline]
+    pub fn push(&mut self, byte: u8) -> Result<(), String> {
+        if self.is_full() {
+            return Err(full_error());
+        }
+
+        let tail = self.tail();
+        self.buf[tail].write(byte);
+        self.mark_written(1);
+        Ok(())
+    }
+
+    // 4. Чтение одного байта
+    #[inline]
+    pub fn pop(&mut self) -> Option<u8> {
+        let byte = *self.as_slices().0.first()?;
+        self.consume(1);
+        Some(byte)
+    }
+
+    // 5. Запись стольких байт, сколько помещается (не более двух копирований)
+    pub fn extend_from_slice(&mut self, data: &[u8]) -> usize {
+        self.traced("extend", data.len(), |ring| ring.copy_in(data), |&n| n)
+    }
+
+    // 5.0.1. То же с подробным результатом: сколько осталось и почему остановились
+    pub fn extend_partial(&mut self, data: &[u8]) -> PartialWrite {
+        PartialWrite::new(self.extend_from_slice(data), data.len())
+    }
+
+    #[inline]
+    fn copy_in(&mut self, data: &[u8]) -> usize {
+        let (first, second) = self.free_slices_mut();
+        let n = data.len().min(first.len() + second.len());
+        let split = n.min(first.len());
+
+        copy_bytes(&mut first[..split], &data[..split]);
+        copy_bytes(&mut second[..n - split], &data[split..n]);
+        self.mark_written(n);
+        n
+    }
+
+    // 6. Чтение в срез вызывающего; возвращает, сколько байт скопировано
+    pub fn drain_into(&mut self, out: &mut [u8]) -> usize {
+        self.traced("drain", out.len(), |ring| ring.copy_out(out), |&n| n)
+    }
+
+    #[inline]
+    fn copy_out(&mut self, out: &mut [u8]) -> usize {
+        let n = self.peek_into(out);
+        self.consume(n);
+        n
+    }
+
+    // 6.0.1. Чтение до count байт (но не больше N) в массив на стеке - без обращения
+    // к куче на горячем пути, где вычитывают по несколько байт
+    pub fn drain_small<const N: usize>(&mut self, count: usize) -> SmallBytes<N> {
+        let mut small = SmallBytes {
+            buf: [0; N],
+            len: 0,
+        };
+        small.len = self.drain_into(&mut small.buf[..count.min(N)]);
+        small
+    }
+
+    // 6.1. Копирование в срез без вычитывания (например, заголовка кадра)
+    pub fn peek_into(&self, out: &mut [u8]) -> usize {
+        let (first, second) = self.as_slices();
+        let n = out.len().min(first.len() + second.len());
+        let split = n.min(first.len());
+
+        copy_bytes(&mut out[..split], &first[..split]);
+        copy_bytes(&mut out[split..n], &second[..n - split]);
+        n
+    }
+
+    // 6.2. Отбрасывание до n самых старых байт; возвращает, сколько отброшено
+    pub fn discard(&mut self, n: usize) -> usize {
+        let n = n.min(self.len);
+        self.consume(n);
+        n
+    }
+
+    // 6.3. Чтение без копирования: непрерывная часть содержимого до шва прямо из
+    // хранилища. Голова сдвигается при отпускании (на весь кусок) или при commit
+    pub fn read_chunk(&mut self) -> ReadChunk<'_> {
+        let len = self.as_slices().0.len();
+        ReadChunk { ring: self, len }
+    }
+
+    // 7. Содержимое от старых байт к новым: до границы массива и после неё
+    pub fn as_slices(&self) -> (&[u8], &[u8]) {
+        let end = self.head + self.len;
+        let capacity = self.buf.len();
+        if end <= capacity {
+            (self.bytes(self.head..end), &[])
+        } else {
+            (
+                self.bytes(self.head..capacity),
+                self.bytes(0..end - capacity),
+            )
+        }
+    }
+
+    // 7.1. Перекрывающиеся окна длины n; окно на шве массива приходит двумя срезами
+    pub fn windows(&self, n: usize) -> impl Iterator<Item = (&[u8], &[u8])> + '_ {
+        assert!(n > 0, "Окно не может быть пустым!");
+
+        let capacity = self.buf.len();
+        (0..(self.len + 1).saturating_sub(n)).map(move |start| {
+            let from = wrap(self.head + start, capacity);
+            if from + n <= capacity {
+                (self.bytes(from..from + n), &[][..])
+            } else {
+                (
+                    self.bytes(from..capacity),
+                    self.bytes(0..from + n - capacity),
+                )
+            }
+        })
+    }
+
+    // 7.2. Логический индекс первого вхождения needle (например, b"\r\n\r\n"),
+    // без склейки содержимого в один срез
+    pub fn find(&self, needle: &[u8]) -> Option<usize> {
+        let m = needle.len();
+        if m == 0 {
+            return Some(0);
+        }
+        let (first, second) = self.as_slices();
+
+        // Вхождения по порядку: целиком в первом срезе, на шве, целиком во втором
+        if let Some(i) = first.windows(m).position(|w| w == needle) {
+            return Some(i);
+        }
+        for start in first.len().saturating_sub(m - 1)..first.len() {
+            let (before, after) = needle.split_at(first.len() - start);
+            if first[start..] == *before && second.starts_with(after) {
+                return Some(start);
+            }
+        }
+        second
+            .windows(m)
+            .position(|w| w == needle)
+            .map(|i| first.len() + i)
+    }
+
+    // 8. Запись на месте: замыкание получает до n свободных непрерывных байт
+    // и возвращает, сколько из них заполнило. Паника в замыкании ничего не фиксирует
+    pub fn write_with(&mut self, n: usize, fill: impl FnOnce(&mut [u8]) -> usize) -> usize {
+        let (first, _) = self.free_slices_mut();
+        let room = n.min(first.len());
+        let written = fill(&mut first[..room]);
+        assert!(written <= room, "Записано больше выданного места!");
+
+        self.mark_written(written);
+        written
+    }
+
+    // 8.1. Контрольная сумма логического содержимого (CRC32 или xxHash32), без вычитывания
+    pub fn checksum(&self) -> u32 {
+        let (first, second) = self.as_slices();
+        checksum::of_parts(&[first, second])
+    }
+
+    // 8.2. Запись в неинициализированную память без обнуления (recv, Read::read_buf):
+    // до n свободных непрерывных байт от хвоста до шва. Записанное фиксирует commit
+    pub fn write_chunk_uninit(&mut self, n: usize) -> &mut [MaybeUninit<u8>] {
+        let tail = self.tail();
+        let room = n.min(self.free()).min(self.buf.len() - tail);
+        &mut self.buf[tail..tail + room]
+    }
+
+    // 8.2.1. Кусок для DMA: начинается на адресе, кратном alignment, а длина кратна ему.
+    // Пуст, если хвост сейчас не выровнен (после записи некратного числа байт)
+    pub fn write_chunk_aligned(&mut self, n: usize) -> &mut [MaybeUninit<u8>] {
+        let align = self.buf.align;
+        if !self.tail().is_multiple_of(align) {
+            return &mut [];
+        }
+        let chunk = self.write_chunk_uninit(n);
+        let len = chunk.len() - chunk.len() % align;
+        &mut chunk[..len]
+    }
+
+    // 8.2.2. То же место в виде захвата (например, для передачи в DMA): пока он жив,
+    // кольцо занято; commit фиксирует записанное, отпускание без commit - ничего
+    pub fn write_grant(&mut self, n: usize) -> WriteChunk<'_> {
+        let start = self.tail();
+        let len = self.write_chunk_aligned(n).len();
+        WriteChunk {
+            ring: self,
+            start,
+            len,
+        }
+    }
+
+    // 8.3. Фиксация n байт, записанных в кусок от write_chunk_uninit
+    ///
+    /// # Safety
+    ///
+    /// Первые n байт куска, выданного write_chunk_uninit, должны быть инициализированы.
+    pub unsafe fn commit(&mut self, n: usize) {
+        let room = self.free().min(self.buf.len() - self.tail());
+        assert!(n <= room, "Фиксация больше выданного места!");
+        self.mark_written(n);
+    }
+
+    // 9. Свободное место: от хвоста до границы массива и от начала массива до головы.
+    // Ещё не записанная часть сначала обнуляется (один раз за жизнь кольца)
+    fn free_slices_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        let tail = self.tail();
+        let free = self.free();
+        let capacity = self.buf.len();
+        let end = (tail + free).min(capacity);
+        if self.init < end {
+            self.buf[self.init..end].fill(MaybeUninit::new(0));
+            self.init = end;
+        }
+
+        // SAFETY: [tail, end) только что инициализированы, а место до головы
+        // лежит внутри уже записанной части [0, tail)
+        if tail + free <= capacity {
+            (
+                unsafe { assume_init_mut(&mut self.buf[tail..end]) },
+                &mut [],
+            )
+        } else {
+            let (start, rest) = self.buf.split_at_mut(tail);
+            let second = &mut start[..free - (capacity - tail)];
+            unsafe { (assume_init_mut(rest), assume_init_mut(second)) }
+        }
+    }
+
+    // 9.0.1. Инициализированная часть хранилища как байты
+    #[inline]
+    fn bytes(&self, range: Range<usize>) -> &[u8] {
+        debug_assert!(range.end <= self.init, "Чтение незаписанных байт!");
+        // SAFETY: байты [0, init) записаны
+        unsafe { assume_init(&self.buf[range]) }
+    }
+
+    // 9.1. Учёт n байт, записанных в свободное место от хвоста. Запись идёт от начала
+    // хранилища подряд, поэтому переход через шов значит, что записано всё
+    #[inline]
+    fn mark_written(&mut self, n: usize) {
+        debug_assert!(n <= self.free(), "Фиксация больше свободного места!");
+        let end = self.head + self.len + n;
+        self.init = if end > self.buf.len() {
+            self.buf.len()
+        } else {
+            self.init.max(end)
+        };
+        self.len += n;
+    }
+
+    // 9.2. Освобождение n самых старых байт
+    #[inline]
+    fn consume(&mut self, n: usize) {
+        debug_assert!(n <= self.len, "Освобождение больше содержимого!");
+        self.head = wrap(self.head + n, self.buf.len());
+        self.len -= n;
+    }
+
+    #[inline]
+    fn tail(&self) -> usize {
+        wrap(self.head + self.len, self.buf.len())
+    }
+
+    // 9.3. Пакетная операция в спане tracing (возможность tracing): сколько байт
+    // просили, сколько перенесено и заполнение после. Без возможности - просто вызов
+    #[inline(always)]
+    fn traced<R>(
+        &mut self,
+        op: &'static str,
+        requested: usize,
+        f: impl FnOnce(&mut Self) -> R,
+        moved: impl FnOnce(&R) -> usize,
+    ) -> R {
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::debug_span!(
+                "byte_ring",
+                op,
+                requested,
+                moved = tracing::field::Empty,
+                len = tracing::field::Empty,
+                capacity = self.capacity(),
+            );
+            let _entered = span.enter();
+            let result = f(self);
+            span.record("moved", moved(&result));
+            span.record("len", self.len());
+            result
+        }
+        #[cfg(not(feature = "tracing"))]
+        {
+            let _ = (op, requested, moved);
+            f(self)
+        }
+    }
+}
+
+// 9.2.2. Закольцовывание индекса меньше 2 * capacity без деления
+#[inline]
+fn wrap(index: usize, capacity: usize) -> usize {
+    if index >= capacity {
+        index - capacity
+    } else {
+        index
+    }
+}
+
+// 9.2.2.2. Хранилище с заданным выравниванием: берём на align - 1 байт больше
+// и начинаем с первого выровненного адреса
+#[derive(Debug)]
+struct Storage {
+    raw: Vec<MaybeUninit<u8>>,
+    offset: usize,
+    len: usize,
+    align: usize,
+}
+
+impl Storage {
+    fn new(len: usize, align: usize) -> Self {
+        Self::try_new(len, align).expect("Не удалось выделить хранилище!")
+    }
+
+    // 9.2.2.2.1. То же без паники и аварийного завершения: None, если памяти не хватило
+    // или размер не помещается в адресное пространство
+    fn try_new(len: usize, align: usize) -> Option<Self> {
+        let size = len.checked_add(align - 1)?;
+        let mut raw = Vec::new();
+        raw.try_reserve_exact(size).ok()?;
+        raw.resize(size, MaybeUninit::uninit());
+        let offset = raw.as_ptr().align_offset(align);
+        assert!(offset < align, "Не удалось выровнять хранилище!");
+        Some(Storage {
+            raw,
+            offset,
+            len,
+            align,
+        })
+    }
+}
+
+// 9.2.2.3. Копия выделяет своё выровненное хранилище (смещение может отличаться)
+impl Clone for Storage {
+    fn clone(&self) -> Self {
+        let mut copy = Storage::new(self.len, self.align);
+        copy.copy_from_slice(self);
+        copy
+    }
+}
+
+impl Deref for Storage {
+    type Target = [MaybeUninit<u8>];
+
+    #[inline]
+    fn deref(&self) -> &[MaybeUninit<u8>] {
+        &self.raw[self.offset..self.offset + self.len]
+    }
+}
+
+impl DerefMut for Storage {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [MaybeUninit<u8>] {
+        &mut self.raw[self.offset..self.offset + self.len]
+    }
+}
+
+// 9.2.2.1. Взгляд на записанные байты как на u8 (раскладки MaybeUninit<u8> и u8 совпадают).
+// Вызывающий гарантирует, что все байты среза инициализированы
+#[inline]
+unsafe fn assume_init(part: &[MaybeUninit<u8>]) -> &[u8] {
+    &*(part as *const [MaybeUninit<u8>] as *const [u8])
+}
+
+#[inline]
+unsafe fn assume_init_mut(part: &mut [MaybeUninit<u8>]) -> &mut [u8] {
+    &mut *(part as *mut [MaybeUninit<u8>] as *mut [u8])
+}
+
+// 9.2.3. Копирование для пакетных записи и чтения. С возможностью prefetch большие
+// переносы идут блоками, а процессор заранее получает подсказку о строках источника
+#[inline]
+fn copy_bytes(dst: &mut [u8], src: &[u8]) {
+    #[cfg(all(
+        feature = "prefetch",
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    ))]
+    if src.len() >= PREFETCH_MIN {
+        for (i, (to, from)) in dst
+            .chunks_mut(PREFETCH_STEP)
+            .zip(src.chunks(PREFETCH_STEP))
+            .enumerate()
+        {
+            let ahead = i * PREFETCH_STEP + PREFETCH_AHEAD;
+            for line in (ahead..(ahead + PREFETCH_STEP).min(src.len())).step_by(CACHE_LINE) {
+                prefetch(src[line..].as_ptr());
+            }
+            to.copy_from_slice(from);
+        }
+        return;
+    }
+    dst.copy_from_slice(src);
+}
+
+// 9.2.4. Параметры подсказок: блок копирования, насколько вперёд смотрим и с какого размера
+#[cfg(all(
+    feature = "prefetch",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+const PREFETCH_STEP: usize = 4096;
+#[cfg(all(
+    feature = "prefetch",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+const PREFETCH_AHEAD: usize = 8192;
+#[cfg(all(
+    feature = "prefetch",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+const PREFETCH_MIN: usize = 16384;
+#[cfg(all(
+    feature = "prefetch",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+const CACHE_LINE: usize = 64;
+
+// 9.2.5. Подсказка загрузить строку в кэш; на чтение памяти не влияет
+#[cfg(all(feature = "prefetch", target_arch = "x86_64"))]
+#[inline(always)]
+fn prefetch(ptr: *const u8) {
+    use core::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+    // SAFETY: prefetch - только подсказка, адрес не разыменовывается
+    unsafe { _mm_prefetch::<_MM_HINT_T0>(ptr as *const i8) }
+}
+
+#[cfg(all(feature = "prefetch", target_arch = "aarch64"))]
+#[inline(always)]
+fn prefetch(ptr: *const u8) {
+    // SAFETY: prfm - только подсказка, адрес не разыменовывается
+    unsafe {
+        core::arch::asm!("prfm pldl1keep, [{0}]", in(reg) ptr, options(nostack, readonly, preserves_flags));
+    }
+}
+
+// 9.2.1. Байты, вычитанные в массив на стеке; ведёт себя как срез
+#[derive(Debug, Clone, Copy)]
+pub struct SmallBytes<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> Deref for SmallBytes<N> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+}
+
+// 9.2.6. Кусок содержимого, выданный read_chunk; ведёт себя как срез
+#[derive(Debug)]
+pub struct ReadChunk<'a> {
+    ring: &'a mut ByteRing,
+    len: usize, // Сколько байт освободить при отпускании
+}
+
+impl ReadChunk<'_> {
+    // 9.2.6.1. Освобождаем только n первых байт, остальные остаются в кольце
+    pub fn commit(mut self, n: usize) {
+        assert!(n <= self.len, "Освобождение больше выданного куска!");
+        self.len = n;
+    }
+}
+
+impl Deref for ReadChunk<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.ring.as_slices().0[..self.len]
+    }
+}
+
+impl Drop for ReadChunk<'_> {
+    fn drop(&mut self) {
+        self.ring.consume(self.len);
+    }
+}
+
+// 9.2.7. Свободное место, выданное write_grant; ведёт себя как срез MaybeUninit<u8>
+#[derive(Debug)]
+pub struct WriteChunk<'a> {
+    ring: &'a mut ByteRing,
+    start: usize, // Индекс хвоста в хранилище на момент выдачи
+    len: usize,
+}
+
+impl WriteChunk<'_> {
+    // 9.2.7.1. Фиксация n первых байт куска
+    ///
+    /// # Safety
+    ///
+    /// Первые n байт куска должны быть инициализированы (записаны вызывающим или устройством).
+    pub unsafe fn commit(self, n: usize) {
+        assert!(n <= self.len, "Фиксация больше выданного куска!");
+        self.ring.commit(n);
+    }
+}
+
+impl Deref for WriteChunk<'_> {
+    type Target = [MaybeUninit<u8>];
+
+    fn deref(&self) -> &[MaybeUninit<u8>] {
+        &self.ring.buf[self.start..self.start + self.len]
+    }
+}
+
+impl DerefMut for WriteChunk<'_> {
+    fn deref_mut(&mut self) -> &mut [MaybeUninit<u8>] {
+        &mut self.ring.buf[self.start..self.start + self.len]
+    }
+}
+
+// 9.3. Текстовые дампы прямо из хранилища (возможность encode)
+#[cfg(feature = "encode")]
+impl ByteRing {
+    // 9.3.1. Вычитывание до n байт в шестнадцатеричном виде (строчные цифры)
+    pub fn drain_hex(&mut self, n: usize) -> String {
+        const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+        let n = n.min(self.len);
+        let mut out = String::with_capacity(n * 2);
+        let (first, second) = self.as_slices();
+        for &byte in first.iter().chain(second).take(n) {
+            out.push(DIGITS[(byte >> 4) as usize] as char);
+            out.push(DIGITS[(byte & 0xF) as usize] as char);
+        }
+        self.consume(n);
+        out
+    }
+
+    // 9.3.2. Вычитывание до n байт в Base64 (стандартный алфавит, с дополнением '=')
+    pub fn drain_base64(&mut self, n: usize) -> String {
+        const ALPHABET: &[u8; 64] =
+            b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+        let n = n.min(self.len);
+        let mut out = String::with_capacity(n.div_ceil(3) * 4);
+        let (first, second) = self.as_slices();
+        let mut bytes = first.iter().chain(second).take(n).copied();
+
+        // Группа из трёх байт может начинаться до границы массива и заканчиваться после неё
+        while let Some(b0) = bytes.next() {
+            let b1 = bytes.next();
+            let b2 = bytes.next();
+            let group = (b0 as u32) << 16 | (b1.unwrap_or(0) as u32) << 8 | b2.unwrap_or(0) as u32;
+
+            out.push(ALPHABET[(group >> 18) as usize & 63] as char);
+            out.push(ALPHABET[(group >> 12) as usize & 63] as char);
+            out.push(match b1 {
+                Some(_) => ALPHABET[(group >> 6) as usize & 63] as char,
+                None => '=',
+            });
+            out.push(match b2 {
+                Some(_) => ALPHABET[group as usize & 63] as char,
+                None => '=',
+            });
+        }
+        self.consume(n);
+        out
+    }
+}
+
+// 10. Ввод-вывод напрямую через хранилище (std)
+#[cfg(feature = "std")]
+fn moved_ok(result: &std::io::Result<usize>) -> usize {
+    *result.as_ref().unwrap_or(&0)
+}
+
+#[cfg(feature = "std")]
+impl ByteRing {
+    // 10.1. Чтение из источника прямо в свободное место: один или два вызова read.
+    // Ok(0) - источник исчерпан или кольцо заполнено (проверяйте is_full)
+    pub fn fill_from(&mut self, r: &mut impl std::io::Read) -> std::io::Result<usize> {
+        let free = self.free();
+        self.traced("fill_from", free, |ring| ring.read_from(r), moved_ok)
+    }
+
+    fn read_from(&mut self, r: &mut impl std::io::Read) -> std::io::Result<usize> {
+        let (first, _) = self.free_slices_mut();
+        let room = first.len();
+        if room == 0 {
+            return Ok(0); // Некуда читать
+        }
+        let read = r.read(first)?;
+        assert!(read <= room, "Источник прочитал больше выданного места!");
+        self.mark_written(read);
+        if read < room {
+            return Ok(read); // Источник отдал не всё, второй вызов не нужен
+        }
+
+        let (rest, _) = self.free_slices_mut();
+        if rest.is_empty() {
+            return Ok(read); // Кольцо заполнено
+        }
+        // Первая часть заполнена целиком - дочитываем в начало массива
+        let room = rest.len();
+        match r.read(rest) {
+            Ok(more) => {
+                assert!(more <= room, "Источник прочитал больше выданного места!");
+                self.mark_written(more);
+                Ok(read + more)
+            }
+            Err(_) => Ok(read), // Уже прочитанное не теряем, ошибка повторится при следующем вызове
+        }
+    }
+
+    // 10.2. Запись содержимого в приёмник прямо из хранилища. Голова сдвигается
+    // ровно на принятые байты, короткие записи дописываются в цикле. Ошибка - только
+    // если не принято ничего, иначе - сколько принято
+    pub fn flush_into(&mut self, w: &mut impl std::io::Write) -> std::io::Result<usize> {
+        let len = self.len();
+        self.traced("flush_into", len, |ring| ring.write_to(w), moved_ok)
+    }
+
+    fn write_to(&mut self, w: &mut impl std::io::Write) -> std::io::Result<usize> {
+        let mut total = 0;
+        while !self.is_empty() {
+            let (first, _) = self.as_slices();
+            match w.write(first) {
+                Ok(0) if total > 0 => break, // Принятое уже снято с кольца - сообщаем, сколько
+                Ok(0) => return Err(std::io::ErrorKind::WriteZero.into()),
+                Ok(n) => {
+                    self.consume(n);
+                    total += n;
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(_) if total > 0 => break, // Записанное уже снято с кольца, ошибка повторится позже
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(total)
+    }
+
+    // 10.3. Дочитывание, пока в кольце не окажется n байт (запись фиксированного размера).
+    // Останавливается раньше на конце источника или WouldBlock и возвращает,
+    // сколько байт прочитано за вызов; больше нужного не читает
+    pub fn extend_from_reader_exact(
+        &mut self,
+        r: &mut impl std::io::Read,
+        n: usize,
+    ) -> std::io::Result<usize> {
+        if n > self.capacity() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Запись больше ёмкости буфера!",
+            ));
+        }
+
+        let mut total = 0;
+        while self.len < n {
+            let need = n - self.len;
+            let (first, _) = self.free_slices_mut();
+            let room = need.min(first.len());
+            match r.read(&mut first[..room]) {
+                Ok(0) => break, // Источник исчерпан
+                Ok(read) => {
+                    assert!(read <= room, "Источник прочитал больше выданного места!");
+                    self.mark_written(read);
+                    total += read;
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => break,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(total)
+    }
+
+    // 10.4. Запись из нескольких срезов (как их отдаёт сетевой стек) за один проход
+    // по свободному месту, без предварительной склейки; возвращает, сколько байт поместилось
+    pub fn extend_vectored(&mut self, bufs: &[std::io::IoSlice<'_>]) -> usize {
+        let (first, second) = self.free_slices_mut();
+        let regions = [first, second];
+        let (mut region, mut offset, mut written) = (0, 0, 0);
+        for buf in bufs {
+            let mut src: &[u8] = buf;
+            while !src.is_empty() && region < regions.len() {
+                let room = &mut regions[region][offset..];
+                if room.is_empty() {
+                    (region, offset) = (region + 1, 0); // Переходим через шов массива
+                    continue;
+                }
+                let n = room.len().min(src.len());
+                room[..n].copy_from_slice(&src[..n]);
+                src = &src[n..];
+                offset += n;
+                written += n;
+            }
+        }
+        self.mark_written(written);
+        written
+    }
+}
+
+// 11. Числа фиксированной ширины в порядке little или big endian (как у bytes::Buf),
+// чтобы разбирать двоичные протоколы без вычитывания во временный срез. Число на
+// шве хранилища собирается из двух частей. Операции целиком: push без места
+// возвращает ошибку и ничего не пишет, pop и peek без нужных байт дают None
+impl ByteRing {
+    fn push_array<const N: usize>(&mut self, bytes: [u8; N]) -> Result<(), String> {
+        if self.free() < N {
+            return Err(full_error());
+        }
+        self.copy_in(&bytes);
+        Ok(())
+    }
+
+    fn peek_array<const N: usize>(&self) -> Option<[u8; N]> {
+        if self.len < N {
+            return None;
+        }
+        let mut bytes = [0; N];
+        self.peek_into(&mut bytes);
+        Some(bytes)
+    }
+
+    fn pop_array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let bytes = self.peek_array()?;
+        self.consume(N);
+        Some(bytes)
+    }
+}
+
+// 11.1. Шесть методов на тип: push, pop и peek в обоих порядках байт
+macro_rules! endian_methods {
+    ($($ty:ty => $push_le:ident $push_be:ident $pop_le:ident $pop_be:ident $peek_le:ident $peek_be:ident;)*) => {
+        impl ByteRing {
+            $(
+                pub fn $push_le(&mut self, value: $ty) -> Result<(), String> {
+                    self.push_array(value.to_le_bytes())
+                }
+
+                pub fn $push_be(&mut self, value: $ty) -> Result<(), String> {
+                    self.push_array(value.to_be_bytes())
+                }
+
+                pub fn $pop_le(&mut self) -> Option<$ty> {
+                    self.pop_array().map(<$ty>::from_le_bytes)
+                }
+
+                pub fn $pop_be(&mut self) -> Option<$ty> {
+                    self.pop_array().map(<$ty>::from_be_bytes)
+                }
+
+                pub fn $peek_le(&self) -> Option<$ty> {
+                    self.peek_array().map(<$ty>::from_le_bytes)
+                }
+
+                pub fn $peek_be(&self) -> Option<$ty> {
+                    self.peek_array().map(<$ty>::from_be_bytes)
+                }
+            )*
+        }
+    };
+}
+
+endian_methods! {
+    u16 => push_u16_le push_u16_be pop_u16_le pop_u16_be peek_u16_le peek_u16_be;
+    u32 => push_u32_le push_u32_be pop_u32_le pop_u32_be peek_u32_le peek_u32_be;
+    u64 => push_u64_le push_u64_be pop_u64_le pop_u64_be peek_u64_le peek_u64_be;
+    i16 => push_i16_le push_i16_be pop_i16_le pop_i16_be peek_i16_le peek_i16_be;
+    i32 => push_i32_le push_i32_be pop_i32_le pop_i32_be peek_i32_le peek_i32_be;
+    i64 => push_i64_le push_i64_be pop_i64_le pop_i64_be peek_i64_le peek_i64_be;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ByteRing;
+
+    #[test]
+    fn test_endian_across_seam() {
+        let mut ring = ByteRing::new(7);
+        ring.extend_from_slice(&[0; 5]);
+        ring.discard(5); // Голова у шва: следующие числа его пересекают
+
+        assert!(ring.push_u32_be(0x0102_0304).is_ok());
+        assert!(ring.push_u16_le(0xBEEF).is_ok());
+        assert!(ring.push_u16_le(1).is_err()); // Места на один байт: ничего не записано
+        assert_eq!(ring.len(), 6);
+        assert_eq!(ring.as_slices(), (&[1, 2][..], &[3, 4, 0xEF, 0xBE][..]));
+
+        assert_eq!(ring.peek_u32_le(), Some(0x0403_0201));
+        assert_eq!(ring.pop_u32_be(), Some(0x0102_0304));
+        assert_eq!(ring.pop_i16_le(), Some(0xBEEF_u16 as i16));
+
+        assert!(ring.push_u64_le(u64::MAX - 1).is_err());
+        assert!(ring.push_i32_be(-2).is_ok());
+        assert_eq!(ring.peek_u64_be(), None); // Байт меньше восьми
+        assert_eq!(ring.pop_u64_le(), None);
+        assert_eq!(ring.pop_i32_be(), Some(-2));
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    fn test_push_pop() {
+        let mut ring = ByteRing::new(2);
+        assert!(ring.push(1).is_ok());
+        assert!(ring.push(2).is_ok());
+        assert!(ring.push(3).is_err()); // Полон
+
+        assert_eq!(ring.pop(), Some(1));
+        assert_eq!(ring.pop(), Some(2));
+        assert_eq!(ring.pop(), None);
+    }
+
+    #[test]
+    fn test_drain_small() {
+        let mut ring = ByteRing::new(8);
+        ring.extend_from_slice(b"hello!");
+        ring.discard(3);
+        ring.extend_from_slice(b"worl"); // Содержимое проходит через шов
+
+        let head = ring.drain_small::<4>(16); // Не больше размера массива
+        assert_eq!(&*head, b"lo!w");
+        let rest = ring.drain_small::<8>(8);
+        assert_eq!(&*rest, b"orl");
+        assert!(ring.drain_small::<4>(2).is_empty());
+    }
+
+    #[test]
+    fn test_slices_across_seam() {
+        let mut ring = ByteRing::new(5);
+        assert_eq!(ring.extend_from_slice(b"abcd"), 4);
+        let mut out = [0; 3];
+        assert_eq!(ring.drain_into(&mut out), 3);
+        assert_eq!(&out, b"abc");
+
+        // Запись проходит через границу массива
+        assert_eq!(ring.extend_from_slice(b"efghij"), 4);
+        assert_eq!(ring.as_slices(), (&b"de"[..], &b"fgh"[..]));
+        assert!(ring.is_full());
+    }
+
+    #[test]
+    fn test_large_transfer_across_seam() {
+        // Переносы больше порога подсказок prefetch, с неровным хвостом блока
+        let data: alloc::vec::Vec<u8> = (0..20_000u32).map(|i| (i % 251) as u8).collect();
+        let mut ring = ByteRing::new(16_384);
+        assert_eq!(ring.extend_from_slice(&data[..10_000]), 10_000);
+        let mut out = alloc::vec![0; 9_000];
+        assert_eq!(ring.drain_into(&mut out), 9_000);
+        assert_eq!(out, data[..9_000]);
+
+        assert_eq!(ring.extend_from_slice(&data[10_000..]), 10_000);
+        let mut out = alloc::vec![0; 11_000];
+        assert_eq!(ring.drain_into(&mut out), 11_000);
+        assert_eq!(out, data[9_000..]);
+    }
+
+    #[test]
+    fn test_read_chunk() {
+        let mut ring = ByteRing::new(6);
+        assert_eq!(ring.extend_from_slice(b"abcd"), 4);
+        ring.discard(3);
+        assert_eq!(ring.extend_from_slice(b"efgh"), 4);
+
+        // Кусок заканчивается на шве; частичная фиксация
+        let chunk = ring.read_chunk();
+        assert_eq!(&*chunk, b"def");
+        chunk.commit(1);
+        assert_eq!(&*ring.read_chunk(), b"ef"); // Отпущен целиком
+
+        assert_eq!(&*ring.read_chunk(), b"gh");
+        assert!(ring.is_empty());
+        assert!(ring.read_chunk().is_empty());
+    }
+
+    #[test]
+    fn test_write_chunk_uninit() {
+        let mut ring = ByteRing::new(6);
+        let chunk = ring.write_chunk_uninit(4);
+        assert_eq!(chunk.len(), 4);
+        for (slot, byte) in chunk.iter_mut().zip(b"abc") {
+            slot.write(*byte);
+        }
+        // SAFETY: три первых байта куска записаны выше
+        unsafe { ring.commit(3) };
+        assert_eq!(ring.as_slices(), (&b"abc"[..], &b""[..]));
+
+        // Кусок кончается на шве; незаписанная часть обнуляется только для срезов u8
+        ring.discard(2);
+        assert_eq!(ring.write_chunk_uninit(usize::MAX).len(), 3);
+        assert_eq!(ring.extend_from_slice(b"defgh"), 5);
+        assert_eq!(ring.as_slices(), (&b"cdef"[..], &b"gh"[..]));
+        assert!(ring.write_chunk_uninit(1).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_commit_past_chunk() {
+        let mut ring = ByteRing::new(2);
+        // SAFETY: паника происходит раньше, чем байты будут считаться записанными
+        unsafe { ring.commit(3) };
+    }
+
+    #[test]
+    fn test_aligned_chunks() {
+        let mut ring = ByteRing::with_alignment(256, 64);
+        assert_eq!(ring.alignment(), 64);
+
+        let chunk = ring.write_chunk_aligned(100);
+        assert_eq!(chunk.as_ptr() as usize % 64, 0);
+        assert_eq!(chunk.len(), 64); // Округлено вниз до кратного
+        for slot in chunk.iter_mut() {
+            slot.write(7);
+        }
+        // SAFETY: все 64 байта куска записаны выше
+        unsafe { ring.commit(64) };
+
+        let chunk = ring.write_chunk_aligned(usize::MAX);
+        assert_eq!(chunk.as_ptr() as usize % 64, 0);
+        assert_eq!(chunk.len(), 192);
+
+        assert_eq!(ring.extend_from_slice(b"abc"), 3);
+        assert!(ring.write_chunk_aligned(64).is_empty()); // Хвост не выровнен
+
+        let copy = ring.clone();
+        assert_eq!(copy.as_slices().0.as_ptr() as usize % 64, 0);
+        assert_eq!(copy.as_slices(), ring.as_slices());
+    }
+
+    #[test]
+    fn test_write_grant() {
+        let mut ring = ByteRing::with_alignment(8, 4);
+        {
+            let mut grant = ring.write_grant(7);
+            assert_eq!(grant.len(), 4);
+            grant[0].write(1);
+            grant[1].write(2);
+        } // Без commit ничего не записано
+        assert!(ring.is_empty());
+
+        let mut grant = ring.write_grant(8);
+        for (i, slot) in grant.iter_mut().enumerate() {
+            slot.write(i as u8);
+        }
+        // SAFETY: весь кусок записан выше
+        unsafe { grant.commit(8) };
+        assert_eq!(ring.as_slices().0, [0, 1, 2, 3, 4, 5, 6, 7]);
+        assert!(ring.write_grant(1).is_empty()); // Полон
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_capacity_not_multiple_of_alignment() {
+        let _ = ByteRing::with_alignment(100, 64);
+    }
+
+    #[test]
+    fn test_write_with() {
+        let mut ring = ByteRing::new(6);
+        ring.extend_from_slice(b"xxxx");
+        ring.drain_into(&mut [0; 4]);
+
+        // Непрерывно свободны только 2 байта до границы массива
+        let written = ring.write_with(5, |slot| {
+            assert_eq!(slot.len(), 2);
+            slot[0] = b'o';
+            1 // Фиксируем только то, что действительно записали
+        });
+        assert_eq!(written, 1);
+        assert_eq!(ring.len(), 1);
+
+        // Второй вызов дописывает последний байт перед границей, третий - с начала массива
+        assert_eq!(ring.write_with(3, |slot| slot.len()), 1);
+        let written = ring.write_with(3, |slot| {
+            slot.copy_from_slice(b"ay!");
+            2
+        });
+        assert_eq!(written, 2);
+        assert_eq!(ring.as_slices().1, b"ay");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_extend_vectored() {
+        use std::io::IoSlice;
+
+        let mut ring = ByteRing::new(8);
+        ring.extend_from_slice(b"xxxxx");
+        ring.discard(5); // Свободно 3 байта до границы и 5 в начале
+        let parts = [
+            IoSlice::new(b"ab"),
+            IoSlice::new(b""),
+            IoSlice::new(b"cdef"),
+        ];
+        assert_eq!(ring.extend_vectored(&parts), 6);
+        assert_eq!(ring.as_slices(), (&b"abc"[..], &b"def"[..]));
+
+        let parts = [IoSlice::new(b"gh"), IoSlice::new(b"ijk")];
+        assert_eq!(ring.extend_vectored(&parts), 2); // Остальное не помещается
+        assert!(ring.is_full());
+        assert_eq!(ring.extend_vectored(&parts), 0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fill_from() {
+        let mut ring = ByteRing::new(6);
+        ring.extend_from_slice(b"....");
+        ring.drain_into(&mut [0; 3]);
+
+        // Свободно 2 байта до границы и 3 в начале: два вызова read за раз
+        let mut source: &[u8] = b"abcdefgh";
+        assert_eq!(ring.fill_from(&mut source).unwrap(), 5);
+        assert!(ring.is_full());
+        assert_eq!(ring.as_slices(), (&b".ab"[..], &b"cde"[..]));
+
+        assert_eq!(ring.fill_from(&mut source).unwrap(), 0); // Полон
+        ring.drain_into(&mut [0; 6]);
+        assert_eq!(ring.fill_from(&mut source).unwrap(), 3);
+        assert_eq!(ring.fill_from(&mut source).unwrap(), 0); // Источник исчерпан
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[should_panic(expected = "Источник прочитал больше выданного места!")]
+    fn test_fill_from_rejects_lying_reader() {
+        // Безопасная, но ошибочная реализация Read: сообщает о большем, чем получила
+        struct Liar;
+
+        impl std::io::Read for Liar {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                Ok(buf.len() + 1)
+            }
+        }
+
+        let _ = ByteRing::new(4).fill_from(&mut Liar);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_flush_into() {
+        use std::io::{self, Write};
+
+        // Приёмник, принимающий не больше 2 байт за вызов
+        struct Trickle(Vec<u8>);
+
+        impl Write for Trickle {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                let n = buf.len().min(2);
+                self.0.extend_from_slice(&buf[..n]);
+                Ok(n)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut ring = ByteRing::new(4);
+        ring.extend_from_slice(b"xyz");
+        ring.drain_into(&mut [0; 2]);
+        ring.extend_from_slice(b"abc"); // Содержимое "zabc" проходит через границу
+
+        let mut sink = Trickle(Vec::new());
+        assert_eq!(ring.flush_into(&mut sink).unwrap(), 4);
+        assert_eq!(sink.0, b"zabc");
+        assert!(ring.is_empty());
+
+        ring.push(1).unwrap();
+        let mut full: &mut [u8] = &mut [];
+        assert!(ring.flush_into(&mut full).is_err()); // Приёмник ничего не принимает
+        assert_eq!(ring.len(), 1);
+
+        // Приёмник принял 2 байта, затем Ok(0): результат - принятое, не ошибка
+        ring.extend_from_slice(b"bc");
+        let mut buf = [0; 2];
+        let mut short: &mut [u8] = &mut buf;
+        assert_eq!(ring.flush_into(&mut short).unwrap(), 2);
+        assert_eq!(&buf, &[1, b'b']);
+        assert_eq!(ring.as_slices().0, b"c");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_extend_from_reader_exact() {
+        use std::io::{self, Read};
+
+        // Источник, отдающий по байту и затем WouldBlock
+        struct Drip<'a>(&'a [u8]);
+
+        impl Read for Drip<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                match self.0.split_first() {
+                    Some((&byte, rest)) if !buf.is_empty() => {
+                        buf[0] = byte;
+                        self.0 = rest;
+                        Ok(1)
+                    }
+                    _ => Err(io::ErrorKind::WouldBlock.into()),
+                }
+            }
+        }
+
+        let mut ring = ByteRing::new(8);
+        let mut source = Drip(b"abc");
+        assert_eq!(ring.extend_from_reader_exact(&mut source, 4).unwrap(), 3);
+        assert_eq!(ring.len(), 3); // Запись ещё не целая
+
+        let mut rest: &[u8] = b"defgh";
+        assert_eq!(ring.extend_from_reader_exact(&mut rest, 4).unwrap(), 1);
+        assert_eq!(rest, b"efgh"); // Лишнего не прочитано
+
+        assert!(ring.extend_from_reader_exact(&mut rest, 9).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[should_panic(expected = "Источник прочитал больше выданного места!")]
+    fn test_reader_exact_rejects_lying_reader() {
+        struct Liar;
+
+        impl std::io::Read for Liar {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                Ok(buf.len() + 1)
+            }
+        }
+
+        let _ = ByteRing::new(8).extend_from_reader_exact(&mut Liar, 4);
+    }
+
+    #[test]
+    fn test_checksum_ignores_layout() {
+        let mut straight = ByteRing::new(8);
+        straight.extend_from_slice(b"window");
+
+        let mut wrapped = ByteRing::new(8);
+        wrapped.extend_from_slice(b"xxxxx");
+        wrapped.drain_into(&mut [0; 5]);
+        wrapped.extend_from_slice(b"window"); // То же содержимое через границу массива
+
+        assert_eq!(straight.checksum(), wrapped.checksum());
+        assert_eq!(wrapped.len(), 6); // Содержимое не вычитано
+    }
+
+    #[cfg(feature = "encode")]
+    #[test]
+    fn test_drain_hex_and_base64() {
+        let mut ring = ByteRing::new(8);
+        ring.extend_from_slice(b"xxxxx");
+        ring.drain_into(&mut [0; 5]);
+        ring.extend_from_slice(b"Man\x01\xff"); // Проходит через границу массива
+
+        assert_eq!(ring.drain_hex(2), "4d61");
+        assert_eq!(ring.drain_base64(10), "bgH/"); // Всё, что осталось
+        assert!(ring.is_empty());
+
+        ring.extend_from_slice(b"Ma");
+        assert_eq!(ring.drain_base64(2), "TWE=");
+        ring.extend_from_slice(b"M");
+        assert_eq!(ring.drain_base64(1), "TQ==");
+    }
+
+    #[test]
+    fn test_windows() {
+        let mut ring = ByteRing::new(4);
+        ring.extend_from_slice(b"xxa");
+        ring.drain_into(&mut [0; 2]);
+        ring.extend_from_slice(b"bcd");
+
+        let windows: Vec<Vec<u8>> = ring.windows(3).map(|(a, b)| [a, b].concat()).collect();
+        assert_eq!(windows, [b"abc".to_vec(), b"bcd".to_vec()]);
+        assert_eq!(ring.windows(3).nth(1), Some((&b"b"[..], &b"cd"[..]))); // Окно на шве
+        assert_eq!(ring.windows(5).count(), 0);
+    }
+
+    #[test]
+    fn test_find() {
+        let mut ring = ByteRing::new(16);
+        ring.extend_from_slice(&[0; 10]);
+        ring.drain_into(&mut [0; 10]);
+        ring.extend_from_slice(b"GET /\r\n\r\nbody"); // Шов проходит внутри "\r\n\r\n"
+
+        assert_eq!(ring.as_slices().0, b"GET /\r");
+        assert_eq!(ring.find(b"\r\n\r\n"), Some(5));
+        assert_eq!(ring.find(b"GET"), Some(0));
+        assert_eq!(ring.find(b"body"), Some(9));
+        assert_eq!(ring.find(b"bodyx"), None);
+        assert_eq!(ring.find(b""), Some(0));
+    }
+
+    #[cfg(all(feature = "tracing", feature = "std"))]
+    #[test]
+    fn test_tracing_spans() {
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        // Подписчик, собирающий поля всех спанов в строки "имя=значение"
+        #[derive(Clone, Default)]
+        struct Capture(Arc<Mutex<Vec<Vec<String>>>>);
+
+        struct Fields<'a>(&'a mut Vec<String>);
+
+        impl Visit for Fields<'_> {
+            fn record_debug(&mut self, field: &Field, value: &dyn core::fmt::Debug) {
+                self.0.push(format!("{}={:?}", field.name(), value));
+            }
+        }
+
+        impl Subscriber for Capture {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+                let mut spans = self.0.lock().unwrap();
+                let mut fields = Vec::new();
+                attrs.record(&mut Fields(&mut fields));
+                spans.push(fields);
+                Id::from_u64(spans.len() as u64)
+            }
+
+            fn record(&self, id: &Id, values: &Record<'_>) {
+                let mut spans = self.0.lock().unwrap();
+                values.record(&mut Fields(&mut spans[id.into_u64() as usize - 1]));
+            }
+
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, _: &Event<'_>) {}
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let capture = Capture::default();
+        tracing::subscriber::with_default(capture.clone(), || {
+            let mut ring = ByteRing::new(4);
+            ring.extend_from_slice(b"abcdef");
+            ring.drain_into(&mut [0; 1]);
+            ring.flush_into(&mut Vec::new()).unwrap();
+            ring.fill_from(&mut &b"xy"[..]).unwrap();
+        });
+
+        let spans = capture.0.lock().unwrap();
+        let expected = [
+            "op=\"extend\" requested=6 capacity=4 moved=4 len=4",
+            "op=\"drain\" requested=1 capacity=4 moved=1 len=3",
+            "op=\"flush_into\" requested=3 capacity=4 moved=3 len=0",
+            "op=\"fill_from\" requested=4 capacity=4 moved=2 len=2",
+        ];
+        let spans: Vec<String> = spans.iter().map(|fields| fields.join(" ")).collect();
+        assert_eq!(spans, expected);
+    }
+}
@@ -0,0 +1,464 @@
+// Ограниченный канал много отправителей - один получатель поверх BlockingRing.
+// Поведение как у std::sync::mpsc::sync_channel: отправители клонируются,
+// уничтожение всех отправителей или получателя разрывает канал
+
+use core::fmt;
+use std::time::{Duration, Instant};
+
+use crate::blocking::Wait;
+use crate::shim::{Arc, AtomicUsize, Ordering};
+use crate::{BlockingError, BlockingRing};
+
+// 0. Ошибки канала; отправка всегда возвращает неотправленное значение
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrySendError<T> {
+    Full(T),         // Нет места
+    Disconnected(T), // Получатель уничтожен
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SendTimeoutError<T> {
+    Timeout(T),      // Место так и не освободилось
+    Disconnected(T), // Получатель уничтожен
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecvError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryRecvError {
+    Empty,        // Данных пока нет
+    Disconnected, // Данных нет и не будет: все отправители уничтожены
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecvTimeoutError {
+    Timeout,      // Данные так и не пришли
+    Disconnected, // Данных нет и не будет
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Получатель уничтожен!")
+    }
+}
+
+impl<T> fmt::Display for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => write!(f, "Буфер Заполнен!"),
+            TrySendError::Disconnected(_) => write!(f, "Получатель уничтожен!"),
+        }
+    }
+}
+
+impl<T> fmt::Display for SendTimeoutError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SendTimeoutError::Timeout(_) => write!(f, "Время ожидания истекло!"),
+            SendTimeoutError::Disconnected(_) => write!(f, "Получатель уничтожен!"),
+        }
+    }
+}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Все отправители уничтожены!")
+    }
+}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => write!(f, "Канал пуст!"),
+            TryRecvError::Disconnected => write!(f, "Все отправители уничтожены!"),
+        }
+    }
+}
+
+impl fmt::Display for RecvTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecvTimeoutError::Timeout => write!(f, "Время ожидания истекло!"),
+            RecvTimeoutError::Disconnected => write!(f, "Все отправители уничтожены!"),
+        }
+    }
+}
+
+impl<T: fmt::Debug> core::error::Error for SendError<T> {}
+impl<T: fmt::Debug> core::error::Error for TrySendError<T> {}
+impl<T: fmt::Debug> core::error::Error for SendTimeoutError<T> {}
+impl core::error::Error for RecvError {}
+impl core::error::Error for TryRecvError {}
+impl core::error::Error for RecvTimeoutError {}
+
+// 1. Общая часть: кольцо и число живых отправителей и получателей. Разрыв - это
+// закрытие кольца: отправка сразу получает ошибку, а получатель дочитывает остаток.
+// Слабые отправители в счёт не входят и канал открытым не держат
+#[derive(Debug)]
+struct Chan<T> {
+    ring: BlockingRing<T>,
+    senders: AtomicUsize,
+    receivers: AtomicUsize,
+}
+
+// 2. Создаём канал на capacity сообщений
+pub fn channel<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    let chan = Arc::new(Chan {
+        ring: BlockingRing::new(capacity),
+        senders: AtomicUsize::new(1),
+        receivers: AtomicUsize::new(1),
+    });
+    let sender = Sender {
+        chan: Arc::clone(&chan),
+    };
+    (sender, Receiver { chan })
+}
+
+// 3. Отправитель
+#[derive(Debug)]
+pub struct Sender<T> {
+    chan: Arc<Chan<T>>,
+}
+
+impl<T> Sender<T> {
+    // 3.1. Отправка с ожиданием места
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        self.chan
+            .ring
+            .push_or_return(value, Wait::Forever)
+            .map_err(|(_, value)| SendError(value))
+    }
+
+    // 3.1.1. Отправка с ожиданием места до момента deadline (бюджет кадра и т.п.)
+    pub fn send_deadline(&self, value: T, deadline: Instant) -> Result<(), SendTimeoutError<T>> {
+        self.chan
+            .ring
+            .push_or_return(value, Wait::Until(deadline))
+            .map_err(|(e, value)| match e {
+                BlockingError::Timeout => SendTimeoutError::Timeout(value),
+                _ => SendTimeoutError::Disconnected(value),
+            })
+    }
+
+    // 3.1.2. То же с относительным сроком; срок за пределами часов - ожидание без срока
+    pub fn send_timeout(&self, value: T, timeout: Duration) -> Result<(), SendTimeoutError<T>> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.send_deadline(value, deadline),
+            None => self
+                .send(value)
+                .map_err(|SendError(value)| SendTimeoutError::Disconnected(value)),
+        }
+    }
+
+    // 3.2. Отправка без ожидания
+    pub fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        self.chan
+            .ring
+            .push_or_return(value, Wait::No)
+            .map_err(|(e, value)| match e {
+                BlockingError::Full => TrySendError::Full(value),
+                _ => TrySendError::Disconnected(value),
+            })
+    }
+
+    pub fn len(&self) -> usize {
+        self.chan.ring.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chan.ring.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.chan.ring.capacity()
+    }
+
+    // 3.3. Слабая ссылка на канал: не мешает разрыву, когда уйдут настоящие отправители
+    pub fn downgrade(&self) -> WeakSender<T> {
+        WeakSender {
+            chan: Arc::clone(&self.chan),
+        }
+    }
+
+    // 3.4. Сколько живых (сильных) отправителей и получателей
+    pub fn sender_count(&self) -> usize {
+        self.chan.senders.load(Ordering::Acquire)
+    }
+
+    pub fn receiver_count(&self) -> usize {
+        self.chan.receivers.load(Ordering::Acquire)
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.chan.senders.fetch_add(1, Ordering::Relaxed);
+        Sender {
+            chan: Arc::clone(&self.chan),
+        }
+    }
+}
+
+// 3.5. Последний отправитель разрывает канал
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        if self.chan.senders.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.chan.ring.close();
+        }
+    }
+}
+
+// 3.6. Слабый отправитель: сам не отправляет, но может стать отправителем,
+// пока жив хотя бы один настоящий
+#[derive(Debug)]
+pub struct WeakSender<T> {
+    chan: Arc<Chan<T>>,
+}
+
+impl<T> WeakSender<T> {
+    // 3.6.1. None - все отправители уже уничтожены, канал разорван
+    pub fn upgrade(&self) -> Option<Sender<T>> {
+        let mut senders = self.chan.senders.load(Ordering::Relaxed);
+        loop {
+            if senders == 0 {
+                return None;
+            }
+            match self.chan.senders.compare_exchange_weak(
+                senders,
+                senders + 1,
+                Ordering::AcqRel,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => {
+                    return Some(Sender {
+                        chan: Arc::clone(&self.chan),
+                    })
+                }
+                Err(actual) => senders = actual,
+            }
+        }
+    }
+
+    pub fn sender_count(&self) -> usize {
+        self.chan.senders.load(Ordering::Acquire)
+    }
+
+    pub fn receiver_count(&self) -> usize {
+        self.chan.receivers.load(Ordering::Acquire)
+    }
+}
+
+impl<T> Clone for WeakSender<T> {
+    fn clone(&self) -> Self {
+        WeakSender {
+            chan: Arc::clone(&self.chan),
+        }
+    }
+}
+
+// 4. Получатель (единственный)
+#[derive(Debug)]
+pub struct Receiver<T> {
+    chan: Arc<Chan<T>>,
+}
+
+impl<T> Receiver<T> {
+    // 4.1. Получение с ожиданием; Err - канал пуст и все отправители уничтожены
+    pub fn recv(&self) -> Result<T, RecvError> {
+        match self.chan.ring.pop() {
+            Ok(Some(value)) => Ok(value),
+            _ => Err(RecvError),
+        }
+    }
+
+    // 4.1.1. Получение с ожиданием до момента deadline
+    pub fn recv_deadline(&self, deadline: Instant) -> Result<T, RecvTimeoutError> {
+        match self.chan.ring.pop_deadline(deadline) {
+            Ok(Some(value)) => Ok(value),
+            Err(BlockingError::Timeout) => Err(RecvTimeoutError::Timeout),
+            _ => Err(RecvTimeoutError::Disconnected),
+        }
+    }
+
+    // 4.1.2. То же с относительным сроком
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.recv_deadline(deadline),
+            None => self.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        }
+    }
+
+    // 4.2. Получение без ожидания. Флаг разрыва читаем до попытки: всё, что
+    // отправлено до разрыва, эта попытка уже увидит
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        let disconnected = self.chan.ring.is_closed();
+        match self.chan.ring.try_pop() {
+            Ok(Some(value)) => Ok(value),
+            Ok(None) if !disconnected => Err(TryRecvError::Empty),
+            _ => Err(TryRecvError::Disconnected),
+        }
+    }
+
+    // 4.3. Итератор с ожиданием до разрыва и итератор по уже отправленному
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        core::iter::from_fn(|| self.recv().ok())
+    }
+
+    pub fn try_iter(&self) -> impl Iterator<Item = T> + '_ {
+        core::iter::from_fn(|| self.try_recv().ok())
+    }
+
+    pub fn len(&self) -> usize {
+        self.chan.ring.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chan.ring.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.chan.ring.capacity()
+    }
+
+    // 4.4. Сколько живых отправителей (слабые не считаются)
+    pub fn sender_count(&self) -> usize {
+        self.chan.senders.load(Ordering::Acquire)
+    }
+}
+
+// 4.5. Без получателя отправлять некуда
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.chan.receivers.store(0, Ordering::Release);
+        self.chan.ring.close();
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::{
+        channel, RecvError, RecvTimeoutError, SendError, SendTimeoutError, TryRecvError,
+        TrySendError,
+    };
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_send_and_recv() {
+        let (tx, rx) = channel(2);
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+
+        tx.send(1).unwrap();
+        assert_eq!(tx.try_send(2), Ok(()));
+        assert_eq!(tx.try_send(3), Err(TrySendError::Full(3)));
+        assert_eq!(rx.recv(), Ok(1));
+        assert_eq!(rx.try_recv(), Ok(2));
+        assert_eq!(rx.capacity(), 2);
+    }
+
+    #[test]
+    fn test_senders_drop_disconnects_after_drain() {
+        let (tx, rx) = channel(4);
+        let tx2 = tx.clone();
+        tx.send(1).unwrap();
+        drop(tx);
+        tx2.send(2).unwrap(); // Клон ещё жив - канал цел
+        drop(tx2);
+
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+        assert_eq!(rx.recv(), Err(RecvError));
+    }
+
+    #[test]
+    fn test_receiver_drop_returns_value() {
+        let (tx, rx) = channel(1);
+        tx.send("first").unwrap();
+        drop(rx);
+
+        assert_eq!(tx.send("lost"), Err(SendError("lost")));
+        assert_eq!(tx.try_send("x"), Err(TrySendError::Disconnected("x")));
+    }
+
+    #[test]
+    fn test_weak_sender() {
+        let (tx, rx) = channel(4);
+        let weak = tx.downgrade();
+        assert_eq!((weak.sender_count(), weak.receiver_count()), (1, 1));
+
+        let upgraded = weak.upgrade().unwrap();
+        upgraded.send(1).unwrap();
+        assert_eq!(rx.sender_count(), 2);
+        drop(upgraded);
+        drop(tx);
+
+        // Остался только слабый отправитель - канал разорван
+        assert_eq!(rx.sender_count(), 0);
+        assert_eq!(rx.recv(), Ok(1));
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_receiver_count() {
+        let (tx, rx) = channel::<u8>(1);
+        assert_eq!(tx.receiver_count(), 1);
+        drop(rx);
+        assert_eq!(tx.receiver_count(), 0);
+        assert_eq!(tx.downgrade().receiver_count(), 0);
+    }
+
+    #[test]
+    fn test_deadlines() {
+        let (tx, rx) = channel(1);
+        let deadline = Instant::now() + Duration::from_millis(20);
+        assert_eq!(rx.recv_deadline(deadline), Err(RecvTimeoutError::Timeout));
+        assert!(Instant::now() >= deadline);
+
+        tx.send(1).unwrap();
+        let soon = Instant::now() + Duration::from_millis(10);
+        assert_eq!(tx.send_deadline(2, soon), Err(SendTimeoutError::Timeout(2)));
+
+        let sender = thread::spawn(move || tx.send_timeout(3, Duration::from_secs(10)));
+        assert_eq!(rx.recv_timeout(Duration::from_secs(10)), Ok(1));
+        assert_eq!(rx.recv_timeout(Duration::from_secs(10)), Ok(3)); // Место освободилось вовремя
+        assert_eq!(sender.join().unwrap(), Ok(()));
+        assert_eq!(
+            rx.recv_deadline(Instant::now()),
+            Err(RecvTimeoutError::Disconnected)
+        );
+    }
+
+    #[test]
+    fn test_many_producers() {
+        let (tx, rx) = channel(4);
+        let producers: Vec<_> = (0..4)
+            .map(|id| {
+                let tx = tx.clone();
+                thread::spawn(move || {
+                    for i in 0..100 {
+                        tx.send(id * 1000 + i).unwrap();
+                    }
+                })
+            })
+            .collect();
+        drop(tx);
+
+        let mut last = [None; 4];
+        let mut count = 0;
+        for value in rx.iter() {
+            let (id, i) = (value / 1000, value % 1000);
+            assert!(last[id].is_none_or(|prev| prev < i)); // Порядок одного отправителя сохранён
+            last[id] = Some(i);
+            count += 1;
+        }
+        assert_eq!(count, 400);
+        for producer in producers {
+            producer.join().unwrap();
+        }
+    }
+}
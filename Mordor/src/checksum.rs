@@ -0,0 +1,95 @@
+// Контрольные суммы содержимого без вычитывания: CRC32 по умолчанию,
+// xxHash32 при включённой возможности xxhash
+
+// 1. Таблица CRC32 (IEEE, отражённый полином), строится при компиляции
+const POLY: u32 = 0xEDB8_8320;
+const TABLE: [u32; 256] = build_table();
+
+const fn build_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ POLY
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+// 2. Потоковый CRC32: части можно подавать по очереди
+#[derive(Debug, Clone, Copy)]
+pub struct Crc32 {
+    state: u32,
+}
+
+impl Crc32 {
+    pub const fn new() -> Self {
+        Crc32 { state: !0 }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.state = (self.state >> 8) ^ TABLE[((self.state ^ byte as u32) & 0xFF) as usize];
+        }
+    }
+
+    pub fn finish(&self) -> u32 {
+        !self.state
+    }
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// 3. Сумма по частям логического содержимого выбранным алгоритмом
+#[cfg(not(feature = "xxhash"))]
+pub fn of_parts(parts: &[&[u8]]) -> u32 {
+    let mut crc = Crc32::new();
+    for part in parts {
+        crc.update(part);
+    }
+    crc.finish()
+}
+
+#[cfg(feature = "xxhash")]
+pub fn of_parts(parts: &[&[u8]]) -> u32 {
+    let mut hasher = xxhash_rust::xxh32::Xxh32::new(0);
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.digest()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{of_parts, Crc32};
+
+    #[test]
+    fn test_crc32_check_value() {
+        let mut crc = Crc32::new();
+        crc.update(b"12345");
+        crc.update(b"6789");
+        assert_eq!(crc.finish(), 0xCBF4_3926); // Контрольное значение CRC-32/IEEE
+    }
+
+    #[test]
+    fn test_parts_match_whole() {
+        assert_eq!(
+            of_parts(&[b"hello ", b"world"]),
+            of_parts(&[b"hello world", b""])
+        );
+        assert_ne!(of_parts(&[b"hello"]), of_parts(&[b"hellp"]));
+    }
+}
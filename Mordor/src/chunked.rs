@@ -0,0 +1,272 @@
+// Байтовое кольцо большой ёмкости на блоках фиксированного размера: блок
+// выделяется при первой записи в него, поэтому почти пустое кольцо на сотни
+// мегабайт не занимает всю память сразу
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::partial::PartialWrite;
+use crate::ring::full_error;
+
+// 1. Размер блока по умолчанию
+pub const CHUNK_SIZE: usize = 64 * 1024;
+
+// 2. Логическое окно [head, head + len) поверх блоков; блок, в который
+// ещё ни разу не писали, не выделен
+#[derive(Debug, Clone)]
+pub struct ChunkedByteRing {
+    chunks: Vec<Option<Box<[u8]>>>,
+    chunk_size: usize,
+    capacity: usize,
+    head: usize,
+    len: usize,
+}
+
+impl ChunkedByteRing {
+    // 2.1. Кольцо на capacity байт с блоками по CHUNK_SIZE
+    pub fn new(capacity: usize) -> Self {
+        Self::with_chunk_size(capacity, CHUNK_SIZE)
+    }
+
+    pub fn with_chunk_size(capacity: usize, chunk_size: usize) -> Self {
+        assert!(capacity > 0, "В буфере есть место!");
+        assert!(chunk_size > 0, "Блок не может быть пустым!");
+
+        ChunkedByteRing {
+            chunks: vec![None; capacity.div_ceil(chunk_size)],
+            chunk_size,
+            capacity,
+            head: 0,
+            len: 0,
+        }
+    }
+
+    // 3. Размеры
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn free(&self) -> usize {
+        self.capacity - self.len
+    }
+
+    // 3.1. Сколько байт хранилища действительно выделено
+    pub fn allocated_bytes(&self) -> usize {
+        self.chunks.iter().flatten().map(|chunk| chunk.len()).sum()
+    }
+
+    // 3.2. Память в куче: выделенные блоки и таблица блоков
+    pub fn heap_bytes(&self) -> usize {
+        self.allocated_bytes() + self.chunks.capacity() * core::mem::size_of::<Option<Box<[u8]>>>()
+    }
+
+    // 4. Запись одного байта
+    pub fn push(&mut self, byte: u8) -> Result<(), String> {
+        if self.is_full() {
+            return Err(full_error());
+        }
+        self.extend_from_slice(&[byte]);
+        Ok(())
+    }
+
+    // 5. Чтение одного байта
+    pub fn pop(&mut self) -> Option<u8> {
+        let mut byte = [0];
+        (self.drain_into(&mut byte) == 1).then_some(byte[0])
+    }
+
+    // 6. Запись стольких байт, сколько помещается; блоки выделяются по мере надобности
+    pub fn extend_from_slice(&mut self, data: &[u8]) -> usize {
+        let n = data.len().min(self.free());
+        let mut written = 0;
+        while written < n {
+            let pos = self.wrap(self.head + self.len + written);
+            let (index, offset) = (pos / self.chunk_size, pos % self.chunk_size);
+            let size = self.chunk_size.min(self.capacity - index * self.chunk_size);
+            let chunk = self.chunks[index].get_or_insert_with(|| vec![0; size].into_boxed_slice());
+
+            let step = (n - written).min(size - offset);
+            chunk[offset..offset + step].copy_from_slice(&data[written..written + step]);
+            written += step;
+        }
+        self.len += n;
+        n
+    }
+
+    // 6.1. То же с подробным результатом: сколько осталось и почему остановились
+    pub fn extend_partial(&mut self, data: &[u8]) -> PartialWrite {
+        PartialWrite::new(self.extend_from_slice(data), data.len())
+    }
+
+    // 7. Копирование в срез без вычитывания
+    pub fn peek_into(&self, out: &mut [u8]) -> usize {
+        let n = out.len().min(self.len);
+        let mut read = 0;
+        while read < n {
+            let pos = self.wrap(self.head + read);
+            let (index, offset) = (pos / self.chunk_size, pos % self.chunk_size);
+            let chunk = self.chunks[index]
+                .as_deref()
+                .expect("Блок с данными не выделен!");
+
+            let step = (n - read).min(chunk.len() - offset);
+            out[read..read + step].copy_from_slice(&chunk[offset..offset + step]);
+            read += step;
+        }
+        n
+    }
+
+    // 7.1. Чтение в срез вызывающего; возвращает, сколько байт скопировано
+    pub fn drain_into(&mut self, out: &mut [u8]) -> usize {
+        let n = self.peek_into(out);
+        self.discard(n)
+    }
+
+    // 7.2. Отбрасывание до n самых старых байт
+    pub fn discard(&mut self, n: usize) -> usize {
+        let n = n.min(self.len);
+        self.head = self.wrap(self.head + n);
+        self.len -= n;
+        n
+    }
+
+    pub fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+    }
+
+    // 8. Возврат памяти блоков, в которых нет ни одного байта окна
+    // (после всплеска нагрузки); возвращает, сколько байт освобождено
+    pub fn release_unused(&mut self) -> usize {
+        let mut released = 0;
+        for index in 0..self.chunks.len() {
+            if !self.chunk_in_window(index) {
+                if let Some(chunk) = self.chunks[index].take() {
+                    released += chunk.len();
+                }
+            }
+        }
+        released
+    }
+
+    // 8.1. Пересекается ли блок с окном [head, head + len)
+    fn chunk_in_window(&self, index: usize) -> bool {
+        let start = index * self.chunk_size;
+        let end = (start + self.chunk_size).min(self.capacity);
+        let overlaps = |from: usize, to: usize| from < end && start < to;
+
+        let window_end = self.head + self.len;
+        if window_end <= self.capacity {
+            overlaps(self.head, window_end)
+        } else {
+            overlaps(self.head, self.capacity) || overlaps(0, window_end - self.capacity)
+        }
+    }
+
+    // 9. Закольцовывание индекса меньше 2 * capacity без деления
+    fn wrap(&self, index: usize) -> usize {
+        if index >= self.capacity {
+            index - self.capacity
+        } else {
+            index
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChunkedByteRing, CHUNK_SIZE};
+    use crate::ByteRing;
+    use alloc::vec;
+
+    #[test]
+    fn test_allocates_lazily() {
+        let mut ring = ChunkedByteRing::new(256 * 1024 * 1024);
+        assert_eq!(ring.allocated_bytes(), 0);
+
+        assert_eq!(ring.extend_from_slice(&[7; 10]), 10);
+        assert_eq!(ring.allocated_bytes(), CHUNK_SIZE); // Один блок на сотни мегабайт
+
+        let mut out = [0; 10];
+        assert_eq!(ring.drain_into(&mut out), 10);
+        assert_eq!(out, [7; 10]);
+    }
+
+    #[test]
+    fn test_heap_bytes() {
+        let mut ring = ChunkedByteRing::with_chunk_size(10, 4);
+        let table = 3 * core::mem::size_of::<Option<Box<[u8]>>>();
+        assert_eq!(ring.heap_bytes(), table);
+
+        ring.extend_from_slice(b"abcde"); // Два блока
+        assert_eq!(ring.heap_bytes(), table + 8);
+    }
+
+    #[test]
+    fn test_matches_byte_ring() {
+        // Ёмкость не кратна блоку: последний блок короче
+        let mut chunked = ChunkedByteRing::with_chunk_size(23, 5);
+        let mut plain = ByteRing::new(23);
+
+        let mut next = 0u8;
+        for round in 0..40 {
+            let data: vec::Vec<u8> = (0..round % 13)
+                .map(|_| {
+                    next = next.wrapping_add(1);
+                    next
+                })
+                .collect();
+            assert_eq!(
+                chunked.extend_from_slice(&data),
+                plain.extend_from_slice(&data)
+            );
+
+            let (mut a, mut b) = ([0; 9], [0; 9]);
+            let take = round % 9;
+            assert_eq!(
+                chunked.drain_into(&mut a[..take]),
+                plain.drain_into(&mut b[..take])
+            );
+            assert_eq!(a, b);
+            assert_eq!(chunked.len(), plain.len());
+        }
+    }
+
+    #[test]
+    fn test_push_pop_and_release() {
+        let mut ring = ChunkedByteRing::with_chunk_size(8, 4);
+        for byte in 0..8 {
+            assert!(ring.push(byte).is_ok());
+        }
+        assert!(ring.push(8).is_err());
+        assert_eq!(ring.allocated_bytes(), 8);
+
+        assert_eq!(ring.discard(5), 5); // В окне остались байты 5..8 второго блока
+        assert_eq!(ring.release_unused(), 4);
+        assert_eq!(ring.allocated_bytes(), 4);
+
+        assert!(ring.push(8).is_ok()); // Первый блок выделяется заново
+        assert_eq!(ring.pop(), Some(5));
+        let mut out = [0; 3];
+        assert_eq!(ring.peek_into(&mut out), 3);
+        assert_eq!(out, [6, 7, 8]);
+
+        ring.clear();
+        assert_eq!(ring.release_unused(), 8);
+        assert_eq!(ring.pop(), None);
+    }
+}
@@ -0,0 +1,168 @@
+// Разбор сообщений прямо из ByteRing: трейт Decode, цикл разбора
+// и два готовых декодера - с префиксом длины и с разделителем
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::ByteRing;
+
+// 1. Декодер забирает из кольца одно целое сообщение или ждёт данных (Ok(None))
+pub trait Decode {
+    type Item;
+    type Error;
+
+    fn decode(&mut self, buf: &mut ByteRing) -> Result<Option<Self::Item>, Self::Error>;
+}
+
+// 1.1. Цикл разбора: все сообщения, которые уже целиком лежат в кольце
+pub fn decode_all<D: Decode>(
+    decoder: &mut D,
+    buf: &mut ByteRing,
+) -> Result<Vec<D::Item>, D::Error> {
+    let mut items = Vec::new();
+    while let Some(item) = decoder.decode(buf)? {
+        items.push(item);
+    }
+    Ok(items)
+}
+
+// 2. Ошибки готовых декодеров
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodecError {
+    FrameTooLarge(usize), // Сообщение длиннее допустимого (или не влезет в кольцо)
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodecError::FrameTooLarge(n) => write!(f, "Сообщение слишком длинное: {} байт", n),
+        }
+    }
+}
+
+impl core::error::Error for CodecError {}
+
+// 3. Кадры с префиксом длины: u32 в сетевом порядке байт, затем содержимое
+#[derive(Debug, Clone)]
+pub struct LengthPrefixed {
+    max_len: usize,
+}
+
+impl LengthPrefixed {
+    pub fn new(max_len: usize) -> Self {
+        LengthPrefixed { max_len }
+    }
+}
+
+impl Decode for LengthPrefixed {
+    type Item = Vec<u8>;
+    type Error = CodecError;
+
+    fn decode(&mut self, buf: &mut ByteRing) -> Result<Option<Vec<u8>>, CodecError> {
+        let mut prefix = [0; 4];
+        if buf.peek_into(&mut prefix) < prefix.len() {
+            return Ok(None); // Заголовок ещё не пришёл
+        }
+
+        let len = u32::from_be_bytes(prefix) as usize;
+        if len > self.max_len || prefix.len() + len > buf.capacity() {
+            return Err(CodecError::FrameTooLarge(len));
+        }
+        if buf.len() < prefix.len() + len {
+            return Ok(None); // Содержимое пришло не целиком
+        }
+
+        buf.discard(prefix.len());
+        let mut frame = vec![0; len];
+        buf.drain_into(&mut frame);
+        Ok(Some(frame))
+    }
+}
+
+// 4. Кадры, завершённые разделителем (например, b"\r\n"); разделитель отбрасывается
+#[derive(Debug, Clone)]
+pub struct Delimited {
+    delimiter: Vec<u8>,
+    max_len: usize,
+}
+
+impl Delimited {
+    pub fn new(delimiter: &[u8], max_len: usize) -> Self {
+        assert!(!delimiter.is_empty(), "Разделитель не может быть пустым!");
+
+        Delimited {
+            delimiter: delimiter.to_vec(),
+            max_len,
+        }
+    }
+}
+
+impl Decode for Delimited {
+    type Item = Vec<u8>;
+    type Error = CodecError;
+
+    fn decode(&mut self, buf: &mut ByteRing) -> Result<Option<Vec<u8>>, CodecError> {
+        let Some(len) = buf.find(&self.delimiter) else {
+            // Разделителя нет: либо ждём данных, либо он уже не поместится
+            if buf.len() > self.max_len || buf.is_full() {
+                return Err(CodecError::FrameTooLarge(buf.len()));
+            }
+            return Ok(None);
+        };
+        if len > self.max_len {
+            return Err(CodecError::FrameTooLarge(len));
+        }
+
+        let mut frame = vec![0; len];
+        buf.drain_into(&mut frame);
+        buf.discard(self.delimiter.len());
+        Ok(Some(frame))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_all, CodecError, Decode, Delimited, LengthPrefixed};
+    use crate::ByteRing;
+
+    #[test]
+    fn test_length_prefixed() {
+        let mut ring = ByteRing::new(32);
+        let mut decoder = LengthPrefixed::new(16);
+        ring.extend_from_slice(&[0, 0, 0, 2, b'h', b'i', 0, 0, 0, 3, b'y']);
+
+        assert_eq!(
+            decode_all(&mut decoder, &mut ring),
+            Ok(vec![b"hi".to_vec()])
+        );
+        assert_eq!(ring.len(), 5); // Второй кадр ждёт остатка
+
+        ring.extend_from_slice(b"o!");
+        assert_eq!(decoder.decode(&mut ring), Ok(Some(b"yo!".to_vec())));
+        assert!(ring.is_empty());
+
+        ring.extend_from_slice(&[0, 0, 1, 0]);
+        assert_eq!(
+            decoder.decode(&mut ring),
+            Err(CodecError::FrameTooLarge(256))
+        );
+    }
+
+    #[test]
+    fn test_delimited() {
+        let mut ring = ByteRing::new(16);
+        let mut decoder = Delimited::new(b"\r\n", 8);
+        ring.extend_from_slice(b"PING\r\nPO");
+
+        assert_eq!(decoder.decode(&mut ring), Ok(Some(b"PING".to_vec())));
+        assert_eq!(decoder.decode(&mut ring), Ok(None));
+
+        ring.extend_from_slice(b"NG\r\n\r\n");
+        let frames = decode_all(&mut decoder, &mut ring).unwrap();
+        assert_eq!(frames, [b"PONG".to_vec(), Vec::new()]);
+
+        ring.extend_from_slice(b"too long line");
+        assert!(decoder.decode(&mut ring).is_err());
+    }
+}
@@ -0,0 +1,164 @@
+// Кольцевой буфер с ёмкостью, заданной на этапе компиляции (без выделения памяти в куче)
+
+// 1. Хранилище - массив фиксированного размера
+#[derive(Debug)]
+pub struct ConstRingBuffer<T, const N: usize> {
+    buffer: [Option<T>; N], // Хранилище данных (None - пустая ячейка)
+    head: usize,            // Индекс для чтения (голова)
+    tail: usize,            // Индекс для записи (хвост)
+    size: usize,            // Текущее количество элементов
+}
+
+impl<T, const N: usize> ConstRingBuffer<T, N> {
+    // 1.1. Создаём пустой буфер; const fn позволяет класть буфер в static
+    pub const fn new() -> Self {
+        assert!(N > 0, "В буфере есть место!");
+
+        ConstRingBuffer {
+            buffer: [const { None }; N],
+            head: 0,
+            tail: 0,
+            size: 0,
+        }
+    }
+
+    // 2. Размеры
+    pub const fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub const fn is_full(&self) -> bool {
+        self.size == N
+    }
+
+    pub const fn len(&self) -> usize {
+        self.size
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    // 3. Запись элемента; без кучи строку ошибки не создать, поэтому возвращаем значение.
+    // Индексы берутся по модулю N: так отсутствие паники видно компилятору (no_panic)
+    #[cfg_attr(all(feature = "no_panic", not(debug_assertions)), no_panic::no_panic)]
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(value);
+        }
+
+        self.buffer[self.tail % N] = Some(value);
+        self.tail = (self.tail + 1) % N;
+        self.size += 1;
+        Ok(())
+    }
+
+    // 4. Чтение элемента
+    #[cfg_attr(all(feature = "no_panic", not(debug_assertions)), no_panic::no_panic)]
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let value = self.buffer[self.head % N].take();
+        self.head = (self.head + 1) % N;
+        self.size -= 1;
+        value
+    }
+
+    // 5. Доступ по логическому индексу (0 - самый старый)
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.size {
+            return None;
+        }
+
+        self.buffer[(self.head + index) % N].as_ref()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        (0..self.size).filter_map(move |index| self.get(index))
+    }
+
+    // 6. Чтение в срез вызывающего; возвращает, сколько элементов прочитано
+    #[cfg_attr(all(feature = "no_panic", not(debug_assertions)), no_panic::no_panic)]
+    pub fn drain_into(&mut self, out: &mut [T]) -> usize {
+        let mut count = 0;
+        for slot in out.iter_mut() {
+            let Some(value) = self.pop() else {
+                break; // Буфер опустел
+            };
+            *slot = value;
+            count += 1;
+        }
+        count
+    }
+}
+
+impl<T: Copy, const N: usize> ConstRingBuffer<T, N> {
+    // 7. Запись стольких элементов, сколько помещается (Copy: копирование не паникует)
+    #[cfg_attr(all(feature = "no_panic", not(debug_assertions)), no_panic::no_panic)]
+    pub fn extend_from_slice(&mut self, data: &[T]) -> usize {
+        let mut count = 0;
+        for &value in data {
+            if self.push(value).is_err() {
+                break; // Буфер полон
+            }
+            count += 1;
+        }
+        count
+    }
+}
+
+impl<T, const N: usize> Default for ConstRingBuffer<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConstRingBuffer;
+    use std::sync::Mutex;
+
+    // Глобальный буфер без ленивой инициализации
+    static LOG: Mutex<ConstRingBuffer<u8, 4096>> = Mutex::new(ConstRingBuffer::new());
+    const EMPTY: ConstRingBuffer<u8, 4> = ConstRingBuffer::new();
+
+    #[test]
+    fn test_basic_operations() {
+        let mut rb: ConstRingBuffer<u8, 2> = ConstRingBuffer::new();
+
+        assert!(rb.push(1).is_ok());
+        assert!(rb.push(2).is_ok());
+        assert!(rb.push(3).is_err());
+        assert_eq!(rb.pop(), Some(1));
+        assert!(rb.push(3).is_ok()); // Запись через границу массива
+        assert_eq!(rb.iter().copied().collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(rb.capacity(), 2);
+    }
+
+    #[test]
+    fn test_static_ring() {
+        LOG.lock().unwrap().push(42).unwrap();
+
+        assert_eq!(LOG.lock().unwrap().pop(), Some(42));
+        assert!(EMPTY.is_empty());
+        assert_eq!(EMPTY.capacity(), 4);
+    }
+
+    // Состояние спрятано от оптимизатора: с no_panic в release сборка теста
+    // не слинкуется, если в операциях остался путь к панике
+    #[test]
+    fn test_bulk_copy() {
+        let mut rb: ConstRingBuffer<u8, 3> = std::hint::black_box(ConstRingBuffer::new());
+
+        assert_eq!(rb.extend_from_slice(&[1, 2]), 2);
+        assert_eq!(rb.pop(), Some(1));
+        assert_eq!(rb.extend_from_slice(&[3, 4, 5]), 2); // Через границу и до заполнения
+
+        let mut out = [0; 4];
+        assert_eq!(rb.drain_into(&mut out), 3);
+        assert_eq!(out, [2, 3, 4, 0]);
+        assert_eq!(rb.drain_into(&mut out), 0);
+    }
+}
@@ -0,0 +1,166 @@
+// Двойной буфер (пинг-понг): писатель заполняет одну половину, читатель читает другую
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+use crate::RingBuffer;
+
+// 1. Две половины-кольца и индекс той, что сейчас отдана читателю
+#[derive(Debug)]
+pub struct DoubleBuffer<T> {
+    halves: [Mutex<RingBuffer<T>>; 2],
+    front: AtomicUsize, // Половина для чтения, другая - для записи
+}
+
+impl<T> DoubleBuffer<T> {
+    // 1.1. Создаём двойной буфер, каждая половина на capacity элементов
+    pub fn new(capacity: usize) -> Self {
+        DoubleBuffer {
+            halves: [
+                Mutex::new(RingBuffer::new(capacity)),
+                Mutex::new(RingBuffer::new(capacity)),
+            ],
+            front: AtomicUsize::new(0),
+        }
+    }
+
+    // 2. Запись в заднюю половину
+    pub fn push(&self, value: T) -> Result<(), String> {
+        self.back().push(value)
+    }
+
+    // 3. Чтение из передней половины
+    pub fn pop(&self) -> Option<T> {
+        self.front().pop()
+    }
+
+    // 4. Смена половин местами (нельзя вызывать, удерживая front()/back() в том же потоке)
+    pub fn swap(&self) {
+        // Держим обе половины, чтобы никто не писал и не читал во время смены
+        let _first = self.halves[0].lock().unwrap();
+        let _second = self.halves[1].lock().unwrap();
+        self.front.fetch_xor(1, Ordering::AcqRel);
+    }
+
+    // 5. Прямой доступ к половинам для пакетной работы
+    pub fn front(&self) -> MutexGuard<'_, RingBuffer<T>> {
+        self.lock_half(0)
+    }
+
+    pub fn back(&self) -> MutexGuard<'_, RingBuffer<T>> {
+        self.lock_half(1)
+    }
+
+    // 5.1. Замок половины front ^ side. Индекс перечитывается под замком: swap мог
+    // пройти между чтением и захватом, и тогда взята не та половина - пробуем снова.
+    // Пока замок держим мы, swap индекс не поменяет
+    fn lock_half(&self, side: usize) -> MutexGuard<'_, RingBuffer<T>> {
+        loop {
+            let index = self.front.load(Ordering::Acquire) ^ side;
+            let half = self.halves[index].lock().unwrap();
+            if self.front.load(Ordering::Acquire) ^ side == index {
+                return half;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DoubleBuffer;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_ping_pong() {
+        let db = DoubleBuffer::new(3);
+        db.push(1).unwrap();
+        db.push(2).unwrap();
+        assert_eq!(db.pop(), None); // Читатель ещё ничего не видит
+
+        db.swap();
+        db.push(3).unwrap(); // Пишем уже в другую половину
+        assert_eq!(db.pop(), Some(1));
+        assert_eq!(db.pop(), Some(2));
+        assert_eq!(db.pop(), None);
+
+        db.swap();
+        assert_eq!(db.pop(), Some(3));
+    }
+
+    #[test]
+    fn test_half_capacity() {
+        let db = DoubleBuffer::new(1);
+
+        assert!(db.push(1).is_ok());
+        assert!(db.push(2).is_err()); // Половина заполнена
+        assert_eq!(db.back().len(), 1);
+        assert!(db.front().is_empty());
+    }
+
+    #[test]
+    fn test_swap_races_push_pop() {
+        // Писатель помечает элемент индексом передней половины, видимым под замком
+        // задней; читатель проверяет, что элемент пришёл из другой половины
+        let db = Arc::new(DoubleBuffer::new(8));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let writer = {
+            let (db, stop) = (Arc::clone(&db), Arc::clone(&stop));
+            thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let mut back = db.back();
+                    let front = db.front.load(Ordering::Acquire);
+                    let _ = back.push(front);
+                }
+            })
+        };
+        let swapper = {
+            let (db, stop) = (Arc::clone(&db), Arc::clone(&stop));
+            thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    db.swap();
+                    thread::yield_now();
+                }
+            })
+        };
+
+        let mut seen = 0;
+        while seen < 500 {
+            let mut front = db.front();
+            let index = db.front.load(Ordering::Acquire);
+            while let Some(tag) = front.pop() {
+                assert_eq!(tag, index ^ 1, "Писатель и читатель в одной половине!");
+                seen += 1;
+            }
+        }
+        stop.store(true, Ordering::Relaxed);
+        writer.join().unwrap();
+        swapper.join().unwrap();
+    }
+
+    #[test]
+    fn test_frames_across_threads() {
+        let db = Arc::new(DoubleBuffer::new(4));
+
+        for frame in 0..10 {
+            let writer = {
+                let db = Arc::clone(&db);
+                thread::spawn(move || {
+                    for i in 0..4 {
+                        db.push(frame * 4 + i).unwrap();
+                    }
+                })
+            };
+            writer.join().unwrap();
+            db.swap();
+
+            let mut front = db.front();
+            assert_eq!(
+                front.drain(4),
+                (frame * 4..frame * 4 + 4).collect::<Vec<_>>()
+            );
+        }
+    }
+}
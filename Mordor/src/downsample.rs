@@ -0,0 +1,150 @@
+// Кольцо временного ряда с прореживанием: при заполнении старые точки сливаются
+
+use alloc::vec::Vec;
+
+use crate::RingBuffer;
+
+// 1. Как сливать две соседние точки
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Merge {
+    Average, // Взвешенное среднее по числу исходных отсчётов
+    Max,     // Максимум (пики не теряются)
+}
+
+// 2. Точка истории: значение и сколько исходных отсчётов она покрывает
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    pub value: f64,
+    pub span: usize,
+}
+
+// 3. Кольцо точек, от самых старых (грубых) к самым новым (точным)
+#[derive(Debug)]
+pub struct DownsampleRing {
+    samples: RingBuffer<Sample>,
+    merge: Merge,
+}
+
+impl DownsampleRing {
+    // 3.1. Создаём кольцо на capacity точек (нужно хотя бы две для слияния)
+    pub fn new(capacity: usize, merge: Merge) -> Self {
+        assert!(capacity > 1, "Для слияния нужно хотя бы две точки!");
+
+        DownsampleRing {
+            samples: RingBuffer::new(capacity),
+            merge,
+        }
+    }
+
+    // 4. Добавление отсчёта; при заполнении сливаем пару старых точек
+    pub fn push(&mut self, value: f64) {
+        if self.samples.is_full() {
+            self.compact();
+        }
+
+        let _ = self.samples.push(Sample { value, span: 1 });
+    }
+
+    // 5. Слияние самой старой пары точек одинакового охвата
+    fn compact(&mut self) {
+        // Точки одного охвата сливаются как разряды двоичного счётчика,
+        // поэтому охват растёт степенями двойки от новых к старым
+        let index = (0..self.samples.len() - 1)
+            .find(|&i| {
+                self.samples.get(i).map(|s| s.span) == self.samples.get(i + 1).map(|s| s.span)
+            })
+            .unwrap_or(0);
+
+        if let Some(newer) = self.samples.remove(index + 1) {
+            if let Some(older) = self.samples.get_mut(index) {
+                *older = merge(*older, newer, self.merge);
+            }
+        }
+    }
+
+    // 6. Текущая история
+    pub fn iter(&self) -> impl Iterator<Item = &Sample> + '_ {
+        self.samples.iter()
+    }
+
+    pub fn values(&self) -> Vec<f64> {
+        self.samples.iter().map(|s| s.value).collect()
+    }
+
+    // 7. Сколько исходных отсчётов покрывает вся история
+    pub fn total_span(&self) -> usize {
+        self.samples.iter().map(|s| s.span).sum()
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+}
+
+// 8. Слияние двух соседних точек
+fn merge(older: Sample, newer: Sample, mode: Merge) -> Sample {
+    let span = older.span + newer.span;
+    let value = match mode {
+        Merge::Average => {
+            (older.value * older.span as f64 + newer.value * newer.span as f64) / span as f64
+        }
+        Merge::Max => older.value.max(newer.value),
+    };
+    Sample { value, span }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DownsampleRing, Merge};
+
+    #[test]
+    fn test_no_merge_until_full() {
+        let mut dr = DownsampleRing::new(4, Merge::Average);
+        for v in [1.0, 2.0, 3.0] {
+            dr.push(v);
+        }
+
+        assert_eq!(dr.values(), vec![1.0, 2.0, 3.0]);
+        assert_eq!(dr.total_span(), 3);
+    }
+
+    #[test]
+    fn test_average_merges_oldest_pair() {
+        let mut dr = DownsampleRing::new(3, Merge::Average);
+        for v in [1.0, 3.0, 5.0, 7.0] {
+            dr.push(v);
+        }
+
+        assert_eq!(dr.values(), vec![2.0, 5.0, 7.0]); // 1 и 3 слились в 2
+        assert_eq!(dr.total_span(), 4);
+    }
+
+    #[test]
+    fn test_max_keeps_peaks() {
+        let mut dr = DownsampleRing::new(3, Merge::Max);
+        for v in [9.0, 1.0, 2.0, 3.0, 4.0] {
+            dr.push(v);
+        }
+
+        assert_eq!(dr.values()[0], 9.0); // Пик сохраняется в грубой точке
+        assert_eq!(dr.total_span(), 5);
+    }
+
+    #[test]
+    fn test_history_grows_beyond_capacity() {
+        let mut dr = DownsampleRing::new(8, Merge::Average);
+        for v in 0..1000 {
+            dr.push(v as f64);
+        }
+
+        // Охват не растёт от старых точек к новым
+        let spans: Vec<usize> = dr.iter().map(|s| s.span).collect();
+        assert!(spans.windows(2).all(|w| w[0] >= w[1]));
+        assert_eq!(dr.total_span(), 1000);
+        assert_eq!(dr.len(), 8);
+    }
+}
@@ -0,0 +1,142 @@
+// Кольцо событий с глобальными смещениями и повторным чтением истории
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::RingBuffer;
+
+// 1. Ошибка чтения: запрошенное смещение уже перезаписано
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventError {
+    Lagged(u64), // Сколько событий потеряно между запрошенным и самым старым хранимым
+}
+
+impl fmt::Display for EventError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventError::Lagged(n) => write!(f, "Читатель отстал на {} событий", n),
+        }
+    }
+}
+
+impl core::error::Error for EventError {}
+
+// 2. События и смещение следующего записываемого
+#[derive(Debug)]
+pub struct EventRing<T> {
+    events: RingBuffer<T>,
+    next_offset: u64, // Смещение, которое получит следующее событие
+}
+
+impl<T> EventRing<T> {
+    // 2.1. Создаём кольцо, хранящее последние capacity событий
+    pub fn new(capacity: usize) -> Self {
+        EventRing {
+            events: RingBuffer::new(capacity),
+            next_offset: 0,
+        }
+    }
+
+    // 3. Запись события, возвращает его глобальное смещение
+    pub fn push(&mut self, event: T) -> u64 {
+        self.events.push_overwrite(event); // Самое старое событие уходит из истории
+        self.next_offset += 1;
+        self.next_offset - 1
+    }
+
+    // 4. Повторное чтение всех хранимых событий начиная с offset
+    pub fn read_from(&self, offset: u64) -> Result<Vec<(u64, &T)>, EventError> {
+        let first = self.first_offset();
+        if offset < first {
+            return Err(EventError::Lagged(first - offset));
+        }
+
+        // Смещения из будущего дают пустой результат; не влезающее в usize (32-битные цели)
+        // тоже за концом истории
+        let skip = usize::try_from(offset - first).unwrap_or(usize::MAX);
+        Ok((first..self.next_offset)
+            .zip(self.events.iter())
+            .skip(skip)
+            .collect())
+    }
+
+    // 5. Одно событие по смещению
+    pub fn get(&self, offset: u64) -> Result<Option<&T>, EventError> {
+        let first = self.first_offset();
+        if offset < first {
+            return Err(EventError::Lagged(first - offset));
+        }
+
+        let index = usize::try_from(offset - first).unwrap_or(usize::MAX); // За концом
+        Ok(self.events.get(index))
+    }
+
+    // 6. Границы хранимой истории
+    pub fn first_offset(&self) -> u64 {
+        self.next_offset - self.events.len() as u64
+    }
+
+    pub fn next_offset(&self) -> u64 {
+        self.next_offset
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EventError, EventRing};
+
+    #[test]
+    fn test_far_offsets() {
+        let mut er = EventRing::new(2);
+        er.push("a");
+
+        // Разница больше u32::MAX: на 32-битной цели усечение попало бы на событие 0
+        let far = 1_u64 << 32;
+        assert_eq!(er.get(far), Ok(None));
+        assert_eq!(er.read_from(far), Ok(vec![]));
+        assert_eq!(er.get(u64::MAX), Ok(None));
+    }
+
+    #[test]
+    fn test_offsets_and_replay() {
+        let mut er = EventRing::new(4);
+        assert_eq!(er.push("a"), 0);
+        assert_eq!(er.push("b"), 1);
+        assert_eq!(er.push("c"), 2);
+
+        assert_eq!(er.read_from(1), Ok(vec![(1, &"b"), (2, &"c")]));
+        assert_eq!(er.read_from(3), Ok(vec![])); // Новых событий нет
+        assert_eq!(er.get(0), Ok(Some(&"a")));
+    }
+
+    #[test]
+    fn test_lagged() {
+        let mut er = EventRing::new(2);
+        for i in 0..5 {
+            er.push(i);
+        }
+
+        assert_eq!(er.first_offset(), 3);
+        assert_eq!(er.read_from(1), Err(EventError::Lagged(2)));
+        assert_eq!(er.get(2), Err(EventError::Lagged(1)));
+        assert_eq!(er.read_from(3), Ok(vec![(3, &3), (4, &4)]));
+    }
+
+    #[test]
+    fn test_future_offset() {
+        let mut er = EventRing::new(2);
+        er.push(1);
+
+        assert_eq!(er.read_from(10), Ok(vec![]));
+        assert_eq!(er.get(10), Ok(None));
+        assert_eq!(er.next_offset(), 1);
+    }
+}
@@ -0,0 +1,117 @@
+// Уведомление через eventfd для циклов событий (epoll, mio): дескриптор читаем,
+// пока в кольце есть данные или оно закрыто, то есть в режиме уровня (level-triggered).
+// Сам дескриптор только сигналит - данные по-прежнему забираются через try_pop
+
+use core::sync::atomic::{AtomicBool, Ordering};
+use std::io;
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd};
+
+// 1. Неблокирующий eventfd и его текущее состояние (взведён ли счётчик)
+#[derive(Debug)]
+pub struct EventFd {
+    fd: OwnedFd,
+    readable: AtomicBool,
+}
+
+impl EventFd {
+    // 1.1. Создаём невзведённый дескриптор
+    pub fn new() -> io::Result<Self> {
+        // SAFETY: eventfd не обращается к памяти вызывающего
+        let fd = unsafe { libc::eventfd(0, libc::EFD_NONBLOCK | libc::EFD_CLOEXEC) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(EventFd {
+            // SAFETY: дескриптор только что создан и больше никому не принадлежит
+            fd: unsafe { OwnedFd::from_raw_fd(fd) },
+            readable: AtomicBool::new(false),
+        })
+    }
+
+    // 2. Переводим дескриптор в нужное состояние; системный вызов - только при смене.
+    // Вызывается под блокировкой кольца, поэтому смены не перемешиваются
+    pub(crate) fn set_readable(&self, readable: bool) {
+        if self.readable.swap(readable, Ordering::Relaxed) == readable {
+            return;
+        }
+        if readable {
+            self.write(1);
+        } else {
+            self.drain();
+        }
+    }
+
+    // 2.1. Взводим счётчик: дескриптор становится читаемым
+    fn write(&self, value: u64) {
+        let bytes = value.to_ne_bytes();
+        // SAFETY: пишем ровно 8 байт из живого массива
+        let _ = unsafe { libc::write(self.fd.as_raw_fd(), bytes.as_ptr().cast(), bytes.len()) };
+    }
+
+    // 2.2. Сбрасываем счётчик чтением; пустой счётчик (EAGAIN) - тоже сброс
+    fn drain(&self) {
+        let mut bytes = [0u8; 8];
+        // SAFETY: читаем не больше 8 байт в живой массив
+        let _ = unsafe { libc::read(self.fd.as_raw_fd(), bytes.as_mut_ptr().cast(), bytes.len()) };
+    }
+}
+
+impl AsFd for EventFd {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.fd.as_fd()
+    }
+}
+
+impl AsRawFd for EventFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.as_raw_fd()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EventFd;
+    use crate::BlockingRing;
+    use std::os::fd::{AsFd, AsRawFd};
+
+    // Читаем ли дескриптор прямо сейчас (poll без ожидания)
+    fn is_readable(fd: impl AsFd) -> bool {
+        let mut pollfd = libc::pollfd {
+            fd: fd.as_fd().as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let ready = unsafe { libc::poll(&mut pollfd, 1, 0) };
+        ready == 1 && pollfd.revents & libc::POLLIN != 0
+    }
+
+    #[test]
+    fn test_set_readable() {
+        let fd = EventFd::new().unwrap();
+        assert!(!is_readable(&fd));
+        fd.set_readable(true);
+        fd.set_readable(true); // Повтор ничего не меняет
+        assert!(is_readable(&fd));
+        fd.set_readable(false);
+        assert!(!is_readable(&fd));
+    }
+
+    #[test]
+    fn test_ring_readiness() {
+        let ring = BlockingRing::with_event_fd(2).unwrap();
+        let fd = ring.event_fd().unwrap();
+        assert!(!is_readable(fd));
+
+        ring.push(1).unwrap();
+        ring.push(2).unwrap();
+        assert!(is_readable(fd));
+        assert_eq!(ring.try_pop(), Ok(Some(1)));
+        assert!(is_readable(fd)); // Данные ещё есть
+        assert_eq!(ring.pop(), Ok(Some(2)));
+        assert!(!is_readable(fd));
+
+        ring.close(); // Закрытие тоже будит цикл событий: pop вернёт None
+        assert!(is_readable(fd));
+        assert!(BlockingRing::<u8>::new(1).event_fd().is_none());
+    }
+}
@@ -0,0 +1,112 @@
+// Очередь с ограниченным сроком жизни элементов: просроченная работа никому не нужна,
+// поэтому чтение пропускает её и считает пропущенное
+
+use std::time::Instant;
+
+use crate::RingBuffer;
+
+// 1. Кольцо пар (срок, значение)
+#[derive(Debug)]
+pub struct ExpiringRing<T> {
+    ring: RingBuffer<(Instant, T)>,
+    expired: u64, // Сколько элементов выброшено по сроку за всё время
+}
+
+impl<T> ExpiringRing<T> {
+    // 1.1. Создаём буфер заданного размера
+    pub fn new(capacity: usize) -> Self {
+        ExpiringRing {
+            ring: RingBuffer::new(capacity),
+            expired: 0,
+        }
+    }
+
+    // 2. Запись элемента, действительного до expires_at
+    pub fn push(&mut self, value: T, expires_at: Instant) -> Result<(), String> {
+        self.ring.push((expires_at, value))
+    }
+
+    // 3. Чтение самого старого непросроченного элемента; просроченные по пути выбрасываются
+    pub fn pop(&mut self) -> Option<T> {
+        let now = Instant::now();
+        while let Some((expires_at, value)) = self.ring.pop() {
+            if expires_at > now {
+                return Some(value);
+            }
+            self.expired += 1;
+        }
+        None
+    }
+
+    // 4. Удаление всех просроченных элементов (и из середины очереди); возвращает их число
+    pub fn purge_expired(&mut self) -> usize {
+        let now = Instant::now();
+        let before = self.ring.len();
+        self.ring.retain(|(expires_at, _)| *expires_at > now);
+
+        let purged = before - self.ring.len();
+        self.expired += purged as u64;
+        purged
+    }
+
+    // 5. Счётчик выброшенных по сроку
+    pub fn expired(&self) -> u64 {
+        self.expired
+    }
+
+    // 6. Размеры (вместе с ещё не выброшенными просроченными)
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ring.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.ring.capacity()
+    }
+
+    // 6.1. Память в куче (метки времени хранятся рядом с элементами)
+    pub fn heap_bytes(&self) -> usize {
+        self.ring.heap_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ExpiringRing;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_pop_skips_expired() {
+        let past = Instant::now();
+        let future = past + Duration::from_secs(3600);
+        let mut ring = ExpiringRing::new(4);
+        assert!(ring.push("stale", past).is_ok());
+        assert!(ring.push("fresh", future).is_ok());
+        assert!(ring.push("stale too", past).is_ok());
+
+        assert_eq!(ring.pop(), Some("fresh"));
+        assert_eq!(ring.expired(), 1);
+        assert_eq!(ring.pop(), None);
+        assert_eq!(ring.expired(), 2);
+    }
+
+    #[test]
+    fn test_purge_expired() {
+        let past = Instant::now();
+        let future = past + Duration::from_secs(3600);
+        let mut ring = ExpiringRing::new(4);
+        for (i, expires_at) in [future, past, future, past].into_iter().enumerate() {
+            assert!(ring.push(i, expires_at).is_ok());
+        }
+        assert!(ring.push(4, future).is_err()); // Просроченные тоже занимают место
+
+        assert_eq!(ring.purge_expired(), 2);
+        assert_eq!(ring.expired(), 2);
+        assert_eq!(ring.len(), 2);
+        assert_eq!(ring.pop(), Some(0));
+        assert_eq!(ring.pop(), Some(2));
+    }
+}
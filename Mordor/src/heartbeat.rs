@@ -0,0 +1,116 @@
+// Сторожевой таймер: кольцо меток времени последних событий писателя
+// отвечает на вопрос "было ли хотя бы k событий за последнее окно?"
+
+use std::time::{Duration, Instant};
+
+use crate::RingBuffer;
+
+// 1. Метки времени по возрастанию; старые вытесняются новыми
+#[derive(Debug)]
+pub struct Heartbeat {
+    beats: RingBuffer<Instant>,
+}
+
+impl Heartbeat {
+    // 1.1. capacity - наибольшее k, о котором можно спросить
+    pub fn new(capacity: usize) -> Self {
+        Heartbeat {
+            beats: RingBuffer::new(capacity),
+        }
+    }
+
+    // 2. Отметка события сейчас
+    pub fn beat(&mut self) {
+        self.beat_at(Instant::now());
+    }
+
+    // 2.1. Отметка события с его собственным временем (не раньше предыдущего)
+    pub fn beat_at(&mut self, at: Instant) {
+        debug_assert!(
+            self.last().is_none_or(|last| last <= at),
+            "Метки должны идти по возрастанию!"
+        );
+        self.beats.push_overwrite(at);
+    }
+
+    // 3. Было ли хотя бы k событий за последние window
+    pub fn is_alive(&self, k: usize, window: Duration) -> bool {
+        assert!(
+            k <= self.beats.capacity(),
+            "Кольцо не помнит столько событий!"
+        );
+        if k == 0 {
+            return true;
+        }
+        // Метки упорядочены: достаточно проверить k-ю с конца
+        match self.beats.len().checked_sub(k) {
+            Some(index) => self.beats.get(index).is_some_and(|&at| within(at, window)),
+            None => false, // Событий меньше k за всё время
+        }
+    }
+
+    // 3.1. Сколько событий за последние window (не больше ёмкости)
+    pub fn count_within(&self, window: Duration) -> usize {
+        self.beats.iter().filter(|&&at| within(at, window)).count()
+    }
+
+    // 4. Время последнего события
+    pub fn last(&self) -> Option<Instant> {
+        self.beats
+            .len()
+            .checked_sub(1)
+            .and_then(|i| self.beats.get(i))
+            .copied()
+    }
+}
+
+fn within(at: Instant, window: Duration) -> bool {
+    at.elapsed() <= window
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Heartbeat;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    const HOUR: Duration = Duration::from_secs(3600);
+
+    #[test]
+    fn test_alive_needs_k_recent_beats() {
+        let mut hb = Heartbeat::new(3);
+        assert!(!hb.is_alive(1, HOUR));
+        assert!(hb.is_alive(0, HOUR));
+
+        hb.beat();
+        hb.beat();
+        assert!(hb.is_alive(2, HOUR));
+        assert!(!hb.is_alive(3, HOUR)); // Событий пока только два
+        assert_eq!(hb.count_within(HOUR), 2);
+    }
+
+    #[test]
+    fn test_old_beats_fall_out_of_window() {
+        let mut hb = Heartbeat::new(4);
+        let old = Instant::now();
+        hb.beat_at(old);
+        thread::sleep(Duration::from_millis(20));
+        hb.beat();
+
+        let window = Duration::from_millis(10);
+        assert!(hb.is_alive(1, window));
+        assert!(!hb.is_alive(2, window)); // Первое событие слишком давно
+        assert_eq!(hb.count_within(window), 1);
+        assert!(hb.last() > Some(old));
+    }
+
+    #[test]
+    fn test_keeps_only_latest() {
+        let mut hb = Heartbeat::new(2);
+        for _ in 0..5 {
+            hb.beat();
+        }
+        assert_eq!(hb.count_within(HOUR), 2); // Старые вытеснены
+        assert!(hb.is_alive(2, HOUR));
+    }
+}
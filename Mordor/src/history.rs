@@ -0,0 +1,130 @@
+// Ограниченная история состояний для отмены/повтора (undo/redo)
+
+use crate::RingBuffer;
+
+// 1. Кольцо состояний и позиция текущего состояния в нём
+#[derive(Debug)]
+pub struct History<T> {
+    states: RingBuffer<T>, // Состояния от самого старого к самому новому
+    cursor: usize,         // Логический индекс текущего состояния
+}
+
+impl<T> History<T> {
+    // 1.1. Создаём историю на capacity состояний
+    pub fn new(capacity: usize) -> Self {
+        History {
+            states: RingBuffer::new(capacity),
+            cursor: 0,
+        }
+    }
+
+    // 2. Запись нового состояния: ветка повтора отбрасывается, старейшее вытесняется
+    pub fn push(&mut self, state: T) {
+        while self.states.len() > self.cursor + 1 {
+            self.states.pop_back(); // Убираем состояния, отменённые ранее
+        }
+
+        self.states.push_overwrite(state);
+        self.cursor = self.states.len() - 1; // Новое состояние становится текущим
+    }
+
+    // 3. Шаг назад, возвращает новое текущее состояние
+    pub fn undo(&mut self) -> Option<&T> {
+        if !self.can_undo() {
+            return None; // Отменять нечего
+        }
+
+        self.cursor -= 1;
+        self.states.get(self.cursor)
+    }
+
+    // 4. Шаг вперёд по ранее отменённым состояниям
+    pub fn redo(&mut self) -> Option<&T> {
+        if !self.can_redo() {
+            return None; // Повторять нечего
+        }
+
+        self.cursor += 1;
+        self.states.get(self.cursor)
+    }
+
+    // 5. Текущее состояние
+    pub fn current(&self) -> Option<&T> {
+        self.states.get(self.cursor)
+    }
+
+    // 6. Можно ли отменить / повторить
+    pub fn can_undo(&self) -> bool {
+        self.cursor > 0
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.cursor + 1 < self.states.len()
+    }
+
+    // 7. Количество сохранённых состояний (включая отменённые)
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::History;
+
+    #[test]
+    fn test_undo_redo() {
+        let mut h = History::new(5);
+        h.push("a");
+        h.push("b");
+        h.push("c");
+
+        assert_eq!(h.undo(), Some(&"b"));
+        assert_eq!(h.undo(), Some(&"a"));
+        assert_eq!(h.undo(), None); // Дальше некуда
+        assert_eq!(h.redo(), Some(&"b"));
+        assert_eq!(h.current(), Some(&"b"));
+    }
+
+    #[test]
+    fn test_push_discards_redo_branch() {
+        let mut h = History::new(5);
+        h.push(1);
+        h.push(2);
+        h.push(3);
+        h.undo();
+        h.undo();
+
+        h.push(10); // Состояния 2 и 3 больше недоступны
+        assert!(!h.can_redo());
+        assert_eq!(h.len(), 2);
+        assert_eq!(h.undo(), Some(&1));
+    }
+
+    #[test]
+    fn test_evicts_oldest() {
+        let mut h = History::new(3);
+        for i in 0..5 {
+            h.push(i);
+        }
+
+        assert_eq!(h.current(), Some(&4));
+        assert_eq!(h.undo(), Some(&3));
+        assert_eq!(h.undo(), Some(&2));
+        assert_eq!(h.undo(), None); // 0 и 1 вытеснены
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut h: History<u8> = History::new(2);
+
+        assert!(h.is_empty());
+        assert_eq!(h.current(), None);
+        assert_eq!(h.undo(), None);
+        assert_eq!(h.redo(), None);
+    }
+}
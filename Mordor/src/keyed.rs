@@ -0,0 +1,119 @@
+// Очередь FIFO с дедупликацией по ключу (склейка повторных событий)
+
+use alloc::string::{String, ToString};
+
+use crate::RingBuffer;
+
+// 1. Что делать, если элемент с таким ключом уже в буфере
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    Replace, // Заменить значение на месте, сохранив позицию в очереди
+    Reject,  // Отклонить новый элемент
+}
+
+// 2. Кольцо пар (ключ, значение)
+#[derive(Debug)]
+pub struct KeyedRing<K, T> {
+    ring: RingBuffer<(K, T)>,
+    policy: DuplicatePolicy,
+}
+
+impl<K: PartialEq, T> KeyedRing<K, T> {
+    // 2.1. Создаём буфер заданного размера с выбранной политикой
+    pub fn new(capacity: usize, policy: DuplicatePolicy) -> Self {
+        KeyedRing {
+            ring: RingBuffer::new(capacity),
+            policy,
+        }
+    }
+
+    // 3. Запись элемента с учётом политики дубликатов
+    pub fn push(&mut self, key: K, value: T) -> Result<(), String> {
+        if let Some(index) = self.position(&key) {
+            return match self.policy {
+                DuplicatePolicy::Replace => {
+                    if let Some(slot) = self.ring.get_mut(index) {
+                        slot.1 = value; // Обновляем значение, место в очереди не меняется
+                    }
+                    Ok(())
+                }
+                DuplicatePolicy::Reject => Err("Ключ уже есть в буфере!".to_string()),
+            };
+        }
+
+        self.ring.push((key, value)) // Новый ключ - обычная запись
+    }
+
+    // 4. Чтение самого старого элемента
+    pub fn pop(&mut self) -> Option<(K, T)> {
+        self.ring.pop()
+    }
+
+    // 5. Поиск значения по ключу
+    pub fn get(&self, key: &K) -> Option<&T> {
+        self.ring.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.position(key).is_some()
+    }
+
+    // 6. Размеры
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ring.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.ring.is_full()
+    }
+
+    // 7. Логический индекс элемента с данным ключом
+    fn position(&self, key: &K) -> Option<usize> {
+        self.ring.iter().position(|(k, _)| k == key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DuplicatePolicy, KeyedRing};
+
+    #[test]
+    fn test_replace_keeps_position() {
+        let mut kr = KeyedRing::new(3, DuplicatePolicy::Replace);
+        assert!(kr.push("cpu", 10).is_ok());
+        assert!(kr.push("mem", 20).is_ok());
+        assert!(kr.push("cpu", 15).is_ok()); // Склеивается с первым
+
+        assert_eq!(kr.len(), 2);
+        assert_eq!(kr.get(&"cpu"), Some(&15));
+        assert_eq!(kr.pop(), Some(("cpu", 15)));
+        assert_eq!(kr.pop(), Some(("mem", 20)));
+    }
+
+    #[test]
+    fn test_reject_duplicate() {
+        let mut kr = KeyedRing::new(3, DuplicatePolicy::Reject);
+        assert!(kr.push(1, "a").is_ok());
+        assert!(kr.push(1, "b").is_err());
+
+        assert_eq!(kr.get(&1), Some(&"a"));
+        assert_eq!(kr.len(), 1);
+    }
+
+    #[test]
+    fn test_full_and_key_reuse() {
+        let mut kr = KeyedRing::new(2, DuplicatePolicy::Replace);
+        assert!(kr.push(1, 1).is_ok());
+        assert!(kr.push(2, 2).is_ok());
+        assert!(kr.push(3, 3).is_err()); // Новый ключ не помещается
+        assert!(kr.push(2, 22).is_ok()); // Замена места не требует
+
+        assert_eq!(kr.pop(), Some((1, 1)));
+        assert!(!kr.contains_key(&1));
+        assert!(kr.push(1, 11).is_ok()); // После извлечения ключ можно добавить снова
+    }
+}
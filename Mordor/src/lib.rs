@@ -0,0 +1,217 @@
+// Кольцевой буфер и построенные на нём структуры данных
+//
+// Возможности (cargo features):
+//   std   - потокобезопасные и асинхронные обёртки, очередь задач (по умолчанию, включает alloc)
+//   alloc - RingBuffer, ByteRing и всё, что на них построено (нужна куча)
+//   serde - сериализация RingBuffer
+//   arbitrary, quickcheck - генерация случайных буферов для фаззинга и свойств
+//   xxhash - ByteRing::checksum считает xxHash32 вместо CRC32
+//   encode - ByteRing::drain_hex и drain_base64 для текстовых дампов
+//   deflate - сжатие содержимого в снимках ByteRing
+//   tokio - ByteRing как bytes::Buf/BufMut и декодеры для tokio-util Framed
+//   audit - журнал последних операций RingBuffer для отчётов об ошибках
+//   visualize - печать RingBuffer::visualize после каждой операции (для обучения и отладки)
+//   tracing - спаны tracing вокруг пакетных операций ByteRing (байты и заполнение)
+//   embedded - ReadChunk и WriteChunk как буферы embedded-dma для передач через DMA
+//   no_panic - проверка при линковке release-сборки, что push, pop, extend_from_slice
+//              и drain_into у ConstRingBuffer не паникуют. Только у него: RingBuffer
+//              при записи может выделять память (авторасширение, строка ошибки)
+//   prefetch - программная подгрузка кэша в пакетных копированиях ByteRing (x86_64, aarch64)
+//   eventfd - BlockingRing с eventfd для epoll и mio (только Linux)
+//   uring - сброс PersistentRing в файл через io_uring (только Linux)
+// Без возможностей остаётся крошечное ядро для встраиваемых систем: ConstRingBuffer, rng и checksum.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[macro_use]
+mod macros;
+
+// 1. Ядро: не требует ни кучи, ни std
+pub mod checksum;
+pub mod const_ring;
+pub mod rng;
+
+pub use checksum::Crc32;
+pub use const_ring::ConstRingBuffer;
+pub use rng::{RandomSource, XorShift64};
+
+// 2. Структуры на куче (alloc)
+#[cfg(feature = "alloc")]
+pub mod advisor;
+#[cfg(feature = "audit")]
+pub mod audit;
+#[cfg(feature = "alloc")]
+pub mod bit_ring;
+#[cfg(feature = "alloc")]
+pub mod builder;
+#[cfg(feature = "alloc")]
+pub mod byte_ring;
+#[cfg(feature = "alloc")]
+pub mod chunked;
+#[cfg(feature = "alloc")]
+pub mod codec;
+#[cfg(feature = "alloc")]
+pub mod downsample;
+#[cfg(feature = "alloc")]
+pub mod event;
+#[cfg(feature = "alloc")]
+pub mod history;
+#[cfg(feature = "alloc")]
+pub mod keyed;
+#[cfg(feature = "alloc")]
+pub mod message;
+#[cfg(feature = "alloc")]
+pub mod meta;
+#[cfg(feature = "alloc")]
+pub mod partial;
+#[cfg(feature = "alloc")]
+pub mod percentile;
+#[cfg(feature = "alloc")]
+pub mod pod_ring;
+#[cfg(feature = "alloc")]
+pub mod pool;
+#[cfg(feature = "alloc")]
+pub mod priority;
+#[cfg(feature = "alloc")]
+pub mod replay;
+#[cfg(feature = "alloc")]
+pub mod reservoir;
+#[cfg(feature = "alloc")]
+pub mod ring;
+#[cfg(feature = "alloc")]
+pub mod rle;
+#[cfg(feature = "alloc")]
+mod shim;
+#[cfg(feature = "alloc")]
+pub mod sorted;
+#[cfg(feature = "alloc")]
+pub mod spsc;
+#[cfg(feature = "alloc")]
+pub mod stats;
+#[cfg(feature = "alloc")]
+pub mod text;
+
+#[cfg(feature = "alloc")]
+pub use advisor::CapacityAdvisor;
+#[cfg(feature = "audit")]
+pub use audit::{AuditEntry, AuditLog, AuditOp};
+#[cfg(feature = "alloc")]
+pub use bit_ring::BitRing;
+#[cfg(feature = "alloc")]
+pub use builder::RingBufferBuilder;
+#[cfg(feature = "alloc")]
+pub use byte_ring::{ByteRing, ReadChunk, SmallBytes, WriteChunk};
+#[cfg(feature = "alloc")]
+pub use chunked::ChunkedByteRing;
+#[cfg(feature = "alloc")]
+pub use codec::{CodecError, Decode, Delimited, LengthPrefixed};
+#[cfg(feature = "alloc")]
+pub use downsample::{DownsampleRing, Merge, Sample};
+#[cfg(feature = "alloc")]
+pub use event::{EventError, EventRing};
+#[cfg(feature = "alloc")]
+pub use history::History;
+#[cfg(feature = "alloc")]
+pub use keyed::{DuplicatePolicy, KeyedRing};
+#[cfg(feature = "alloc")]
+pub use message::MessageRing;
+#[cfg(feature = "alloc")]
+pub use meta::MetaRing;
+#[cfg(feature = "alloc")]
+pub use partial::{PartialWrite, StopReason};
+#[cfg(feature = "alloc")]
+pub use percentile::PercentileWindow;
+#[cfg(feature = "alloc")]
+pub use pod_ring::PodRing;
+#[cfg(feature = "alloc")]
+pub use pool::{Pool, PoolGuard};
+#[cfg(feature = "alloc")]
+pub use priority::PriorityRing;
+#[cfg(feature = "alloc")]
+pub use replay::ReplayBuffer;
+#[cfg(feature = "alloc")]
+pub use reservoir::Reservoir;
+#[cfg(feature = "alloc")]
+pub use ring::{Handle, Layout, OverflowPolicy, RingBuffer, Window};
+#[cfg(feature = "alloc")]
+pub use rle::{RleRing, Run};
+#[cfg(feature = "alloc")]
+pub use sorted::SortedRing;
+#[cfg(feature = "alloc")]
+pub use spsc::{Backoff, Consumer, Producer, Spin, WaitStrategy};
+#[cfg(feature = "alloc")]
+pub use stats::{Stats, StatsSnapshot};
+#[cfg(feature = "alloc")]
+pub use text::StrRingBuffer;
+
+// 3. Многопоточность (std)
+#[cfg(feature = "std")]
+pub mod async_ring;
+#[cfg(feature = "std")]
+pub mod blocking;
+#[cfg(feature = "std")]
+pub mod channel;
+#[cfg(feature = "std")]
+pub mod double;
+#[cfg(feature = "std")]
+pub mod expiring;
+#[cfg(feature = "std")]
+pub mod heartbeat;
+#[cfg(feature = "std")]
+pub mod persist;
+#[cfg(feature = "std")]
+pub mod snapshot;
+#[cfg(feature = "std")]
+pub mod tickets;
+#[cfg(feature = "std")]
+pub mod trace;
+#[cfg(feature = "std")]
+pub mod work;
+
+#[cfg(feature = "std")]
+pub use async_ring::AsyncRing;
+#[cfg(feature = "std")]
+pub use blocking::{BlockingError, BlockingRing, QuotaProducer};
+#[cfg(feature = "std")]
+pub use channel::{
+    channel, Receiver, RecvError, RecvTimeoutError, SendError, SendTimeoutError, Sender,
+    TryRecvError, TrySendError, WeakSender,
+};
+#[cfg(feature = "std")]
+pub use double::DoubleBuffer;
+#[cfg(feature = "std")]
+pub use expiring::ExpiringRing;
+#[cfg(feature = "std")]
+pub use heartbeat::Heartbeat;
+#[cfg(feature = "std")]
+pub use persist::{HeaderError, PersistentRing, RingObserver, SyncPolicy};
+#[cfg(feature = "std")]
+pub use pool::with_tls_buffer;
+#[cfg(feature = "std")]
+pub use spsc::{Park, SpinThenYield};
+#[cfg(feature = "std")]
+pub use tickets::{Permit, Tickets};
+#[cfg(feature = "std")]
+pub use trace::{Recorder, Replayer, Step, TraceTarget};
+#[cfg(feature = "std")]
+pub use work::{Job, WorkQueue};
+
+// 4. Интеграции
+#[cfg(any(feature = "arbitrary", feature = "quickcheck"))]
+mod arbitrary_impl;
+#[cfg(feature = "embedded")]
+mod dma_impl;
+#[cfg(all(feature = "eventfd", target_os = "linux"))]
+pub mod event_fd;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(feature = "tokio")]
+pub mod tokio_codec;
+
+#[cfg(all(feature = "eventfd", target_os = "linux"))]
+pub use event_fd::EventFd;
+#[cfg(feature = "tokio")]
+pub use tokio_codec::{FramedError, RingCodec};
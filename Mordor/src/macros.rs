@@ -0,0 +1,84 @@
+// Макрос для создания заполненных буферов, по образцу vec![]
+
+// ring_buffer![]                 - пустой буфер ёмкостью по умолчанию (RingBuffer::default)
+// ring_buffer![a, b, c]          - буфер ровно под перечисленные элементы
+// ring_buffer![cap; a, b]        - буфер ёмкостью cap с первыми элементами
+// ring_buffer![const N; a, b]    - ConstRingBuffer<_, N> с первыми элементами
+#[macro_export]
+macro_rules! ring_buffer {
+    () => {
+        $crate::RingBuffer::default()
+    };
+    (const $cap:expr; $($value:expr),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut ring = $crate::ConstRingBuffer::<_, { $cap }>::new();
+        $(if ring.push($value).is_err() {
+            panic!("Элементов больше, чем ёмкость!");
+        })*
+        ring
+    }};
+    ($cap:expr; $($value:expr),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut ring = $crate::RingBuffer::new($cap);
+        $(ring.push($value).expect("Элементов больше, чем ёмкость!");)*
+        ring
+    }};
+    ($($value:expr),+ $(,)?) => {{
+        let values = [$($value),+];
+        let mut ring = $crate::RingBuffer::new(values.len());
+        for value in values {
+            ring.push(value).expect("Элементов больше, чем ёмкость!");
+        }
+        ring
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ConstRingBuffer;
+    #[cfg(feature = "alloc")]
+    use crate::RingBuffer;
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_list_form() {
+        let mut rb = ring_buffer![1, 2, 3];
+
+        assert!(rb.is_full());
+        assert_eq!(rb.drain(3), vec![1, 2, 3]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_empty_form() {
+        let rb: RingBuffer<u32> = ring_buffer![];
+
+        assert!(rb.is_empty());
+        assert_eq!(rb.capacity(), RingBuffer::<u32>::default().capacity());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_capacity_form() {
+        let rb: RingBuffer<u8> = ring_buffer![4; 7, 8];
+        let empty: RingBuffer<u8> = ring_buffer![2;];
+
+        assert_eq!(rb.capacity(), 4);
+        assert_eq!(rb.iter().copied().collect::<Vec<_>>(), vec![7, 8]);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_const_form() {
+        let rb: ConstRingBuffer<u8, 3> = ring_buffer![const 3; 1, 2];
+
+        assert_eq!(rb.len(), 2);
+        assert_eq!(rb.capacity(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_too_many_values() {
+        let _: ConstRingBuffer<u8, 1> = ring_buffer![const 1; 1, 2];
+    }
+}
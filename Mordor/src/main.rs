@@ -1,204 +1,335 @@
-// 1. Определяем структуру данных
-
-#[derive(Debug)]
-pub struct RingBuffer {
-    buffer: Vec<Option<u8>>, // Хранилище данных (None - пустая ячейка)
-    capacity: usize,         // Максимальная вместимость буфера
-    head: usize,             // Индекс для чтения (голова)
-    tail: usize,             // Индекс для записи (хвост)
-    size: usize,             // Текущее количество элементов
+// Демонстрация и диагностика: прокачиваем stdin в stdout через ByteRing
+// и печатаем в stderr скорость и заполненность кольца; подкоманда bench
+// меряет основные операции RingBuffer на текущей машине, inspect показывает
+// сохранённый снимок кольца (например, бортовой самописец упавшего приложения)
+//
+// Использование: Mordor [--capacity N] [--chunk-size N]
+//                Mordor bench [--capacity N] [--iterations N]
+//                Mordor inspect <файл>
+
+use std::hint::black_box;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use mordor::snapshot::Header;
+use mordor::{ByteRing, RingBuffer};
+
+const USAGE: &str = "Использование: Mordor [--capacity N] [--chunk-size N]
+               Mordor bench [--capacity N] [--iterations N]
+               Mordor inspect <файл>";
+
+// 1. Что запускать
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Pump,             // Перекачка stdin в stdout
+    Bench,            // Замеры операций
+    Inspect(PathBuf), // Разбор файла снимка
 }
 
-// 2. Реализация методов
-impl RingBuffer {
-    // 2.1. Создаём новый буфер заданного размера
-    pub fn new(capacity: usize) -> Self {
-        // Проверяем, что размер буфера положительный
-        assert!(capacity > 0, "В буфере есть место!");
-
-        RingBuffer {
-            buffer: vec![None; capacity], // Инициализируем пустыми значениями
-            capacity,                     // Сохраняем ёмкость
-            head: 0,                      // Начинаем с индекса 0
-            tail: 0,                      // Начинаем с индекса 0
-            size: 0,                      // Начальный размер - 0
+// 1.1. Настройки из командной строки
+#[derive(Debug, PartialEq, Eq)]
+struct Options {
+    command: Command,
+    capacity: usize,   // Ёмкость кольца (в байтах или элементах)
+    chunk_size: usize, // Сколько байт читаем за один раз
+    iterations: usize, // Сколько раз повторяем каждый замер
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            command: Command::Pump,
+            capacity: 64 * 1024,
+            chunk_size: 8 * 1024,
+            iterations: 1000,
         }
     }
+}
 
-    // 2. Проверка на пустоту
-    pub fn is_empty(&self) -> bool {
-        self.size == 0 // Если размер 0 - буфер пуст
+// 1.2. Разбор подкоманды и флагов; ошибка - текст для пользователя
+fn parse_options(args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut args = args.peekable();
+    let mut options = Options::default();
+    if args.next_if(|arg| arg == "bench").is_some() {
+        options.command = Command::Bench;
+    } else if args.next_if(|arg| arg == "inspect").is_some() {
+        let path = args
+            .next()
+            .ok_or_else(|| format!("Не указан файл\n{}", USAGE))?;
+        options.command = Command::Inspect(PathBuf::from(path));
     }
 
-    // 3. Проверка на заполненность
-    pub fn is_full(&self) -> bool {
-        self.size == self.capacity // Если размер равен ёмкости - буфер полон
+    while let Some(flag) = args.next() {
+        let target = match (flag.as_str(), &options.command) {
+            ("--capacity", _) => &mut options.capacity,
+            ("--chunk-size", Command::Pump) => &mut options.chunk_size,
+            ("--iterations", Command::Bench) => &mut options.iterations,
+            ("-h" | "--help", _) => return Err(USAGE.to_string()),
+            _ => return Err(format!("Неизвестный флаг {}\n{}", flag, USAGE)),
+        };
+        *target = args
+            .next()
+            .and_then(|value| value.parse().ok())
+            .filter(|&value| value > 0)
+            .ok_or_else(|| format!("После {} нужно положительное число", flag))?;
     }
+    Ok(options)
+}
 
-    // 4. Текущее количество элементов
-    pub fn len(&self) -> usize {
-        self.size // Просто возвращаем размер
+// 2. Итоги перекачки
+#[derive(Debug, Default)]
+struct Report {
+    bytes: u64,       // Сколько байт прошло через кольцо
+    flushes: u64,     // Сколько раз выгружали кольцо
+    peak_fill: usize, // Наибольшая заполненность перед выгрузкой
+    fill_sum: u64,    // Сумма заполненности перед выгрузками (для среднего)
+}
+
+impl Report {
+    fn print(&self, capacity: usize, elapsed: Duration) {
+        let seconds = elapsed.as_secs_f64().max(f64::EPSILON);
+        let average = self.fill_sum.checked_div(self.flushes).unwrap_or(0);
+
+        eprintln!("Передано: {} байт за {:.3} с", self.bytes, seconds);
+        eprintln!("Скорость: {:.1} МБ/с", self.bytes as f64 / seconds / 1e6);
+        eprintln!(
+            "Заполненность: пик {} из {} байт, в среднем {} байт за {} выгрузок",
+            self.peak_fill, capacity, average, self.flushes
+        );
     }
+}
 
-    // 5. Запись элемента
-    pub fn push(&mut self, value: u8) -> Result<(), String> {
-        if self.is_full() {
-            return Err("Буфер Заполнен!".to_string()); // Ошибка если полон
+// 3. Перекачка: читаем порциями до chunk_size и выгружаем, когда следующая порция не влезет
+fn pump(
+    ring: &mut ByteRing,
+    input: &mut impl Read,
+    output: &mut impl Write,
+    chunk_size: usize,
+) -> io::Result<Report> {
+    let mut report = Report::default();
+    loop {
+        let read = ring.fill_from(&mut input.by_ref().take(chunk_size as u64))?;
+        let eof = read == 0 && !ring.is_full();
+        if (ring.free() < chunk_size || eof) && !ring.is_empty() {
+            report.peak_fill = report.peak_fill.max(ring.len());
+            report.fill_sum += ring.len() as u64;
+            report.flushes += 1;
+            report.bytes += ring.flush_into(output)? as u64;
+        }
+        if eof {
+            output.flush()?;
+            return Ok(report);
         }
-
-        self.buffer[self.tail] = Some(value); // Записываем значение
-        self.tail = (self.tail + 1) % self.capacity; // Перемещаем хвост с закольцовыванием
-        self.size += 1; // Увеличиваем размер
-        Ok(()) // Возвращаем успешный результат
     }
+}
 
-    // 6. Чтение элемента
-    pub fn pop(&mut self) -> Option<u8> {
-        if self.is_empty() {
-            return None; // Возвращаем None если пуст
+// 4. Замеры: push/pop по одному элементу и extend/drain целым буфером
+fn bench(capacity: usize, iterations: usize) {
+    let mut ring = RingBuffer::new(capacity);
+    measure("push + pop", || {
+        for _ in 0..iterations {
+            for i in 0..capacity {
+                let _ = ring.push(black_box(i as u8));
+            }
+            while let Some(value) = ring.pop() {
+                black_box(value);
+            }
+        }
+        2 * capacity * iterations
+    });
+
+    let data = vec![0xA5u8; capacity];
+    measure("extend + drain", || {
+        for _ in 0..iterations {
+            ring.extend(black_box(&data));
+            black_box(ring.drain(capacity));
         }
+        2 * capacity * iterations
+    });
+}
 
-        let value = self.buffer[self.head].take(); // Забираем значение из головы
-        self.head = (self.head + 1) % self.capacity; // Перемещаем голову
-        self.size -= 1; // Уменьшаем размер
-        value // Возвращаем значение
-    }
+// 4.1. Замер одного сценария; run возвращает число выполненных операций
+fn measure(name: &str, run: impl FnOnce() -> usize) {
+    let started = Instant::now();
+    let ops = run();
+    let seconds = started.elapsed().as_secs_f64().max(f64::EPSILON);
+    println!(
+        "{:<16} {:>12.0} оп/с ({} операций за {:.3} с)",
+        name,
+        ops as f64 / seconds,
+        ops,
+        seconds
+    );
+}
+
+// 5. Разбор снимка: заголовок и шестнадцатеричный дамп логического содержимого
+fn inspect(path: &Path, out: &mut impl Write) -> io::Result<()> {
+    let file = std::fs::read(path)?;
+    let header = Header::read_from(&mut &file[..])?;
+    let ring = ByteRing::load_snapshot(&mut &file[..])?;
+
+    // Снимок хранит содержимое в логическом порядке: голова восстановленного кольца в нуле
+    writeln!(out, "Файл:     {}", path.display())?;
+    writeln!(out, "Сжатие:   {:?}", header.compression)?;
+    writeln!(out, "Ёмкость:  {} байт", header.capacity)?;
+    writeln!(out, "Размер:   {} байт", header.len)?;
+    writeln!(out, "Голова:   0, хвост: {}", ring.len() % ring.capacity())?;
+    writeln!(out, "CRC:      {:08x}", ring.checksum())?;
+    writeln!(out)?;
+
+    let (first, second) = ring.as_slices();
+    hexdump(first.iter().chain(second).copied(), out)
+}
 
-    // 7. Запись нескольких элементов
-    pub fn extend(&mut self, data: &[u8]) -> usize {
-        let mut count = 0;
-        for &byte in data {
-            if self.push(byte).is_err() {
-                // Пытаемся добавить каждый байт
-                break; // Прерываем если буфер полон
+// 5.1. Строки по 16 байт: смещение, байты в hex, печатные символы
+fn hexdump(bytes: impl Iterator<Item = u8>, out: &mut impl Write) -> io::Result<()> {
+    let mut line = Vec::with_capacity(16);
+    let mut offset = 0;
+    let mut bytes = bytes.peekable();
+    while bytes.peek().is_some() {
+        line.clear();
+        line.extend(bytes.by_ref().take(16));
+
+        write!(out, "{:08x}  ", offset)?;
+        for i in 0..16 {
+            match line.get(i) {
+                Some(byte) => write!(out, "{:02x} ", byte)?,
+                None => write!(out, "   ")?,
+            }
+            if i == 7 {
+                write!(out, " ")?; // Разделяем две половины строки
             }
-            count += 1; // Считаем успешно добавленные
         }
-        count // Возвращаем количество добавленных
+        let text: String = line
+            .iter()
+            .map(|&b| {
+                if b.is_ascii_graphic() || b == b' ' {
+                    b as char
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        writeln!(out, " |{}|", text)?;
+        offset += line.len();
     }
+    Ok(())
+}
 
-    // 8. Чтение нескольких элементов
-    pub fn drain(&mut self, count: usize) -> Vec<u8> {
-        let mut result = Vec::new();
-        for _ in 0..count {
-            match self.pop() {
-                // Пытаемся извлечь элемент
-                Some(byte) => result.push(byte), // Добавляем в результат
-                None => break,                   // Прерываем если буфер пуст
-            }
+fn main() -> ExitCode {
+    let options = match parse_options(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+    match &options.command {
+        Command::Pump => {}
+        Command::Bench => {
+            bench(options.capacity, options.iterations);
+            return ExitCode::SUCCESS;
+        }
+        Command::Inspect(path) => {
+            return match inspect(path, &mut io::stdout().lock()) {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(e) => {
+                    eprintln!("Не удалось разобрать снимок: {}", e);
+                    ExitCode::FAILURE
+                }
+            };
+        }
+    }
+
+    let mut ring = ByteRing::new(options.capacity);
+    let started = Instant::now();
+    match pump(
+        &mut ring,
+        &mut io::stdin().lock(),
+        &mut io::stdout().lock(),
+        options.chunk_size,
+    ) {
+        Ok(report) => {
+            report.print(options.capacity, started.elapsed());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Ошибка ввода-вывода: {}", e);
+            ExitCode::FAILURE
         }
-        result // Возвращаем прочитанные байты
     }
 }
 
-//  Модуль тестирования
 #[cfg(test)]
 mod tests {
-    use super::RingBuffer;
-
-    #[test]
-    fn test_creation() {
-        let rb = RingBuffer::new(5);
-        assert_eq!(rb.capacity, 5);
-        assert!(rb.is_empty());
-        assert_eq!(rb.len(), 0);
+    use super::{hexdump, parse_options, pump, Command, Options};
+    use mordor::ByteRing;
+    use std::path::PathBuf;
+
+    fn args(list: &[&str]) -> impl Iterator<Item = String> {
+        list.iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>()
+            .into_iter()
     }
 
     #[test]
-    fn test_basic_operations() {
-        let mut rb = RingBuffer::new(3);
-
-        // Тест записи
-        assert!(rb.push(1).is_ok());
-        assert!(rb.push(2).is_ok());
-        assert_eq!(rb.len(), 2);
-        assert!(!rb.is_empty());
-        assert!(!rb.is_full());
-
-        // Тест чтения
-        assert_eq!(rb.pop(), Some(1));
-        assert_eq!(rb.pop(), Some(2));
-        assert_eq!(rb.pop(), None);
-        assert!(rb.is_empty());
+    fn test_parse_options() {
+        assert_eq!(parse_options(args(&[])), Ok(Options::default()));
+        assert_eq!(
+            parse_options(args(&["--chunk-size", "3", "--capacity", "10"])),
+            Ok(Options {
+                capacity: 10,
+                chunk_size: 3,
+                ..Options::default()
+            })
+        );
+        assert_eq!(
+            parse_options(args(&["bench", "--iterations", "5"])),
+            Ok(Options {
+                command: Command::Bench,
+                iterations: 5,
+                ..Options::default()
+            })
+        );
+        assert!(parse_options(args(&["bench", "--chunk-size", "5"])).is_err());
+        assert!(parse_options(args(&["--iterations", "5"])).is_err());
+        assert_eq!(
+            parse_options(args(&["inspect", "ring.snap"])).map(|o| o.command),
+            Ok(Command::Inspect(PathBuf::from("ring.snap")))
+        );
+        assert!(parse_options(args(&["inspect"])).is_err());
+        assert!(parse_options(args(&["--capacity", "0"])).is_err());
+        assert!(parse_options(args(&["--capacity"])).is_err());
+        assert!(parse_options(args(&["--speed", "1"])).is_err());
     }
 
     #[test]
-    fn test_full_behavior() {
-        let mut rb = RingBuffer::new(2);
-
-        assert!(rb.push(1).is_ok());
-        assert!(rb.push(2).is_ok());
-        assert!(rb.push(3).is_err()); // Должно вернуть ошибку
-
-        assert_eq!(rb.drain(3), vec![1, 2]); // Читаем больше чем есть
-    }
-
-    #[test]
-    fn test_wrap_around() {
-        let mut rb = RingBuffer::new(3);
-
-        // Заполняем и частично освобождаем
-        assert_eq!(rb.extend(&[1, 2, 3]), 3);
-        assert_eq!(rb.drain(2), vec![1, 2]);
-
-        // Должны записаться в начало
-        assert_eq!(rb.extend(&[4, 5]), 2);
-        assert_eq!(rb.drain(3), vec![3, 4, 5]);
+    fn test_pump_copies_everything() {
+        let input: Vec<u8> = (0..=255).cycle().take(10_000).collect();
+        let mut output = Vec::new();
+        let mut ring = ByteRing::new(100);
+
+        let report = pump(&mut ring, &mut &input[..], &mut output, 30).unwrap();
+        assert_eq!(output, input); // Порядок и содержимое сохранены
+        assert_eq!(report.bytes, 10_000);
+        assert!(report.peak_fill <= 100);
+        assert!(ring.is_empty());
     }
 
     #[test]
-    fn test_edge_cases() {
-        let mut rb = RingBuffer::new(1);
-
-        // Граничный случай с буфером размером 1
-        assert!(rb.push(1).is_ok());
-        assert!(rb.is_full());
-        assert!(rb.push(2).is_err());
-
-        assert_eq!(rb.pop(), Some(1));
-        assert!(rb.is_empty());
+    fn test_hexdump() {
+        let mut out = Vec::new();
+        hexdump(b"Mordor ring\x00\x01 buffer!".iter().copied(), &mut out).unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<_> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("00000000  4d 6f 72 64 6f 72 20 72  69 6e 67 00 01"));
+        assert!(lines[0].ends_with("|Mordor ring.. bu|"));
+        assert!(lines[1].starts_with("00000010  66 66 65 72 21"));
+        assert!(lines[1].ends_with("|ffer!|"));
     }
 }
-
-// 4. Пример использования
-fn main() {
-    let mut buffer = RingBuffer::new(3);
-
-    // Заполняем буфер
-    buffer.push(10).unwrap();
-    buffer.push(20).unwrap();
-    buffer.push(30).unwrap();
-
-    // Попытка переполнения
-    if buffer.push(40).is_err() {
-        println!("Буффер Заполнен!");
-    }
-
-    // Читаем данные
-    while let Some(value) = buffer.pop() {
-        println!("Прочитанны!: {}", value);
-    }
-
-    let mut buffer = RingBuffer::new(3);
-   let written = buffer.extend("ab" .as_bytes());
-println!("Записано значений: {}", written); // 2
-
-
-
-let written = buffer.extend("cd".as_bytes());
-println!("Записано значений: {}", written); // 1
-
-let val = buffer.pop();
-println!("Убрано первое входное значение : {:?}", val); 
-
-buffer.push('e' as u8).unwrap(); 
-println!("Вписанное новое значение на место первого входного значения : {:?}", buffer);
-
-let values = buffer.drain(2);
-println!("Прочитано элементов задаётся после .drain: {:?}", values);
-
-let values = buffer.drain(2);
-println!("Прочитано: {:?}", values); 
-
-}
-
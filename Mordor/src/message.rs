@@ -0,0 +1,110 @@
+// Кольцо целых сообщений с ограничением по суммарному объёму в байтах
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::RingBuffer;
+
+// 1. Сообщения и учёт занятых байт
+#[derive(Debug)]
+pub struct MessageRing {
+    messages: RingBuffer<Vec<u8>>, // Сами сообщения, от старых к новым
+    max_bytes: usize,              // Бюджет по байтам на все сообщения
+    bytes: usize,                  // Сколько байт занято сейчас
+}
+
+impl MessageRing {
+    // 1.1. Создаём кольцо с бюджетом max_bytes и не более max_messages сообщений
+    pub fn new(max_bytes: usize, max_messages: usize) -> Self {
+        MessageRing {
+            messages: RingBuffer::new(max_messages),
+            max_bytes,
+            bytes: 0,
+        }
+    }
+
+    // 2. Запись сообщения с вытеснением старых; возвращает число вытесненных
+    pub fn push(&mut self, message: Vec<u8>) -> Result<usize, String> {
+        if message.len() > self.max_bytes {
+            return Err("Сообщение больше всего бюджета!".to_string());
+        }
+
+        let mut evicted = 0;
+        while self.messages.is_full() || self.bytes + message.len() > self.max_bytes {
+            self.pop(); // Освобождаем место за счёт самых старых
+            evicted += 1;
+        }
+
+        self.bytes += message.len();
+        self.messages.push(message)?;
+        Ok(evicted)
+    }
+
+    // 3. Чтение самого старого сообщения
+    pub fn pop(&mut self) -> Option<Vec<u8>> {
+        let message = self.messages.pop()?;
+        self.bytes -= message.len();
+        Some(message)
+    }
+
+    // 4. Просмотр самого старого сообщения без извлечения
+    pub fn peek(&self) -> Option<&[u8]> {
+        self.messages.get(0).map(|m| m.as_slice())
+    }
+
+    // 5. Размеры
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    pub fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MessageRing;
+
+    #[test]
+    fn test_byte_budget_evicts_oldest() {
+        let mut mr = MessageRing::new(10, 8);
+        assert_eq!(mr.push(vec![1; 4]), Ok(0));
+        assert_eq!(mr.push(vec![2; 4]), Ok(0));
+        assert_eq!(mr.push(vec![3; 6]), Ok(1)); // Нужно 6 байт - уходит самое старое
+        assert_eq!(mr.push(vec![4; 7]), Ok(2)); // А теперь оба оставшихся
+
+        assert_eq!(mr.len(), 1);
+        assert_eq!(mr.bytes(), 7);
+        assert_eq!(mr.peek(), Some(&[4u8; 7][..]));
+    }
+
+    #[test]
+    fn test_message_count_limit() {
+        let mut mr = MessageRing::new(100, 2);
+        mr.push(b"a".to_vec()).unwrap();
+        mr.push(b"b".to_vec()).unwrap();
+
+        assert_eq!(mr.push(b"c".to_vec()), Ok(1));
+        assert_eq!(mr.pop(), Some(b"b".to_vec()));
+        assert_eq!(mr.pop(), Some(b"c".to_vec()));
+        assert_eq!(mr.bytes(), 0);
+    }
+
+    #[test]
+    fn test_oversized_message() {
+        let mut mr = MessageRing::new(3, 4);
+        mr.push(vec![0; 2]).unwrap();
+
+        assert!(mr.push(vec![0; 4]).is_err());
+        assert_eq!(mr.len(), 1); // Существующие сообщения не тронуты
+    }
+}
@@ -0,0 +1,146 @@
+// Кольцо с параллельным кольцом метаданных (время записи, метка-тег):
+// метаданные идут в ногу с данными, а тип элемента остаётся прежним
+
+use alloc::string::String;
+
+use crate::{OverflowPolicy, RingBuffer};
+
+// 1. Два кольца одной ёмкости с одинаковой политикой; i-й элемент данных
+// всегда соответствует i-й записи метаданных
+#[derive(Debug, Clone)]
+pub struct MetaRing<T, M> {
+    data: RingBuffer<T>,
+    meta: RingBuffer<M>,
+}
+
+impl<T, M> MetaRing<T, M> {
+    // 1.1. Создаём пару колец заданного размера
+    pub fn new(capacity: usize) -> Self {
+        Self::with_policy(capacity, OverflowPolicy::Reject)
+    }
+
+    pub fn with_policy(capacity: usize, policy: OverflowPolicy) -> Self {
+        MetaRing {
+            data: RingBuffer::builder()
+                .capacity(capacity)
+                .policy(policy)
+                .build(),
+            meta: RingBuffer::builder()
+                .capacity(capacity)
+                .policy(policy)
+                .build(),
+        }
+    }
+
+    // 2. Запись значения с его метаданными
+    pub fn push(&mut self, value: T, meta: M) -> Result<(), String> {
+        self.data.push(value)?;
+        let _ = self.meta.push(meta); // Размеры совпадают: если влезли данные, влезут и метаданные
+        Ok(())
+    }
+
+    // 3. Чтение пары (значение, метаданные)
+    pub fn pop(&mut self) -> Option<(T, M)> {
+        let value = self.data.pop()?;
+        let meta = self.meta.pop()?;
+        Some((value, meta))
+    }
+
+    // 3.1. Чтение только значения (метаданные отбрасываются)
+    pub fn pop_value(&mut self) -> Option<T> {
+        self.pop().map(|(value, _)| value)
+    }
+
+    // 4. Доступ по логическому индексу и обход от старых к новым
+    pub fn get(&self, index: usize) -> Option<(&T, &M)> {
+        Some((self.data.get(index)?, self.meta.get(index)?))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&T, &M)> + '_ {
+        self.data.iter().zip(self.meta.iter())
+    }
+
+    // 5. Кольцо данных отдельно (для кода, которому метаданные не нужны)
+    pub fn values(&self) -> &RingBuffer<T> {
+        &self.data
+    }
+
+    // 6. Размеры и очистка
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.data.is_full()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    pub fn clear(&mut self) {
+        self.data.clear();
+        self.meta.clear();
+    }
+
+    // 6.1. Память в куче: оба кольца, данные и метаданные
+    pub fn heap_bytes(&self) -> usize {
+        self.data.heap_bytes() + self.meta.heap_bytes()
+    }
+}
+
+// 7. Метка времени записи
+#[cfg(feature = "std")]
+impl<T> MetaRing<T, std::time::Instant> {
+    pub fn push_now(&mut self, value: T) -> Result<(), String> {
+        self.push(value, std::time::Instant::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MetaRing;
+    use crate::OverflowPolicy;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_pairs_stay_in_step() {
+        let mut ring = MetaRing::new(2);
+        assert!(ring.push("a", 1u32).is_ok());
+        assert!(ring.push("b", 2).is_ok());
+        assert!(ring.push("c", 3).is_err());
+
+        assert_eq!(ring.get(1), Some((&"b", &2)));
+        assert_eq!(ring.pop(), Some(("a", 1)));
+        assert!(ring.push("c", 3).is_ok());
+        assert_eq!(ring.iter().collect::<Vec<_>>(), [(&"b", &2), (&"c", &3)]);
+        assert_eq!(ring.pop_value(), Some("b"));
+        assert_eq!(ring.values().get(0), Some(&"c"));
+    }
+
+    #[test]
+    fn test_overwrite_evicts_both() {
+        let mut ring = MetaRing::with_policy(2, OverflowPolicy::Overwrite);
+        for i in 0..5u8 {
+            assert!(ring.push(i, u32::from(i) * 10).is_ok());
+        }
+        assert_eq!(ring.pop(), Some((3, 30)));
+        assert_eq!(ring.pop(), Some((4, 40)));
+        assert!(ring.is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_timestamps() {
+        let mut ring = MetaRing::new(2);
+        let before = std::time::Instant::now();
+        assert!(ring.push_now(7).is_ok());
+        let (value, at) = ring.pop().unwrap();
+        assert_eq!(value, 7);
+        assert!(at >= before);
+    }
+}
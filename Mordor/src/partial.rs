@@ -0,0 +1,87 @@
+// Подробный результат пакетной записи: сколько записано, сколько осталось
+// во входных данных и почему запись остановилась - чтобы не перепроверять
+// состояние буфера после вызова
+
+// 1. Причина остановки записи
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    InputExhausted, // Входные данные записаны целиком
+    Full,           // Кончилось место в буфере
+}
+
+// 2. Результат записи среза
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartialWrite {
+    pub written: usize,
+    pub remaining_input: usize,
+    pub reason: StopReason,
+}
+
+impl PartialWrite {
+    // 2.1. Записано written из total; всё, что не записано, упёрлось в место
+    #[inline]
+    pub(crate) fn new(written: usize, total: usize) -> Self {
+        let reason = if written == total {
+            StopReason::InputExhausted
+        } else {
+            StopReason::Full
+        };
+        PartialWrite {
+            written,
+            remaining_input: total - written,
+            reason,
+        }
+    }
+
+    // 3. Записано ли всё
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.reason == StopReason::InputExhausted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PartialWrite, StopReason};
+    use crate::{ByteRing, ChunkedByteRing, OverflowPolicy, RingBuffer};
+
+    #[test]
+    fn test_byte_ring() {
+        let mut ring = ByteRing::new(4);
+        let result = ring.extend_partial(b"ab");
+        assert_eq!(result, PartialWrite::new(2, 2));
+        assert!(result.is_complete());
+
+        // Место кончилось посреди входа
+        let result = ring.extend_partial(b"cdef");
+        assert_eq!(result.written, 2);
+        assert_eq!(result.remaining_input, 2);
+        assert_eq!(result.reason, StopReason::Full);
+
+        // Пустой вход в полный буфер - записывать было нечего
+        assert!(ring.extend_partial(b"").is_complete());
+    }
+
+    #[test]
+    fn test_chunked() {
+        let mut ring = ChunkedByteRing::with_chunk_size(5, 2);
+        assert_eq!(ring.extend_partial(b"abcdefg"), PartialWrite::new(5, 7));
+        assert_eq!(ring.extend_partial(b"x").reason, StopReason::Full);
+    }
+
+    #[test]
+    fn test_ring_buffer_policies() {
+        let mut rb = RingBuffer::new(3);
+        let result = rb.extend_partial(&[1, 2, 3, 4]);
+        assert_eq!((result.written, result.remaining_input), (3, 1));
+        assert_eq!(result.reason, StopReason::Full);
+
+        // При перезаписи место не кончается
+        let mut rb = RingBuffer::builder()
+            .capacity(3)
+            .policy(OverflowPolicy::Overwrite)
+            .build();
+        assert!(rb.extend_partial(&[1, 2, 3, 4]).is_complete());
+        assert_eq!(rb.iter().copied().collect::<Vec<_>>(), [2, 3, 4]);
+    }
+}
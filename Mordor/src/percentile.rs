@@ -0,0 +1,151 @@
+// Перцентили по скользящему окну последних N отсчётов (например, задержек для
+// панелей мониторинга): кольцо хранит отсчёты в порядке прихода, а рядом
+// поддерживается отсортированная копия - вставка и удаление двоичным поиском
+
+use alloc::vec::Vec;
+
+use crate::RingBuffer;
+
+// 1. Окно отсчётов и их отсортированный индекс
+#[derive(Debug, Clone)]
+pub struct PercentileWindow<T> {
+    samples: RingBuffer<T>, // От старых к новым
+    sorted: Vec<T>,         // Те же отсчёты по возрастанию
+}
+
+impl<T: Ord + Copy> PercentileWindow<T> {
+    // 1.1. Окно на последние capacity отсчётов
+    pub fn new(capacity: usize) -> Self {
+        PercentileWindow {
+            samples: RingBuffer::new(capacity),
+            sorted: Vec::with_capacity(capacity),
+        }
+    }
+
+    // 2. Новый отсчёт; при заполненном окне уходит самый старый (он и возвращается)
+    pub fn push(&mut self, value: T) -> Option<T> {
+        let evicted = if self.samples.is_full() {
+            self.samples.pop()
+        } else {
+            None
+        };
+        if let Some(old) = evicted {
+            let index = self.sorted.partition_point(|x| *x < old);
+            self.sorted.remove(index);
+        }
+
+        let _ = self.samples.push(value); // Место есть, ошибки не будет
+        let index = self.sorted.partition_point(|x| *x <= value);
+        self.sorted.insert(index, value);
+        evicted
+    }
+
+    // 3. Перцентиль в тысячных по ближайшему рангу: наименьший отсчёт, не меньше
+    // которого permille тысячных окна; None, если окно пусто
+    pub fn percentile(&self, permille: u32) -> Option<T> {
+        assert!(permille <= 1000, "Перцентиль задаётся в тысячных!");
+
+        let rank = (self.sorted.len() * permille as usize)
+            .div_ceil(1000)
+            .max(1);
+        self.sorted.get(rank - 1).copied()
+    }
+
+    // 3.1. Частые перцентили для панелей
+    pub fn p50(&self) -> Option<T> {
+        self.percentile(500)
+    }
+
+    pub fn p95(&self) -> Option<T> {
+        self.percentile(950)
+    }
+
+    pub fn p99(&self) -> Option<T> {
+        self.percentile(990)
+    }
+
+    // 3.2. Границы окна
+    pub fn min(&self) -> Option<T> {
+        self.sorted.first().copied()
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.sorted.last().copied()
+    }
+
+    // 4. Отсчёты в порядке прихода
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.samples.iter()
+    }
+
+    // 5. Размеры и сброс
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.samples.capacity()
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.sorted.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PercentileWindow;
+    use crate::rng::{RandomSource, XorShift64};
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_percentiles() {
+        let mut window = PercentileWindow::new(100);
+        assert_eq!(window.p50(), None);
+
+        for latency in 1..=100u32 {
+            window.push(latency);
+        }
+        assert_eq!(window.p50(), Some(50));
+        assert_eq!(window.p95(), Some(95));
+        assert_eq!(window.p99(), Some(99));
+        assert_eq!(window.percentile(0), Some(1));
+        assert_eq!(window.percentile(1000), Some(100));
+    }
+
+    #[test]
+    fn test_window_slides() {
+        let mut window = PercentileWindow::new(4);
+        for latency in [500, 10, 20, 30] {
+            window.push(latency);
+        }
+        assert_eq!(window.max(), Some(500));
+
+        assert_eq!(window.push(40), Some(500)); // Выброс ушёл из окна
+        assert_eq!(window.max(), Some(40));
+        assert_eq!(window.iter().copied().collect::<Vec<_>>(), [10, 20, 30, 40]);
+    }
+
+    #[test]
+    fn test_matches_sorting() {
+        let mut rng = XorShift64::new(3);
+        let mut window = PercentileWindow::new(50);
+        let mut all = Vec::new();
+        for _ in 0..500 {
+            let value = rng.gen_index(20); // Много повторов
+            window.push(value);
+            all.push(value);
+
+            let mut expected = all[all.len().saturating_sub(50)..].to_vec();
+            expected.sort();
+            let rank = (expected.len() * 95).div_ceil(100);
+            assert_eq!(window.p95(), Some(expected[rank - 1]));
+            assert_eq!(window.min(), expected.first().copied());
+        }
+    }
+}
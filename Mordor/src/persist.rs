@@ -0,0 +1,1107 @@
+// Байтовое кольцо в файле: содержимое переживает перезапуск процесса.
+//
+// Формат файла (версия 2): две ячейки заголовка по SLOT_SIZE байт и область данных
+// длиной в ёмкость, байт кольца в позиции p лежит по смещению HEADER_SIZE + p.
+// Ячейка: "MRDP", версия (1 байт), 3 байта резерва, затем ёмкость, начало, длина,
+// сколько байт записано за всю жизнь файла и номер заголовка (u64 LE), затем CRC32
+// всего предыдущего (u32 LE). Заголовки пишутся в ячейки по очереди, поэтому
+// оборванная сбоем запись портит только новую ячейку, а при открытии берётся
+// целая ячейка с большим номером. Файлы версии 1 (один заголовок без CRC)
+// при открытии переписываются в текущую версию.
+//
+// Запись идёт в память, flush сбрасывает в файл только изменённое с прошлого сброса:
+// промежуточный заголовок (окно без отброшенных и несброшенных байт), не больше двух
+// отрезков данных и итоговый заголовок, с fdatasync после каждого из трёх шагов:
+// порядок системных вызовов не задаёт порядок, в котором байты доходят до диска,
+// поэтому каждый шаг становится долговечным раньше следующего. С возможностью uring
+// на Linux всё это уходит в io_uring одной отправкой вместо нескольких системных вызовов.
+//
+// Режим журнала (WAL): append пишет и сам сбрасывает по политике SyncPolicy.
+// Восстановление после сбоя:
+//   1. PersistentRing::open читает заголовок и область данных как есть;
+//   2. итоговый заголовок пишется после данных, а промежуточный - до них и без новых
+//      байт, поэтому ни один заголовок не указывает на байты, которые не дошли до диска
+//      или перезаписаны: видно всё до последнего завершённого сброса (или до
+//      предыдущего, если сбой оборвал сброс);
+//   3. потеряться может только записанное после него - не больше, чем допускает
+//      политика (ничего при EveryWrite, меньше n байт при EveryBytes(n), записи
+//      последнего интервала при Interval);
+//   4. номер последнего уцелевшего байта - written(): по нему писатель решает,
+//      что повторить (например, по своим номерам записей внутри данных).
+//
+// Другой процесс может следить за кольцом через RingObserver: он только читает файл
+// и по заголовкам узнаёт, какие байты писатель уже сбросил и какие вот-вот перезапишет
+
+use core::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::checksum::Crc32;
+
+#[cfg(all(feature = "uring", target_os = "linux"))]
+use io_uring::IoUring;
+
+// 1. Заголовок файла
+pub const MAGIC: &[u8; 4] = b"MRDP";
+pub const VERSION: u8 = 2;
+pub const SLOT_SIZE: usize = 64;
+pub const HEADER_SIZE: usize = 2 * SLOT_SIZE;
+
+// 1.0.1. Почему файл не открылся; приходит внутри io::Error с видом InvalidData
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderError {
+    NotRing,                // Нет сигнатуры: чужой файл
+    UnsupportedVersion(u8), // Версия новее, чем умеет эта сборка
+    Checksum,               // Обе ячейки заголовка повреждены
+    Corrupt,                // CRC сошлась, но поля противоречат друг другу или размеру файла
+}
+
+impl fmt::Display for HeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeaderError::NotRing => write!(f, "Это не файл кольца!"),
+            HeaderError::UnsupportedVersion(v) => {
+                write!(f, "Неизвестная версия файла кольца: {}!", v)
+            }
+            HeaderError::Checksum => write!(f, "Контрольная сумма заголовка не сошлась!"),
+            HeaderError::Corrupt => write!(f, "Заголовок файла кольца повреждён!"),
+        }
+    }
+}
+
+impl core::error::Error for HeaderError {}
+
+impl From<HeaderError> for io::Error {
+    fn from(e: HeaderError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, e)
+    }
+}
+
+// 1.0.2. Поля заголовка
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Header {
+    capacity: usize,
+    head: usize,
+    len: usize,
+    written: u64,
+    seq: u64, // Номер заголовка: ячейка seq % 2
+}
+
+impl Header {
+    fn encode(&self) -> [u8; SLOT_SIZE] {
+        let mut slot = [0; SLOT_SIZE];
+        slot[..4].copy_from_slice(MAGIC);
+        slot[4] = VERSION;
+        slot[8..16].copy_from_slice(&(self.capacity as u64).to_le_bytes());
+        slot[16..24].copy_from_slice(&(self.head as u64).to_le_bytes());
+        slot[24..32].copy_from_slice(&(self.len as u64).to_le_bytes());
+        slot[32..40].copy_from_slice(&self.written.to_le_bytes());
+        slot[40..48].copy_from_slice(&self.seq.to_le_bytes());
+        let mut crc = Crc32::new();
+        crc.update(&slot[..48]);
+        slot[48..52].copy_from_slice(&crc.finish().to_le_bytes());
+        slot
+    }
+
+    fn slot_offset(&self) -> u64 {
+        (self.seq % 2) * SLOT_SIZE as u64
+    }
+
+    // 1.0.3. Первый байт окна в общем потоке записей
+    fn window_start(&self) -> u64 {
+        self.written - self.len as u64
+    }
+
+    fn decode(slot: &[u8]) -> Result<Header, HeaderError> {
+        if &slot[..4] != MAGIC {
+            return Err(HeaderError::NotRing);
+        }
+        if slot[4] != VERSION {
+            return Err(HeaderError::UnsupportedVersion(slot[4]));
+        }
+        let mut crc = Crc32::new();
+        crc.update(&slot[..48]);
+        if u32::from_le_bytes([slot[48], slot[49], slot[50], slot[51]]) != crc.finish() {
+            return Err(HeaderError::Checksum);
+        }
+        Header::checked(
+            read_usize(slot, 8)?,
+            read_usize(slot, 16)?,
+            read_usize(slot, 24)?,
+            read_u64(slot, 32),
+            read_u64(slot, 40),
+        )
+    }
+
+    fn checked(
+        capacity: usize,
+        head: usize,
+        len: usize,
+        written: u64,
+        seq: u64,
+    ) -> Result<Header, HeaderError> {
+        if capacity == 0 || head >= capacity || len > capacity || (len as u64) > written {
+            return Err(HeaderError::Corrupt);
+        }
+        Ok(Header {
+            capacity,
+            head,
+            len,
+            written,
+            seq,
+        })
+    }
+
+    // 1.0.4. Из двух ячеек - целая с большим номером. Если целых нет, сообщаем
+    // об ошибке ячейки с сигнатурой: так оборванный заголовок не выглядит чужим файлом
+    fn newest(slots: &[u8]) -> Result<Header, HeaderError> {
+        match (
+            Header::decode(&slots[..SLOT_SIZE]),
+            Header::decode(&slots[SLOT_SIZE..]),
+        ) {
+            (Ok(a), Ok(b)) => Ok(if a.seq >= b.seq { a } else { b }),
+            (Ok(h), Err(_)) | (Err(_), Ok(h)) => Ok(h),
+            (Err(HeaderError::NotRing), Err(e)) | (Err(e), Err(_)) => Err(e),
+        }
+    }
+}
+
+// 1.1. Когда append сбрасывает записанное в файл
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncPolicy {
+    Manual,             // Только явный flush (по умолчанию)
+    EveryWrite,         // После каждой записи
+    EveryBytes(u64),    // Когда несброшенного набралось не меньше n байт
+    Interval(Duration), // Если с прошлого сброса прошло не меньше интервала
+}
+
+// 2. Кольцо: копия области данных в памяти и счётчики сброса
+pub struct PersistentRing {
+    file: File,
+    buffer: Vec<u8>,
+    head: usize,
+    len: usize,
+    written: u64, // Сколько байт записано за всю жизнь файла
+    synced: u64,  // Сколько из них уже сброшено в файл
+    seq: u64,     // Номер последнего записанного заголовка
+    policy: SyncPolicy,
+    last_sync: Instant,
+    #[cfg(all(feature = "uring", target_os = "linux"))]
+    uring: Option<IoUring>, // Создаётся при первом сбросе и живёт вместе с кольцом
+}
+
+impl PersistentRing {
+    // 2.1. Новый файл на capacity байт (существующий перезаписывается)
+    pub fn create(path: impl AsRef<Path>, capacity: usize) -> io::Result<Self> {
+        assert!(capacity > 0, "В буфере есть место!");
+
+        let header = Header {
+            capacity,
+            head: 0,
+            len: 0,
+            written: 0,
+            seq: 0,
+        };
+        Self::init(path.as_ref(), header, vec![0; capacity])
+    }
+
+    // 2.1.1. Файл текущей версии с заданным содержимым: данные и обе ячейки заголовка
+    fn init(path: &Path, header: Header, buffer: Vec<u8>) -> io::Result<Self> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        file.set_len((HEADER_SIZE + buffer.len()) as u64)?;
+        file.seek(SeekFrom::Start(HEADER_SIZE as u64))?;
+        file.write_all(&buffer)?;
+
+        let mut ring = Self::from_parts(file, buffer, header);
+        for _ in 0..2 {
+            ring.seq += 1;
+            ring.write_header(ring.header(ring.seq))?;
+        }
+        ring.file.sync_all()?;
+        Ok(ring)
+    }
+
+    // 2.2. Открытие ранее созданного файла: содержимое - на момент последнего сброса.
+    // Ошибки формата - HeaderError внутри io::Error (InvalidData)
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+
+        let mut slots = Vec::with_capacity(HEADER_SIZE);
+        (&mut file)
+            .take(HEADER_SIZE as u64)
+            .read_to_end(&mut slots)?;
+        slots.resize(HEADER_SIZE, 0);
+        if &slots[..4] == MAGIC && slots[4] < VERSION {
+            return Self::migrate(path, &mut file, slots[4]);
+        }
+
+        let header = Header::newest(&slots)?;
+        let buffer = read_data(&mut file, HEADER_SIZE, header.capacity)?;
+        Ok(Self::from_parts(file, buffer, header))
+    }
+
+    // 2.3. Переход со старых версий: старый формат читается целиком, а файл текущей
+    // версии пишется рядом и подменяет исходный через rename, поэтому сбой посреди
+    // миграции оставляет исходный файл нетронутым. Новая версия формата добавляет
+    // сюда ветку чтения предыдущей
+    fn migrate(path: &Path, file: &mut File, version: u8) -> io::Result<Self> {
+        let (header, buffer) = match version {
+            1 => Self::read_v1(file)?,
+            _ => return Err(HeaderError::UnsupportedVersion(version).into()),
+        };
+
+        let temp = path.with_extension("migrating");
+        let ring = Self::init(&temp, header, buffer)?;
+        fs::rename(&temp, path)?;
+        sync_parent(path)?;
+        Ok(ring)
+    }
+
+    // 2.3.1. Версия 1: один заголовок без CRC (ёмкость, начало, длина, записано)
+    // и область данных со смещения 64
+    fn read_v1(file: &mut File) -> io::Result<(Header, Vec<u8>)> {
+        let mut slot = [0; 64];
+        file.seek(SeekFrom::Start(0))?;
+        file.read_exact(&mut slot)?;
+        let header = Header::checked(
+            read_usize(&slot, 8)?,
+            read_usize(&slot, 16)?,
+            read_usize(&slot, 24)?,
+            read_u64(&slot, 32),
+            0,
+        )?;
+
+        let buffer = read_data(file, 64, header.capacity)?;
+        Ok((header, buffer))
+    }
+
+    fn from_parts(file: File, buffer: Vec<u8>, header: Header) -> Self {
+        PersistentRing {
+            file,
+            buffer,
+            head: header.head,
+            len: header.len,
+            written: header.written,
+            synced: header.written,
+            seq: header.seq,
+            policy: SyncPolicy::Manual,
+            last_sync: Instant::now(),
+            #[cfg(all(feature = "uring", target_os = "linux"))]
+            uring: None,
+        }
+    }
+
+    // 3. Размеры
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    pub fn free(&self) -> usize {
+        self.capacity() - self.len
+    }
+
+    // 3.1. Сколько байт записано, но ещё не сброшено в файл
+    pub fn unsynced(&self) -> u64 {
+        self.written - self.synced
+    }
+
+    // 3.2. Сколько байт записано за всю жизнь файла (после открытия - сколько уцелело)
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    // 3.3. Политика сброса для append
+    pub fn sync_policy(&self) -> SyncPolicy {
+        self.policy
+    }
+
+    pub fn set_sync_policy(&mut self, policy: SyncPolicy) {
+        self.policy = policy;
+    }
+
+    // 4. Запись стольких байт, сколько помещается (в память до flush)
+    pub fn extend_from_slice(&mut self, data: &[u8]) -> usize {
+        let n = data.len().min(self.free());
+        let tail = self.wrap(self.head + self.len);
+        let first = n.min(self.capacity() - tail);
+        self.buffer[tail..tail + first].copy_from_slice(&data[..first]);
+        self.buffer[..n - first].copy_from_slice(&data[first..n]);
+
+        self.len += n;
+        self.written += n as u64;
+        n
+    }
+
+    // 4.1. Запись журнала: как extend_from_slice, затем сброс, если того требует политика
+    pub fn append(&mut self, data: &[u8]) -> io::Result<usize> {
+        let n = self.extend_from_slice(data);
+        self.sync_if_due()?;
+        Ok(n)
+    }
+
+    // 4.2. Сброс по политике; для Interval его стоит звать и по таймеру, когда
+    // записей нет. Возвращает, был ли сброс
+    pub fn sync_if_due(&mut self) -> io::Result<bool> {
+        let due = self.unsynced() > 0
+            && match self.policy {
+                SyncPolicy::Manual => false,
+                SyncPolicy::EveryWrite => true,
+                SyncPolicy::EveryBytes(n) => self.unsynced() >= n,
+                SyncPolicy::Interval(interval) => self.last_sync.elapsed() >= interval,
+            };
+        if due {
+            self.flush()?;
+        }
+        Ok(due)
+    }
+
+    // 5. Копирование в срез без вычитывания
+    pub fn peek_into(&self, out: &mut [u8]) -> usize {
+        let n = out.len().min(self.len);
+        let first = n.min(self.capacity() - self.head);
+        out[..first].copy_from_slice(&self.buffer[self.head..self.head + first]);
+        out[first..n].copy_from_slice(&self.buffer[..n - first]);
+        n
+    }
+
+    // 5.1. Чтение в срез вызывающего; возвращает, сколько байт скопировано
+    pub fn drain_into(&mut self, out: &mut [u8]) -> usize {
+        let n = self.peek_into(out);
+        self.discard(n)
+    }
+
+    // 5.2. Отбрасывание до n самых старых байт
+    pub fn discard(&mut self, n: usize) -> usize {
+        let n = n.min(self.len);
+        self.head = self.wrap(self.head + n);
+        self.len -= n;
+        n
+    }
+
+    // 5.3. Очистка сдвигает начало к концу: позиции несброшенных байт не меняются
+    pub fn clear(&mut self) {
+        self.discard(self.len);
+    }
+
+    // 6. Сброс в файл: промежуточный заголовок, изменённые данные, итоговый заголовок,
+    // каждый шаг - за fdatasync (барьер: итоговый заголовок не опередит данные на диске)
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.write_dirty()?;
+        self.seq += 2; // Только после успеха: неудачная запись повторится в те же ячейки
+        self.synced = self.written;
+        self.last_sync = Instant::now();
+        Ok(())
+    }
+
+    fn write_dirty(&mut self) -> io::Result<()> {
+        #[cfg(all(feature = "uring", target_os = "linux"))]
+        if let Some(result) = self.flush_uring() {
+            return result;
+        }
+
+        self.write_header(self.intent_header())?;
+        self.file.sync_data()?;
+        for (start, len) in self.dirty_segments() {
+            if len > 0 {
+                self.file
+                    .seek(SeekFrom::Start((HEADER_SIZE + start) as u64))?;
+                self.file.write_all(&self.buffer[start..start + len])?;
+            }
+        }
+        self.file.sync_data()?;
+        self.write_header(self.header(self.seq + 2))?;
+        self.file.sync_data()
+    }
+
+    // 6.1. Изменённое с прошлого сброса - последние unsynced байт перед концом
+    // (не больше ёмкости): не больше двух отрезков (начало, длина) области данных
+    fn dirty_segments(&self) -> [(usize, usize); 2] {
+        let capacity = self.capacity();
+        let n = self.unsynced().min(capacity as u64) as usize;
+        let tail = self.wrap(self.head + self.len);
+        let start = self.wrap(tail + capacity - n);
+        let first = n.min(capacity - start);
+        [(start, first), (0, n - first)]
+    }
+
+    // 6.2. Сброс через io_uring: записи связаны (IO_LINK) и выполняются в том же
+    // порядке, что и обычный сброс, с fdatasync после каждого шага. None - io_uring
+    // недоступен (например, запрещён seccomp) или отрезок не помещается в одну
+    // запись: сбрасываем обычным путём
+    #[cfg(all(feature = "uring", target_os = "linux"))]
+    fn flush_uring(&mut self) -> Option<io::Result<()>> {
+        use io_uring::{opcode, types};
+        use std::os::fd::AsRawFd;
+
+        if self.uring.is_none() {
+            self.uring = Some(IoUring::new(8).ok()?);
+        }
+
+        let intent = self.intent_header();
+        let header = self.header(self.seq + 2);
+        let fd = types::Fd(self.file.as_raw_fd());
+        let slot = |header: &Header, bytes: &[u8; SLOT_SIZE]| {
+            opcode::Write::new(fd, bytes.as_ptr(), SLOT_SIZE as u32)
+                .offset(header.slot_offset())
+                .build()
+        };
+        let sync = || {
+            opcode::Fsync::new(fd)
+                .flags(types::FsyncFlags::DATASYNC)
+                .build()
+        };
+        let (intent_bytes, header_bytes) = (intent.encode(), header.encode());
+        let mut entries = vec![slot(&intent, &intent_bytes), sync()];
+        let mut expected = vec![SLOT_SIZE as i32, 0];
+        for (start, len) in self.dirty_segments() {
+            if len > 0 {
+                let len = i32::try_from(len).ok()?; // Результат записи - i32
+                let data = self.buffer[start..].as_ptr();
+                let write = opcode::Write::new(fd, data, len as u32);
+                entries.push(write.offset((HEADER_SIZE + start) as u64).build());
+                expected.push(len);
+            }
+        }
+        entries.extend([sync(), slot(&header, &header_bytes), sync()]);
+        expected.extend([0, SLOT_SIZE as i32, 0]);
+
+        let uring = self.uring.as_mut()?;
+        Some(submit_linked(uring, entries, &expected))
+    }
+
+    // 6.3. Ротация: текущее содержимое уходит в запечатанный сегмент - файл того же
+    // формата ёмкостью ровно в экспортированные байты (открывается через open), а
+    // кольцо очищается. Сегмент пишется во временный файл и появляется под именем
+    // path только целиком. Сбой между появлением сегмента и сбросом очищенного
+    // кольца даёт повтор этих байт, но не потерю. Пустое кольцо сегмента не создаёт;
+    // возвращает, сколько байт экспортировано
+    pub fn export_and_reset(&mut self, path: impl AsRef<Path>) -> io::Result<usize> {
+        let path = path.as_ref();
+        let n = self.len;
+        if n == 0 {
+            return Ok(0);
+        }
+
+        let mut contents = vec![0; n];
+        self.peek_into(&mut contents);
+        let header = Header {
+            capacity: n,
+            head: 0,
+            len: n,
+            written: self.written, // Место сегмента в общем потоке записей
+            seq: 0,
+        };
+        let temp = path.with_extension("partial");
+        drop(Self::init(&temp, header, contents)?);
+        fs::rename(&temp, path)?;
+        sync_parent(path)?;
+
+        self.clear();
+        self.flush()?;
+        Ok(n)
+    }
+
+    // 7. Заголовок текущего состояния под номером seq
+    fn header(&self, seq: u64) -> Header {
+        Header {
+            capacity: self.capacity(),
+            head: self.head,
+            len: self.len,
+            written: self.written,
+            seq,
+        }
+    }
+
+    // 7.1. Промежуточный заголовок, который пишется до данных: окно сужено до уже
+    // сброшенных байт, отброшенные в него не входят. Новые данные ложатся только вне
+    // этого окна, поэтому сбой посреди записи данных не оживит перезаписанные байты,
+    // а наблюдатель узнаёт, какие байты вот-вот перезапишут
+    fn intent_header(&self) -> Header {
+        let capacity = self.capacity();
+        let unsynced = self.unsynced();
+        let len = self.len - unsynced.min(self.len as u64) as usize;
+        let tail = self.wrap(self.head + self.len);
+        let synced_tail = self.wrap(tail + capacity - (unsynced % capacity as u64) as usize);
+        Header {
+            capacity,
+            head: self.wrap(synced_tail + capacity - len),
+            len,
+            written: self.synced,
+            seq: self.seq + 1,
+        }
+    }
+
+    fn write_header(&mut self, header: Header) -> io::Result<()> {
+        self.file.seek(SeekFrom::Start(header.slot_offset()))?;
+        self.file.write_all(&header.encode())
+    }
+
+    // 8. Закольцовывание индекса меньше 2 * capacity без деления
+    fn wrap(&self, index: usize) -> usize {
+        if index >= self.capacity() {
+            index - self.capacity()
+        } else {
+            index
+        }
+    }
+}
+
+// 8.1. Кольцо io_uring не печатается - выводим только состояние буфера
+impl fmt::Debug for PersistentRing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PersistentRing")
+            .field("capacity", &self.capacity())
+            .field("head", &self.head)
+            .field("len", &self.len)
+            .field("written", &self.written)
+            .field("synced", &self.synced)
+            .finish_non_exhaustive()
+    }
+}
+
+// 8.2. Отправка связанной цепочки и разбор завершений: i-я запись (user_data = i)
+// должна вернуть expected[i]. Ожидание повторяем при прерывании сигналом, иначе
+// записи продолжились бы после выхода, а заголовок живёт на стеке вызывающего
+#[cfg(all(feature = "uring", target_os = "linux"))]
+fn submit_linked(
+    uring: &mut IoUring,
+    entries: Vec<io_uring::squeue::Entry>,
+    expected: &[i32],
+) -> io::Result<()> {
+    use io_uring::squeue::Flags;
+
+    let last = entries.len() - 1;
+    let entries: Vec<_> = entries
+        .into_iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let entry = entry.user_data(i as u64);
+            if i < last {
+                entry.flags(Flags::IO_LINK) // Следующая запись - только после этой
+            } else {
+                entry
+            }
+        })
+        .collect();
+
+    // SAFETY: буферы записей живы и не меняются, пока мы ждём завершения всех записей
+    unsafe { uring.submission().push_multiple(&entries) }
+        .map_err(|_| io::Error::other("Очередь io_uring переполнена!"))?;
+    while let Err(e) = uring.submit_and_wait(entries.len()) {
+        if e.kind() != io::ErrorKind::Interrupted {
+            return Err(e);
+        }
+    }
+
+    let mut result = Ok(());
+    for cqe in uring.completion() {
+        if cqe.result() < 0 {
+            result = result.and(Err(io::Error::from_raw_os_error(-cqe.result())));
+        } else if cqe.result() != expected[cqe.user_data() as usize] {
+            result = result.and(Err(io::Error::from(io::ErrorKind::WriteZero)));
+        }
+    }
+    result
+}
+
+fn read_u64(bytes: &[u8], at: usize) -> u64 {
+    let mut word = [0; 8];
+    word.copy_from_slice(&bytes[at..at + 8]);
+    u64::from_le_bytes(word)
+}
+
+// Размер или позиция из заголовка: не влезающее в usize (32-битные цели) - повреждение
+fn read_usize(bytes: &[u8], at: usize) -> Result<usize, HeaderError> {
+    usize::try_from(read_u64(bytes, at)).map_err(|_| HeaderError::Corrupt)
+}
+
+// Область данных на capacity байт со смещения offset. Ёмкость из заголовка не доверенная
+// (у версии 1 нет CRC, а CRC текущей может пересчитать кто угодно): сначала сверяем её
+// с размером файла, затем выделяем память без аварийного завершения
+fn read_data(file: &mut File, offset: usize, capacity: usize) -> io::Result<Vec<u8>> {
+    let end = offset
+        .checked_add(capacity)
+        .and_then(|end| u64::try_from(end).ok())
+        .ok_or(HeaderError::Corrupt)?;
+    if file.metadata()?.len() < end {
+        return Err(HeaderError::Corrupt.into());
+    }
+
+    let mut buffer = Vec::new();
+    buffer
+        .try_reserve_exact(capacity)
+        .map_err(|_| HeaderError::Corrupt)?;
+    buffer.resize(capacity, 0);
+    file.seek(SeekFrom::Start(offset as u64))?;
+    file.read_exact(&mut buffer)?;
+    Ok(buffer)
+}
+
+// 8.3. Переименование надёжно только после сброса каталога
+#[cfg(unix)]
+fn sync_parent(path: &Path) -> io::Result<()> {
+    match path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        Some(parent) => File::open(parent)?.sync_all(),
+        None => File::open(".")?.sync_all(),
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_parent(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+// 10. Наблюдатель: читает файл кольца из другого процесса, не мешая писателю.
+// Видит только сброшенное и идёт по окну писателя: байты, которые писатель отбросил
+// раньше, чем наблюдатель их прочёл, пропускаются и попадают в lost()
+#[derive(Debug)]
+pub struct RingObserver {
+    file: File,
+    position: u64, // Следующий байт в общем потоке записей
+    lost: u64,
+}
+
+impl RingObserver {
+    // 10.1. Открытие только для чтения; чтение начинается с начала окна писателя.
+    // Файл старой версии сначала должен открыть (и переписать) сам писатель
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut observer = RingObserver {
+            file: File::open(path)?,
+            position: 0,
+            lost: 0,
+        };
+        observer.position = observer.header()?.window_start();
+        Ok(observer)
+    }
+
+    // 10.2. Номер следующего байта в потоке и сколько байт пропущено
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    pub fn lost(&self) -> u64 {
+        self.lost
+    }
+
+    // 10.3. Сколько сброшенных байт ещё не прочитано
+    pub fn available(&mut self) -> io::Result<u64> {
+        let header = self.header()?;
+        Ok(header.written - self.position.max(header.window_start()))
+    }
+
+    // 10.4. Чтение новых байт; 0 - писатель ещё ничего не сбросил.
+    // Заголовок читается до и после данных: писатель пишет промежуточный заголовок
+    // раньше, чем перезаписывает байты, поэтому всё, что он мог перезаписать во время
+    // чтения, лежит до начала окна второго заголовка и отбрасывается
+    pub fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        let before = self.header()?;
+        self.skip_to(before.window_start());
+        let n = out.len().min((before.written - self.position) as usize);
+        if n == 0 {
+            return Ok(0);
+        }
+
+        let capacity = before.capacity;
+        let tail = (before.head + before.len) % capacity;
+        let back = (before.written - self.position) as usize;
+        let start = (tail + capacity - back) % capacity;
+        let first = n.min(capacity - start);
+        self.read_at(HEADER_SIZE + start, &mut out[..first])?;
+        self.read_at(HEADER_SIZE, &mut out[first..n])?;
+
+        let stale = self.header()?.window_start().saturating_sub(self.position);
+        let stale = stale.min(n as u64) as usize;
+        out.copy_within(stale..n, 0);
+        self.skip_to(self.position + stale as u64);
+        self.position += (n - stale) as u64;
+        Ok(n - stale)
+    }
+
+    // 10.5. Переход к байту start с учётом пропущенного
+    fn skip_to(&mut self, start: u64) {
+        if self.position < start {
+            self.lost += start - self.position;
+            self.position = start;
+        }
+    }
+
+    fn header(&mut self) -> io::Result<Header> {
+        let mut slots = [0; HEADER_SIZE];
+        self.read_at(0, &mut slots)?;
+        Ok(Header::newest(&slots)?)
+    }
+
+    fn read_at(&mut self, offset: usize, out: &mut [u8]) -> io::Result<()> {
+        self.file.seek(SeekFrom::Start(offset as u64))?;
+        self.file.read_exact(out)
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::{
+        Header, HeaderError, PersistentRing, RingObserver, SyncPolicy, HEADER_SIZE, SLOT_SIZE,
+        VERSION,
+    };
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    // Временный файл, удаляемый после теста
+    struct TempPath(PathBuf);
+
+    impl TempPath {
+        fn new(name: &str) -> Self {
+            let path =
+                std::env::temp_dir().join(format!("mordor-{}-{}.ring", name, std::process::id()));
+            TempPath(path)
+        }
+    }
+
+    impl Drop for TempPath {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_survives_reopen() {
+        let path = TempPath::new("reopen");
+        {
+            let mut ring = PersistentRing::create(&path.0, 8).unwrap();
+            assert_eq!(ring.extend_from_slice(b"abcdef"), 6);
+            assert_eq!(ring.discard(4), 4);
+            assert_eq!(ring.extend_from_slice(b"ghijkl"), 6); // Переход через край
+            assert_eq!(ring.unsynced(), 12);
+            ring.flush().unwrap();
+            assert_eq!(ring.unsynced(), 0);
+
+            ring.extend_from_slice(b"!"); // Не сброшено - после открытия не видно
+        }
+
+        let mut ring = PersistentRing::open(&path.0).unwrap();
+        assert_eq!((ring.len(), ring.capacity()), (8, 8));
+        let mut out = [0; 8];
+        assert_eq!(ring.drain_into(&mut out), 8);
+        assert_eq!(&out, b"efghijkl");
+        assert_eq!(
+            fs::metadata(&path.0).unwrap().len(),
+            (HEADER_SIZE + 8) as u64
+        );
+    }
+
+    #[test]
+    fn test_flush_writes_only_dirty() {
+        let path = TempPath::new("dirty");
+        let mut ring = PersistentRing::create(&path.0, 6).unwrap();
+        ring.extend_from_slice(b"abcd");
+        ring.flush().unwrap();
+        ring.discard(3);
+        ring.extend_from_slice(b"xyz"); // Позиции 4, 5 и 0
+        assert_eq!(ring.dirty_segments(), [(4, 2), (0, 1)]);
+        ring.flush().unwrap();
+
+        let bytes = fs::read(&path.0).unwrap();
+        assert_eq!(&bytes[HEADER_SIZE..], b"zbcdxy");
+        let mut out = [0; 4];
+        let mut reopened = PersistentRing::open(&path.0).unwrap();
+        assert_eq!(reopened.drain_into(&mut out), 4);
+        assert_eq!(&out, b"dxyz");
+    }
+
+    #[cfg(all(feature = "uring", target_os = "linux"))]
+    #[test]
+    fn test_uring_flush() {
+        let path = TempPath::new("uring");
+        let mut ring = PersistentRing::create(&path.0, 4).unwrap();
+        ring.extend_from_slice(b"ring");
+        ring.flush().unwrap();
+        assert!(ring.uring.is_some()); // Кольцо io_uring создано и переиспользуется
+        ring.discard(2);
+        ring.extend_from_slice(b"!!");
+        ring.flush().unwrap();
+
+        let mut out = [0; 4];
+        assert_eq!(
+            PersistentRing::open(&path.0).unwrap().peek_into(&mut out),
+            4
+        );
+        assert_eq!(&out, b"ng!!");
+    }
+
+    #[test]
+    fn test_sync_policies() {
+        let path = TempPath::new("wal");
+        let mut ring = PersistentRing::create(&path.0, 16).unwrap();
+        assert_eq!(ring.append(b"ab").unwrap(), 2);
+        assert_eq!(ring.unsynced(), 2); // По умолчанию - только явный flush
+
+        ring.set_sync_policy(SyncPolicy::EveryWrite);
+        ring.append(b"c").unwrap();
+        assert_eq!(ring.unsynced(), 0);
+
+        ring.set_sync_policy(SyncPolicy::EveryBytes(4));
+        ring.append(b"def").unwrap();
+        assert_eq!(ring.unsynced(), 3);
+        ring.append(b"g").unwrap();
+        assert_eq!(ring.unsynced(), 0);
+
+        ring.set_sync_policy(SyncPolicy::Interval(Duration::from_secs(3600)));
+        ring.append(b"h").unwrap();
+        assert!(!ring.sync_if_due().unwrap());
+        ring.set_sync_policy(SyncPolicy::Interval(Duration::ZERO));
+        assert!(ring.sync_if_due().unwrap());
+
+        // Восстановление: всё до последнего сброса на месте
+        ring.set_sync_policy(SyncPolicy::Manual);
+        ring.append(b"lost").unwrap();
+        drop(ring);
+        let ring = PersistentRing::open(&path.0).unwrap();
+        assert_eq!((ring.written(), ring.len()), (8, 8));
+    }
+
+    // Ошибка формата внутри io::Error
+    fn header_error(err: std::io::Error) -> HeaderError {
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        *err.into_inner().unwrap().downcast::<HeaderError>().unwrap()
+    }
+
+    #[test]
+    fn test_rejects_foreign_file() {
+        let path = TempPath::new("foreign");
+        fs::write(&path.0, [0u8; 200]).unwrap();
+        let err = PersistentRing::open(&path.0).unwrap_err();
+        assert_eq!(header_error(err), HeaderError::NotRing);
+
+        let mut bytes = vec![0u8; 200];
+        bytes[..4].copy_from_slice(b"MRDP");
+        bytes[4] = 9;
+        fs::write(&path.0, &bytes).unwrap();
+        let err = PersistentRing::open(&path.0).unwrap_err();
+        assert_eq!(header_error(err), HeaderError::UnsupportedVersion(9));
+    }
+
+    #[test]
+    fn test_torn_header_falls_back() {
+        let path = TempPath::new("torn");
+        let mut ring = PersistentRing::create(&path.0, 4).unwrap();
+        ring.extend_from_slice(b"ab");
+        ring.flush().unwrap(); // Номера 3 и 4
+        ring.extend_from_slice(b"cd");
+        ring.flush().unwrap(); // Номера 5 и 6: итоговый - в ячейке 0
+        drop(ring);
+
+        // Сбой оборвал итоговый заголовок: берётся промежуточный, окно прошлого сброса
+        let mut bytes = fs::read(&path.0).unwrap();
+        bytes[20] ^= 0xFF;
+        fs::write(&path.0, &bytes).unwrap();
+        let ring = PersistentRing::open(&path.0).unwrap();
+        assert_eq!((ring.len(), ring.written()), (2, 2));
+        drop(ring);
+
+        bytes[SLOT_SIZE + 20] ^= 0xFF; // Повреждены обе ячейки
+        fs::write(&path.0, &bytes).unwrap();
+        let err = PersistentRing::open(&path.0).unwrap_err();
+        assert_eq!(header_error(err), HeaderError::Checksum);
+
+        // Заголовок цел, но файл усечён
+        drop(PersistentRing::create(&path.0, 4).unwrap());
+        let file = fs::OpenOptions::new().write(true).open(&path.0).unwrap();
+        file.set_len((HEADER_SIZE + 2) as u64).unwrap();
+        let err = PersistentRing::open(&path.0).unwrap_err();
+        assert_eq!(header_error(err), HeaderError::Corrupt);
+    }
+
+    #[test]
+    fn test_rejects_oversized_capacity() {
+        let path = TempPath::new("oversized");
+
+        // Версия 1 без CRC: ёмкость почти u64::MAX в 64-байтном файле
+        let mut bytes = vec![0u8; 64];
+        bytes[..4].copy_from_slice(b"MRDP");
+        bytes[4] = 1;
+        bytes[8..16].copy_from_slice(&(u64::MAX - 8).to_le_bytes());
+        fs::write(&path.0, &bytes).unwrap();
+        let err = PersistentRing::open(&path.0).unwrap_err();
+        assert_eq!(header_error(err), HeaderError::Corrupt);
+
+        // Текущая версия с пересчитанной CRC: HEADER_SIZE + ёмкость переполняет usize
+        let header = Header {
+            capacity: usize::MAX - 1,
+            head: 0,
+            len: 0,
+            written: 0,
+            seq: 1,
+        };
+        let mut bytes = vec![0u8; HEADER_SIZE];
+        bytes[SLOT_SIZE..].copy_from_slice(&header.encode());
+        fs::write(&path.0, &bytes).unwrap();
+        let err = PersistentRing::open(&path.0).unwrap_err();
+        assert_eq!(header_error(err), HeaderError::Corrupt);
+    }
+
+    #[test]
+    fn test_intent_header_hides_overwritten() {
+        let path = TempPath::new("intent");
+        let mut ring = PersistentRing::create(&path.0, 4).unwrap();
+        ring.extend_from_slice(b"abcd");
+        ring.flush().unwrap();
+        ring.discard(2);
+        ring.extend_from_slice(b"xy"); // Ложатся на место a и b
+        ring.flush().unwrap();
+        drop(ring);
+
+        // Сбой после записи данных, до итогового заголовка: a и b не оживают
+        let mut bytes = fs::read(&path.0).unwrap();
+        bytes[20] ^= 0xFF;
+        fs::write(&path.0, &bytes).unwrap();
+        let ring = PersistentRing::open(&path.0).unwrap();
+        let mut out = [0; 4];
+        assert_eq!(ring.peek_into(&mut out), 2);
+        assert_eq!(&out[..2], b"cd");
+    }
+
+    #[test]
+    fn test_observer_tails_writer() {
+        let path = TempPath::new("observer");
+        let mut ring = PersistentRing::create(&path.0, 4).unwrap();
+        ring.extend_from_slice(b"ab");
+        ring.flush().unwrap();
+
+        let mut observer = RingObserver::open(&path.0).unwrap();
+        assert_eq!(observer.available().unwrap(), 2);
+        let mut out = [0; 8];
+        assert_eq!(observer.read(&mut out).unwrap(), 2);
+        assert_eq!(&out[..2], b"ab");
+        assert_eq!(observer.read(&mut out).unwrap(), 0);
+
+        ring.extend_from_slice(b"c"); // Не сброшено - наблюдатель не видит
+        assert_eq!(observer.available().unwrap(), 0);
+        ring.flush().unwrap();
+        assert_eq!(observer.read(&mut out).unwrap(), 1);
+        ring.discard(3);
+        ring.extend_from_slice(b"defg"); // Через край
+        ring.flush().unwrap();
+        assert_eq!(observer.read(&mut out).unwrap(), 4);
+        assert_eq!(&out[..4], b"defg");
+        assert_eq!((observer.position(), observer.lost()), (7, 0));
+
+        // Писатель отбросил и перезаписал то, что наблюдатель не успел прочитать
+        ring.discard(4);
+        ring.extend_from_slice(b"hijk");
+        ring.flush().unwrap();
+        ring.discard(2);
+        ring.extend_from_slice(b"lm");
+        ring.flush().unwrap();
+        assert_eq!(observer.read(&mut out).unwrap(), 4);
+        assert_eq!(&out[..4], b"jklm");
+        assert_eq!((observer.position(), observer.lost()), (13, 2));
+    }
+
+    #[test]
+    fn test_observer_concurrent() {
+        let path = TempPath::new("concurrent");
+        let mut ring = PersistentRing::create(&path.0, 64).unwrap();
+        let mut observer = RingObserver::open(&path.0).unwrap();
+
+        // Байт с номером i в потоке равен i % 251: прочитанное сверяется по номеру
+        let writer = std::thread::spawn(move || {
+            let mut next = 0u64;
+            for round in 0..300 {
+                ring.discard(ring.len().min(round % 48));
+                let data: Vec<u8> = (0..ring.free().min(40))
+                    .map(|_| {
+                        next += 1;
+                        ((next - 1) % 251) as u8
+                    })
+                    .collect();
+                ring.extend_from_slice(&data);
+                ring.flush().unwrap();
+            }
+            next
+        });
+
+        let mut out = [0; 32];
+        let mut seen = 0;
+        while !writer.is_finished() || observer.available().unwrap() > 0 {
+            let n = observer.read(&mut out).unwrap();
+            let start = observer.position() - n as u64;
+            for (i, &byte) in out[..n].iter().enumerate() {
+                assert_eq!(byte as u64, (start + i as u64) % 251);
+            }
+            seen += n as u64;
+        }
+        let total = writer.join().unwrap();
+        assert_eq!(seen + observer.lost(), total);
+    }
+
+    #[test]
+    fn test_export_and_reset() {
+        let path = TempPath::new("live");
+        let segment = TempPath::new("segment");
+        let mut ring = PersistentRing::create(&path.0, 8).unwrap();
+        assert_eq!(ring.export_and_reset(&segment.0).unwrap(), 0);
+        assert!(!segment.0.exists()); // Пустое кольцо сегмента не создаёт
+
+        ring.extend_from_slice(b"abcdef");
+        ring.discard(2);
+        assert_eq!(ring.export_and_reset(&segment.0).unwrap(), 4);
+        assert!(ring.is_empty());
+        ring.extend_from_slice(b"g"); // Кольцо продолжает работу
+        assert!(PersistentRing::open(&path.0).unwrap().is_empty()); // Очистка сброшена
+
+        let sealed = PersistentRing::open(&segment.0).unwrap();
+        let mut out = [0; 4];
+        assert_eq!(sealed.peek_into(&mut out), 4);
+        assert_eq!(&out, b"cdef");
+        assert_eq!((sealed.capacity(), sealed.written()), (4, 6));
+        assert!(!segment.0.with_extension("partial").exists());
+    }
+
+    #[test]
+    fn test_migrates_v1() {
+        let path = TempPath::new("v1");
+        let mut bytes = vec![0u8; 64 + 4];
+        bytes[..4].copy_from_slice(b"MRDP");
+        bytes[4] = 1;
+        bytes[8..16].copy_from_slice(&4u64.to_le_bytes()); // Ёмкость
+        bytes[16..24].copy_from_slice(&3u64.to_le_bytes()); // Начало
+        bytes[24..32].copy_from_slice(&2u64.to_le_bytes()); // Длина
+        bytes[32..40].copy_from_slice(&7u64.to_le_bytes()); // Записано
+        bytes[64..].copy_from_slice(b"bxxa");
+        fs::write(&path.0, &bytes).unwrap();
+
+        let ring = PersistentRing::open(&path.0).unwrap();
+        let mut out = [0; 2];
+        assert_eq!(ring.peek_into(&mut out), 2);
+        assert_eq!(&out, b"ab");
+        assert_eq!(ring.written(), 7);
+        assert_eq!(
+            fs::metadata(&path.0).unwrap().len(),
+            (HEADER_SIZE + 4) as u64
+        );
+        assert_eq!(fs::read(&path.0).unwrap()[4], VERSION);
+
+        // Переписанный файл открывается уже без миграции
+        assert_eq!(PersistentRing::open(&path.0).unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,230 @@
+// Кольцо для простых копируемых типов (числа, массивы чисел, #[repr(C)] структуры
+// из них). В отличие от RingBuffer ячейки хранятся без Option, поэтому пакетные
+// запись и чтение - два копирования памяти на сегмент вместо поэлементных перемещений
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem::MaybeUninit;
+use core::ptr;
+
+use crate::ring::full_error;
+
+// 1. Хранилище и логическое окно [head, head + len): ячейки окна всегда инициализированы
+#[derive(Debug, Clone)]
+pub struct PodRing<T: Copy> {
+    buf: Vec<MaybeUninit<T>>,
+    head: usize,
+    len: usize,
+}
+
+impl<T: Copy> PodRing<T> {
+    // 1.1. Кольцо на capacity элементов; хранилище не заполняется
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "В буфере есть место!");
+
+        PodRing {
+            buf: vec![MaybeUninit::uninit(); capacity],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    // 2. Размеры
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == self.buf.len()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn free(&self) -> usize {
+        self.buf.len() - self.len
+    }
+
+    pub fn heap_bytes(&self) -> usize {
+        self.buf.capacity() * core::mem::size_of::<T>()
+    }
+
+    // 3. Запись одного элемента
+    pub fn push(&mut self, value: T) -> Result<(), String> {
+        if self.is_full() {
+            return Err(full_error());
+        }
+        self.extend_from_slice(&[value]);
+        Ok(())
+    }
+
+    // 4. Чтение самого старого элемента
+    pub fn pop(&mut self) -> Option<T> {
+        let value = *self.get(0)?;
+        self.discard(1);
+        Some(value)
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        let (first, second) = self.as_slices();
+        first.get(index).or_else(|| second.get(index - first.len()))
+    }
+
+    // 5. Запись стольких элементов, сколько помещается: не больше двух копирований
+    // (до конца хранилища и с его начала)
+    pub fn extend_from_slice(&mut self, data: &[T]) -> usize {
+        let n = data.len().min(self.free());
+        let tail = self.wrap(self.head + self.len);
+        let first = n.min(self.buf.len() - tail);
+
+        // SAFETY: [tail, tail + first) и [0, n - first) лежат в хранилище и не
+        // пересекаются с data, которая заимствована неизменяемо
+        unsafe {
+            let dst = self.buf.as_mut_ptr() as *mut T;
+            ptr::copy_nonoverlapping(data.as_ptr(), dst.add(tail), first);
+            ptr::copy_nonoverlapping(data.as_ptr().add(first), dst, n - first);
+        }
+        self.len += n;
+        n
+    }
+
+    // 6. Копирование в срез без вычитывания
+    pub fn peek_into(&self, out: &mut [T]) -> usize {
+        let (first, second) = self.as_slices();
+        let n = out.len().min(self.len);
+        let split = n.min(first.len());
+
+        // SAFETY: исходные части - инициализированные ячейки окна, out заимствован
+        // изменяемо и с хранилищем не пересекается
+        unsafe {
+            ptr::copy_nonoverlapping(first.as_ptr(), out.as_mut_ptr(), split);
+            ptr::copy_nonoverlapping(second.as_ptr(), out.as_mut_ptr().add(split), n - split);
+        }
+        n
+    }
+
+    // 6.1. Чтение в срез вызывающего; возвращает, сколько элементов скопировано
+    pub fn drain_into_slice(&mut self, out: &mut [T]) -> usize {
+        let n = self.peek_into(out);
+        self.discard(n)
+    }
+
+    // 6.2. Отбрасывание до n самых старых элементов
+    pub fn discard(&mut self, n: usize) -> usize {
+        let n = n.min(self.len);
+        self.head = self.wrap(self.head + n);
+        self.len -= n;
+        n
+    }
+
+    pub fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+    }
+
+    // 7. Содержимое двумя срезами: от head до конца хранилища и с начала
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let first = self.len.min(self.buf.len() - self.head);
+        let (front, back) = (
+            &self.buf[self.head..self.head + first],
+            &self.buf[..self.len - first],
+        );
+        // SAFETY: обе части лежат в окне, а ячейки окна инициализированы;
+        // раскладки MaybeUninit<T> и T совпадают
+        unsafe {
+            (
+                &*(front as *const [MaybeUninit<T>] as *const [T]),
+                &*(back as *const [MaybeUninit<T>] as *const [T]),
+            )
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        let (first, second) = self.as_slices();
+        first.iter().chain(second)
+    }
+
+    fn wrap(&self, index: usize) -> usize {
+        if index >= self.buf.len() {
+            index - self.buf.len()
+        } else {
+            index
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PodRing;
+    use crate::RingBuffer;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_bulk_across_seam() {
+        let mut ring = PodRing::<u32>::new(5);
+        assert_eq!(ring.extend_from_slice(&[1, 2, 3]), 3);
+        assert_eq!(ring.pop(), Some(1));
+        assert_eq!(ring.extend_from_slice(&[4, 5, 6, 7, 8]), 3); // Через шов
+        assert!(ring.push(9).is_err());
+        assert_eq!(ring.as_slices(), (&[2, 3, 4, 5][..], &[6][..]));
+
+        let mut out = [0; 3];
+        assert_eq!(ring.drain_into_slice(&mut out), 3);
+        assert_eq!(out, [2, 3, 4]);
+        assert_eq!(ring.iter().copied().collect::<Vec<_>>(), [5, 6]);
+        assert_eq!(ring.get(1), Some(&6));
+        assert_eq!(ring.get(2), None);
+    }
+
+    #[test]
+    fn test_matches_ring_buffer() {
+        let mut pod = PodRing::<[u16; 3]>::new(17);
+        let mut plain = RingBuffer::new(17);
+
+        let mut next = 0u16;
+        for round in 0..50 {
+            let data: Vec<[u16; 3]> = (0..round % 11)
+                .map(|_| {
+                    next = next.wrapping_add(1);
+                    [next, !next, next ^ 0x55]
+                })
+                .collect();
+            let written = pod.extend_from_slice(&data);
+            for &item in &data[..written] {
+                plain.push(item).unwrap();
+            }
+            assert!(plain.is_full() || written == data.len());
+
+            let mut out = vec![[0; 3]; round % 7];
+            let read = pod.drain_into_slice(&mut out);
+            for item in &out[..read] {
+                assert_eq!(plain.pop(), Some(*item));
+            }
+            assert_eq!(pod.len(), plain.len());
+        }
+    }
+
+    #[test]
+    fn test_large_transfer() {
+        // Тысячи элементов за раз: два копирования памяти на сегмент
+        let data: Vec<u64> = (0..3001).collect();
+        let mut ring = PodRing::new(4000);
+        assert_eq!(ring.extend_from_slice(&data[..2000]), 2000);
+        let mut out = vec![0; 2000];
+        assert_eq!(ring.drain_into_slice(&mut out), 2000);
+        assert_eq!(out, data[..2000]);
+
+        assert_eq!(ring.extend_from_slice(&data), 3001); // Через шов
+        let mut out = vec![0; 3001];
+        assert_eq!(ring.drain_into_slice(&mut out), 3001);
+        assert_eq!(out, data);
+        assert_eq!(ring.heap_bytes(), 4000 * 8);
+    }
+}
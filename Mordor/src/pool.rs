@@ -0,0 +1,187 @@
+// Пул переиспользуемых объектов поверх кольца
+
+use alloc::boxed::Box;
+use core::cell::{Cell, RefCell};
+use core::ops::{Deref, DerefMut};
+
+#[cfg(feature = "std")]
+use crate::ByteRing;
+use crate::RingBuffer;
+
+// 1. Свободные объекты лежат в кольце, недостающие создаёт фабрика
+pub struct Pool<T> {
+    free: RefCell<RingBuffer<T>>, // Возвращённые объекты, готовые к выдаче
+    factory: RefCell<Box<dyn FnMut() -> T>>, // Создаёт объект, если свободных нет
+    created: Cell<usize>,         // Сколько объектов уже создано
+}
+
+impl<T> Pool<T> {
+    // 1.1. Создаём пул не более чем на capacity объектов
+    pub fn new(capacity: usize, factory: impl FnMut() -> T + 'static) -> Self {
+        Pool {
+            free: RefCell::new(RingBuffer::new(capacity)),
+            factory: RefCell::new(Box::new(factory)),
+            created: Cell::new(0),
+        }
+    }
+
+    // 2. Выдача объекта: сначала из свободных, иначе лениво создаём новый
+    pub fn acquire(&self) -> Option<PoolGuard<'_, T>> {
+        let popped = self.free.borrow_mut().pop(); // Заимствование кольца не держим дальше
+        let value = match popped {
+            Some(value) => value,
+            None if self.created.get() < self.capacity() => {
+                self.created.set(self.created.get() + 1);
+                (self.factory.borrow_mut())()
+            }
+            None => return None, // Все объекты уже выданы
+        };
+
+        Some(PoolGuard {
+            pool: self,
+            value: Some(value),
+        })
+    }
+
+    // 3. Сколько объектов можно выдать прямо сейчас
+    pub fn available(&self) -> usize {
+        self.capacity() - self.created.get() + self.free.borrow().len()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.free.borrow().capacity()
+    }
+}
+
+// 4. Выданный объект, возвращается в пул при уничтожении
+pub struct PoolGuard<'a, T> {
+    pool: &'a Pool<T>,
+    value: Option<T>,
+}
+
+impl<T> Deref for PoolGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value.as_ref().expect("Объект уже возвращён!")
+    }
+}
+
+impl<T> DerefMut for PoolGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value.as_mut().expect("Объект уже возвращён!")
+    }
+}
+
+impl<T> Drop for PoolGuard<'_, T> {
+    fn drop(&mut self) {
+        if let Some(value) = self.value.take() {
+            // Место в кольце есть всегда: объектов не больше, чем ёмкость
+            let _ = self.pool.free.borrow_mut().push(value);
+        }
+    }
+}
+
+// 5. Пул байтовых колец на каждый поток: обработчик запроса берёт кольцо нужной
+// ёмкости на время замыкания и возвращает его пустым, без новых выделений памяти
+#[cfg(feature = "std")]
+const TLS_POOL_LIMIT: usize = 8; // Сколько свободных колец поток держит про запас
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static TLS_BUFFERS: RefCell<alloc::vec::Vec<ByteRing>> = const { RefCell::new(alloc::vec::Vec::new()) };
+}
+
+// 5.1. Кольцо ёмкости capacity на время f. Вложенные вызовы получают разные кольца;
+// при панике в f кольцо просто не возвращается в пул
+#[cfg(feature = "std")]
+pub fn with_tls_buffer<R>(capacity: usize, f: impl FnOnce(&mut ByteRing) -> R) -> R {
+    let reused = TLS_BUFFERS.with(|buffers| {
+        let mut buffers = buffers.borrow_mut();
+        let index = buffers
+            .iter()
+            .position(|ring| ring.capacity() == capacity)?;
+        Some(buffers.swap_remove(index))
+    });
+    let mut ring = reused.unwrap_or_else(|| ByteRing::new(capacity));
+
+    let result = f(&mut ring);
+
+    ring.discard(ring.len());
+    TLS_BUFFERS.with(|buffers| {
+        let mut buffers = buffers.borrow_mut();
+        if buffers.len() < TLS_POOL_LIMIT {
+            buffers.push(ring);
+        }
+    });
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pool;
+
+    #[test]
+    fn test_lazy_creation_and_reuse() {
+        let pool = Pool::new(2, || Vec::<u8>::with_capacity(16));
+        assert_eq!(pool.available(), 2);
+
+        {
+            let mut buf = pool.acquire().unwrap();
+            buf.push(7);
+            assert_eq!(pool.available(), 1);
+        } // Возвращается в пул
+
+        let buf = pool.acquire().unwrap();
+        assert_eq!(*buf, vec![7]); // Тот же объект, а не новый
+    }
+
+    #[test]
+    fn test_exhausted() {
+        let pool = Pool::new(1, || 0u32);
+        let first = pool.acquire();
+
+        assert!(first.is_some());
+        assert!(pool.acquire().is_none());
+        drop(first);
+        assert!(pool.acquire().is_some());
+    }
+
+    #[test]
+    fn test_factory_called_once_per_object() {
+        let mut counter = 0;
+        let pool = Pool::new(3, move || {
+            counter += 1;
+            counter
+        });
+
+        for _ in 0..5 {
+            let value = pool.acquire().unwrap();
+            assert_eq!(*value, 1); // Одного объекта хватает на все итерации
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_tls_buffer_reused() {
+        use super::with_tls_buffer;
+
+        let first = with_tls_buffer(64, |ring| {
+            assert_eq!(ring.extend_from_slice(b"scratch"), 7);
+            ring.write_chunk_uninit(1).as_ptr() as usize - 7
+        });
+        let again = with_tls_buffer(64, |ring| {
+            assert!(ring.is_empty()); // Возвращено пустым
+            assert_eq!(ring.capacity(), 64);
+            ring.write_chunk_uninit(1).as_ptr() as usize - 7
+        });
+        assert_eq!(first, again); // То же хранилище
+
+        with_tls_buffer(64, |outer| {
+            with_tls_buffer(64, |inner| {
+                assert!(!core::ptr::eq(outer, inner)); // Вложенный вызов - другое кольцо
+            })
+        });
+        with_tls_buffer(16, |ring| assert_eq!(ring.capacity(), 16));
+    }
+}
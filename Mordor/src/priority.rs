@@ -0,0 +1,159 @@
+// Кольцо с приоритетными полосами поверх общего бюджета ёмкости
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::ring::full_error;
+use crate::RingBuffer;
+
+// 1. Полосы с приоритетами: индекс 0 - самый срочный. Полоса растёт удвоением по мере
+// записи и сжимается, когда опустеет на три четверти, поэтому память - O(capacity + lanes),
+// а не lanes * capacity
+#[derive(Debug)]
+pub struct PriorityRing<T> {
+    lanes: Vec<RingBuffer<T>>, // Каждая полоса может занять весь бюджет
+    capacity: usize,           // Общая ёмкость на все полосы
+    size: usize,               // Сколько элементов во всех полосах вместе
+}
+
+impl<T> PriorityRing<T> {
+    // 1.1. Создаём кольцо на capacity элементов с lanes полосами
+    pub fn new(capacity: usize, lanes: usize) -> Self {
+        assert!(capacity > 0, "В буфере есть место!");
+        assert!(lanes > 0, "Нужна хотя бы одна полоса!");
+
+        PriorityRing {
+            lanes: (0..lanes).map(|_| RingBuffer::new(1)).collect(),
+            capacity,
+            size: 0,
+        }
+    }
+
+    // 1.2. Частый случай: две полосы, высокая (0) и низкая (1)
+    pub fn two_lanes(capacity: usize) -> Self {
+        Self::new(capacity, 2)
+    }
+
+    // 2. Запись в заданную полосу
+    pub fn push(&mut self, lane: usize, value: T) -> Result<(), String> {
+        if lane >= self.lanes.len() {
+            return Err("Нет такой полосы!".to_string());
+        }
+        if self.is_full() {
+            return Err(full_error()); // Общий бюджет исчерпан
+        }
+
+        let ring = &mut self.lanes[lane];
+        if ring.is_full() {
+            ring.resize((ring.capacity() * 2).min(self.capacity));
+        }
+        ring.push(value)?;
+        self.size += 1;
+        Ok(())
+    }
+
+    // 3. Чтение: сначала опустошаем более приоритетные полосы
+    pub fn pop(&mut self) -> Option<T> {
+        let lane = self.lanes.iter().position(|lane| !lane.is_empty())?;
+        self.pop_lane(lane)
+    }
+
+    // 4. Чтение только из заданной полосы
+    pub fn pop_lane(&mut self, lane: usize) -> Option<T> {
+        let ring = self.lanes.get_mut(lane)?;
+        let value = ring.pop()?;
+        if ring.len() <= ring.capacity() / 4 {
+            ring.shrink_to(ring.capacity() / 2); // Запас вдвое: без перевыделений на каждом шаге
+        }
+        self.size -= 1;
+        Some(value)
+    }
+
+    // 5. Размеры
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn lane_len(&self, lane: usize) -> usize {
+        self.lanes.get(lane).map_or(0, |l| l.len())
+    }
+
+    pub fn lane_count(&self) -> usize {
+        self.lanes.len()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    // 5.1. Память в куче: хранилища полос и таблица полос
+    pub fn heap_bytes(&self) -> usize {
+        let lanes: usize = self.lanes.iter().map(RingBuffer::heap_bytes).sum();
+        lanes + self.lanes.capacity() * core::mem::size_of::<RingBuffer<T>>()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.size == self.capacity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PriorityRing;
+
+    #[test]
+    fn test_high_priority_first() {
+        let mut pr = PriorityRing::two_lanes(4);
+        assert!(pr.push(1, "bulk-1").is_ok());
+        assert!(pr.push(1, "bulk-2").is_ok());
+        assert!(pr.push(0, "ctrl").is_ok());
+
+        assert_eq!(pr.pop(), Some("ctrl")); // Срочное обгоняет данные
+        assert_eq!(pr.pop(), Some("bulk-1"));
+        assert_eq!(pr.pop(), Some("bulk-2"));
+        assert_eq!(pr.pop(), None);
+    }
+
+    #[test]
+    fn test_shared_capacity() {
+        let mut pr = PriorityRing::new(3, 3);
+        assert!(pr.push(2, 1).is_ok());
+        assert!(pr.push(2, 2).is_ok());
+        assert!(pr.push(0, 3).is_ok());
+        assert!(pr.push(1, 4).is_err()); // Бюджет общий
+
+        assert!(pr.is_full());
+        assert_eq!(pr.lane_len(2), 2);
+        assert_eq!(pr.pop_lane(2), Some(1));
+        assert!(pr.push(1, 4).is_ok());
+        assert_eq!(pr.len(), 3);
+    }
+
+    #[test]
+    fn test_lanes_share_memory() {
+        let mut pr = PriorityRing::new(1024, 16);
+        let empty = pr.heap_bytes();
+        for value in 0..1024_u64 {
+            assert!(pr.push(value as usize % 16, value).is_ok());
+        }
+        // Полосы выросли ровно под содержимое, а не до 16 * 1024 ячеек
+        let slot = core::mem::size_of::<Option<u64>>();
+        assert!(pr.heap_bytes() <= empty + 1024 * slot);
+
+        while pr.pop().is_some() {}
+        assert_eq!(pr.heap_bytes(), empty); // Опустевшие полосы сжались обратно
+    }
+
+    #[test]
+    fn test_bad_lane() {
+        let mut pr = PriorityRing::two_lanes(2);
+
+        assert!(pr.push(5, 0).is_err());
+        assert_eq!(pr.pop_lane(5), None);
+        assert!(pr.is_empty());
+    }
+}
@@ -0,0 +1,91 @@
+// Буфер воспроизведения опыта для обучения с подкреплением
+
+use alloc::vec::Vec;
+
+use crate::rng::RandomSource;
+use crate::RingBuffer;
+
+// 1. Хранит последние N элементов, вытесняя самые старые
+#[derive(Debug)]
+pub struct ReplayBuffer<T> {
+    ring: RingBuffer<T>,
+}
+
+impl<T> ReplayBuffer<T> {
+    // 1.1. Создаём буфер на capacity элементов
+    pub fn new(capacity: usize) -> Self {
+        ReplayBuffer {
+            ring: RingBuffer::new(capacity),
+        }
+    }
+
+    // 2. Добавление опыта (возвращает вытесненный элемент, если был)
+    pub fn push(&mut self, value: T) -> Option<T> {
+        self.ring.push_overwrite(value)
+    }
+
+    // 3. Равномерная выборка k элементов с возвращением, без удаления из буфера
+    pub fn sample<R: RandomSource>(&self, k: usize, rng: &mut R) -> Vec<&T> {
+        if self.ring.is_empty() {
+            return Vec::new(); // Выбирать не из чего
+        }
+
+        (0..k)
+            .filter_map(|_| self.ring.get(rng.gen_index(self.ring.len())))
+            .collect()
+    }
+
+    // 4. Текущее количество элементов
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+
+    // 5. Проверка на пустоту
+    pub fn is_empty(&self) -> bool {
+        self.ring.is_empty()
+    }
+
+    // 6. Максимальная вместимость
+    pub fn capacity(&self) -> usize {
+        self.ring.capacity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReplayBuffer;
+    use crate::rng::XorShift64;
+
+    #[test]
+    fn test_overwrites_oldest() {
+        let mut rb = ReplayBuffer::new(3);
+
+        assert_eq!(rb.push(1), None);
+        assert_eq!(rb.push(2), None);
+        assert_eq!(rb.push(3), None);
+        assert_eq!(rb.push(4), Some(1)); // Самый старый вытеснен
+        assert_eq!(rb.len(), 3);
+    }
+
+    #[test]
+    fn test_sample_keeps_items() {
+        let mut rb = ReplayBuffer::new(4);
+        for i in 0..10 {
+            rb.push(i);
+        }
+        let mut rng = XorShift64::new(42);
+
+        let sample = rb.sample(100, &mut rng);
+        assert_eq!(sample.len(), 100);
+        assert!(sample.iter().all(|&&v| (6..10).contains(&v))); // Только последние 4
+        assert_eq!(rb.len(), 4); // Ничего не удалено
+    }
+
+    #[test]
+    fn test_sample_empty() {
+        let rb: ReplayBuffer<u8> = ReplayBuffer::new(2);
+        let mut rng = XorShift64::new(1);
+
+        assert!(rb.sample(5, &mut rng).is_empty());
+    }
+}
@@ -0,0 +1,112 @@
+// Равномерная выборка из потока в фиксированной памяти (алгоритм R):
+// после n записей каждый из n элементов лежит в выборке с вероятностью capacity / n
+
+use crate::rng::RandomSource;
+use crate::RingBuffer;
+
+// 1. Выборка и счётчик всех записанных в поток элементов
+#[derive(Debug, Clone)]
+pub struct Reservoir<T> {
+    ring: RingBuffer<T>,
+    seen: u64, // Сколько элементов прошло через push
+}
+
+impl<T> Reservoir<T> {
+    // 1.1. Создаём выборку на capacity элементов
+    pub fn new(capacity: usize) -> Self {
+        Reservoir {
+            ring: RingBuffer::new(capacity),
+            seen: 0,
+        }
+    }
+
+    // 2. Очередной элемент потока; возвращает выброшенный элемент (прежний
+    // из выборки или сам value), пока выборка не заполнена - None
+    pub fn push<R: RandomSource>(&mut self, value: T, rng: &mut R) -> Option<T> {
+        self.seen += 1;
+        if !self.ring.is_full() {
+            let _ = self.ring.push(value); // Место есть, ошибки не будет
+            return None;
+        }
+
+        // Случайная позиция среди всех seen элементов; попала в выборку - замещаем
+        let index = ((rng.next_u64() as u128 * self.seen as u128) >> 64) as u64;
+        match usize::try_from(index)
+            .ok()
+            .and_then(|i| self.ring.get_mut(i))
+        {
+            Some(slot) => Some(core::mem::replace(slot, value)),
+            None => Some(value),
+        }
+    }
+
+    // 3. Текущая выборка (порядок не несёт смысла)
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.ring.iter()
+    }
+
+    // 4. Сколько элементов видел поток
+    pub fn seen(&self) -> u64 {
+        self.seen
+    }
+
+    // 5. Размеры и сброс
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ring.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.ring.capacity()
+    }
+
+    pub fn clear(&mut self) {
+        self.ring.clear();
+        self.seen = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Reservoir;
+    use crate::rng::XorShift64;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_fills_then_replaces() {
+        let mut rng = XorShift64::new(7);
+        let mut sample = Reservoir::new(3);
+        for i in 0..3 {
+            assert_eq!(sample.push(i, &mut rng), None);
+        }
+        assert_eq!(sample.iter().copied().collect::<Vec<_>>(), [0, 1, 2]);
+
+        assert!(sample.push(3, &mut rng).is_some()); // Выброшен ровно один
+        assert_eq!((sample.len(), sample.seen()), (3, 4));
+
+        sample.clear();
+        assert!(sample.is_empty());
+        assert_eq!(sample.seen(), 0);
+    }
+
+    #[test]
+    fn test_uniform() {
+        let mut rng = XorShift64::new(42);
+        let mut hits = [0u32; 100];
+
+        // 2000 выборок по 10 из 100: каждое значение ожидается ~200 раз
+        for _ in 0..2000 {
+            let mut sample = Reservoir::new(10);
+            for i in 0..100 {
+                sample.push(i, &mut rng);
+            }
+            for &i in sample.iter() {
+                hits[i] += 1;
+            }
+        }
+        assert!(hits.iter().all(|&n| (140..=260).contains(&n)), "{:?}", hits);
+    }
+}
@@ -0,0 +1,1981 @@
+// Основной кольцевой буфер
+//
+// Сложность (n - размер, c - ёмкость), закреплена тестами со счётчиком перемещений:
+//   push, pop, pop_back, get, push_overwrite - O(1), ровно одно перемещение элемента
+//   (с вытеснением - два); с авторасширением push - амортизированно O(1): ёмкость
+//   растёт геометрически (рекомендация - p99 заполнения плюс четверть), поэтому
+//   перенос при расширении приходится на уже записанные элементы;
+//   extend, drain, skip, truncate - O(k) для k затронутых элементов;
+//   insert, remove - O(n) сдвигов, retain - O(n), resize и shrink_to - O(n) переносов, clear - O(c)
+//
+// Для типов нулевого размера (RingBuffer<()> как ограниченный счётчик или очередь
+// билетов) хранилище не выделяется: ячейки пусты, занятость следует из головы
+// и размера, а push и pop только меняют счётчики
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::ControlFlow;
+
+#[cfg(feature = "audit")]
+use crate::{AuditLog, AuditOp};
+use crate::{CapacityAdvisor, PartialWrite, RingBufferBuilder, Stats, StatsSnapshot};
+use alloc::boxed::Box;
+
+// 0. Счётчик перемещений элементов (только в тестах): записи в ячейку и извлечения
+// из неё. По нему тесты проверяют, что операции не копируют больше обещанного
+#[cfg(test)]
+std::thread_local! {
+    pub(crate) static MOVES: core::cell::Cell<usize> = const { core::cell::Cell::new(0) };
+}
+
+#[cfg(test)]
+fn count_moves(n: usize) {
+    MOVES.with(|moves| moves.set(moves.get() + n));
+}
+
+#[cfg(not(test))]
+#[inline(always)]
+fn count_moves(_n: usize) {}
+
+// 1. Определяем структуру данных
+
+#[derive(Debug)]
+pub struct RingBuffer<T> {
+    buffer: Vec<Option<T>>,            // Хранилище данных (None - пустая ячейка)
+    capacity: usize,                   // Максимальная вместимость буфера
+    head: usize,                       // Индекс для чтения (голова)
+    tail: usize,                       // Индекс для записи (хвост)
+    size: usize,                       // Текущее количество элементов
+    popped: u64,                       // Сколько элементов ушло с головы за всё время
+    generation: u64,                   // Растёт, когда смещения элементов сдвигаются
+    pub(crate) policy: OverflowPolicy, // Что делает push при заполненном буфере
+    pub(crate) stats: Option<Stats>,   // Счётчики операций (если включены)
+    pub(crate) advisor: Option<Box<CapacityAdvisor>>, // Гистограмма заполнения (если включена)
+    #[cfg(feature = "audit")]
+    pub(crate) audit: Option<Box<AuditLog>>, // Журнал последних операций (если включён)
+}
+
+// 1.0.1. Бюджет памяти буфера по умолчанию (Default) - одна страница
+pub const DEFAULT_BYTES: usize = 4096;
+
+// 1.1. Поведение push при заполненном буфере
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    #[default]
+    Reject, // Вернуть ошибку
+    Overwrite, // Вытеснить самый старый элемент
+}
+
+// 2. Реализация методов
+impl<T> RingBuffer<T> {
+    // 2.1. Создаём новый буфер заданного размера
+    pub fn new(capacity: usize) -> Self {
+        // Проверяем, что размер буфера положительный
+        assert!(capacity > 0, "В буфере есть место!");
+
+        RingBuffer {
+            buffer: Self::slots(capacity),  // Инициализируем пустыми значениями
+            capacity,                       // Сохраняем ёмкость
+            head: 0,                        // Начинаем с индекса 0
+            tail: 0,                        // Начинаем с индекса 0
+            size: 0,                        // Начальный размер - 0
+            popped: 0,                      // Ничего ещё не читали
+            generation: 0,                  // Смещения ещё не сдвигались
+            policy: OverflowPolicy::Reject, // По умолчанию переполнение - ошибка
+            stats: None,                    // Счётчики выключены
+            advisor: None,                  // Советчик выключен
+            #[cfg(feature = "audit")]
+            audit: None, // Журнал выключен
+        }
+    }
+
+    // 2.1.1. Пустой буфер, у которого голова и хвост стоят на индексе start
+    // (нужен генераторам, чтобы получать содержимое, проходящее через границу массива)
+    #[cfg(any(feature = "arbitrary", feature = "quickcheck"))]
+    pub(crate) fn with_start(capacity: usize, start: usize) -> Self {
+        let mut ring = Self::new(capacity);
+        ring.head = start % capacity;
+        ring.tail = start % capacity;
+        ring
+    }
+
+    // 2.1.2. Буфер в пределах бюджета памяти: ёмкость - сколько ячеек помещается
+    // в bytes байт (ячейка хранит Option<T>); бюджет меньше ячейки - паника
+    pub fn with_capacity_bytes(bytes: usize) -> Self {
+        Self::new(Self::capacity_for_bytes(bytes))
+    }
+
+    // 2.1.3. Сколько элементов помещается в bytes байт хранилища
+    pub fn capacity_for_bytes(bytes: usize) -> usize {
+        bytes / core::mem::size_of::<Option<T>>().max(1)
+    }
+
+    // 2.2. Построитель для буфера с дополнительными настройками
+    pub fn builder() -> RingBufferBuilder<T> {
+        RingBufferBuilder::new()
+    }
+
+    // 2. Проверка на пустоту
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.size == 0 // Если размер 0 - буфер пуст
+    }
+
+    // 3. Проверка на заполненность
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.size == self.capacity // Если размер равен ёмкости - буфер полон
+    }
+
+    // 4. Текущее количество элементов
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.size // Просто возвращаем размер
+    }
+
+    // 4.1. Максимальная вместимость буфера
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    // 4.2. Текущая политика переполнения
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    // 4.3. Счётчики операций, если они включены
+    pub fn stats(&self) -> Option<&Stats> {
+        self.stats.as_ref()
+    }
+
+    // 4.3.1. Все счётчики вместе с размерами одним снимком
+    pub fn stats_snapshot(&self) -> Option<StatsSnapshot> {
+        let stats = self.stats.as_ref()?;
+        Some(stats.snapshot(self.size, self.capacity))
+    }
+
+    // 4.3.2. Гистограмма заполнения и рекомендуемая по ней ёмкость, если советчик включён
+    pub fn capacity_advisor(&self) -> Option<&CapacityAdvisor> {
+        self.advisor.as_deref()
+    }
+
+    pub fn suggested_capacity(&self) -> Option<usize> {
+        Some(self.advisor.as_ref()?.suggested_capacity())
+    }
+
+    // 4.3.3. Сколько памяти буфер занимает в куче: ячейки хранилища, советчик
+    // и журнал. Счётчики лежат в самой структуре; память, которой владеют
+    // сами элементы (например, Vec<u8>), не учитывается
+    pub fn heap_bytes(&self) -> usize {
+        let slots = self.buffer.capacity() * core::mem::size_of::<Option<T>>();
+        let advisor = self
+            .advisor
+            .as_ref()
+            .map_or(0, |_| core::mem::size_of::<CapacityAdvisor>());
+        #[cfg(feature = "audit")]
+        let audit = self
+            .audit
+            .as_ref()
+            .map_or(0, |log| core::mem::size_of::<AuditLog>() + log.heap_bytes());
+        #[cfg(not(feature = "audit"))]
+        let audit = 0;
+        slots + advisor + audit
+    }
+
+    // 4.4. Журнал последних операций, если он включён
+    #[cfg(feature = "audit")]
+    pub fn audit_log(&self) -> Option<&AuditLog> {
+        self.audit.as_deref()
+    }
+
+    // 4.4.1. Запись операции в журнал вместе с размером после неё
+    #[cfg(feature = "audit")]
+    fn audit(&mut self, op: AuditOp, count: usize) {
+        let len = self.size;
+        if let Some(log) = &mut self.audit {
+            log.record(op, count, len);
+        }
+        #[cfg(feature = "visualize")]
+        std::println!("{:?} x{}: {}", op, count, self.visualize());
+    }
+
+    // 5. Запись элемента
+    #[inline]
+    pub fn push(&mut self, value: T) -> Result<(), String> {
+        self.push_one(value)?;
+        #[cfg(feature = "audit")]
+        self.audit(AuditOp::Push, 1);
+        Ok(())
+    }
+
+    // 5.0. Запись без учёта в журнале (для составных операций)
+    #[inline]
+    fn push_one(&mut self, value: T) -> Result<(), String> {
+        if self.is_full() && !self.auto_grow() {
+            if self.policy == OverflowPolicy::Reject {
+                return Err(full_error()); // Ошибка если полон
+            }
+            self.evict(); // Политика Overwrite - освобождаем место
+        }
+
+        self.put(self.tail, value); // Записываем значение
+        count_moves(1);
+        self.tail = self.wrap(self.tail + 1); // Перемещаем хвост с закольцовыванием
+        self.size += 1; // Увеличиваем размер
+        if let Some(stats) = &mut self.stats {
+            stats.record_push(self.size);
+            if self.tail == 0 {
+                stats.laps += 1; // Хвост прошёл через конец массива
+            }
+        }
+        if let Some(advisor) = &mut self.advisor {
+            advisor.record(self.size);
+        }
+        self.debug_validate();
+        Ok(()) // Возвращаем успешный результат
+    }
+
+    // 5.0.1. Авторасширение заполненного буфера до рекомендуемой ёмкости;
+    // true, если место появилось
+    #[cold]
+    fn auto_grow(&mut self) -> bool {
+        match self.advisor.as_ref().and_then(|advisor| advisor.grow_to()) {
+            Some(capacity) => {
+                self.resize(capacity);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // 5.1. Запись с вытеснением самого старого элемента, если буфер полон
+    pub fn push_overwrite(&mut self, value: T) -> Option<T> {
+        let evicted = if self.is_full() { self.evict() } else { None }; // Освобождаем место
+        let _ = self.push_one(value); // После вытеснения место точно есть
+        #[cfg(feature = "audit")]
+        self.audit(AuditOp::Push, 1);
+        evicted // Возвращаем вытесненный элемент
+    }
+
+    // 5.2. Запись значения, которое строит замыкание; паника в замыкании
+    // происходит до изменения буфера, поэтому он остаётся прежним
+    pub fn push_with(&mut self, make: impl FnOnce() -> T) -> Result<(), String> {
+        if self.is_full() && self.policy == OverflowPolicy::Reject {
+            return Err(full_error()); // Не строим значение зря
+        }
+        self.push(make())
+    }
+
+    // 5.3. Транзакционная запись: либо все элементы, либо ни одного.
+    // Вытеснение здесь не применяется - при нехватке места запись откатывается,
+    // так же как и при панике внутри итератора
+    pub fn push_all(&mut self, items: impl IntoIterator<Item = T>) -> Result<usize, String> {
+        let start = self.size;
+        let start_tail = self.tail;
+        let mut tx = Rollback {
+            ring: self,
+            pushed: 0,
+        };
+        for value in items {
+            if tx.ring.is_full() {
+                return Err(full_error()); // tx откатит уже записанное
+            }
+            tx.ring.write_back(value);
+            tx.pushed += 1;
+        }
+        let pushed = core::mem::take(&mut tx.pushed); // Фиксируем: откатывать нечего
+        drop(tx);
+
+        self.record_bulk_push(start, start_tail, pushed); // Учитываем только зафиксированные записи
+        #[cfg(feature = "audit")]
+        self.audit(AuditOp::PushAll, pushed);
+        Ok(pushed)
+    }
+
+    // 5.3.0. Запись из итератора; возвращает, сколько элементов принято.
+    // Если длина известна точно (ExactSizeIterator и подобные), помещающаяся часть
+    // пишется подряд без проверок на каждом элементе, остальное - по одному через push
+    pub fn extend_from_iter(&mut self, items: impl IntoIterator<Item = T>) -> usize {
+        let mut iter = items.into_iter();
+        let (start, start_tail) = (self.size, self.tail);
+        let mut count = 0;
+        if let (lower, Some(upper)) = iter.size_hint() {
+            if lower == upper {
+                for value in iter.by_ref().take(lower.min(self.capacity - self.size)) {
+                    self.write_back(value);
+                    count += 1;
+                }
+                self.record_bulk_push(start, start_tail, count);
+            }
+        }
+
+        // Длина неизвестна или не всё поместилось - действует политика переполнения
+        for value in iter {
+            if self.push_one(value).is_err() {
+                break;
+            }
+            count += 1;
+        }
+        #[cfg(feature = "audit")]
+        self.audit(AuditOp::Extend, count);
+        count
+    }
+
+    // 5.3.0.1. Учёт pushed записей подряд, начатых при размере start и хвосте start_tail
+    fn record_bulk_push(&mut self, start: usize, start_tail: usize, pushed: usize) {
+        if let Some(stats) = &mut self.stats {
+            for size in start + 1..=start + pushed {
+                stats.record_push(size);
+            }
+            stats.laps += ((start_tail + pushed) / self.capacity) as u64;
+        }
+        if let Some(advisor) = &mut self.advisor {
+            for size in start + 1..=start + pushed {
+                advisor.record(size);
+            }
+        }
+    }
+
+    // 5.3.1. Дозаполнение до полного буфера значениями из замыкания
+    // (например, тишиной для линии задержки); возвращает число записанных
+    pub fn fill_with(&mut self, mut make: impl FnMut() -> T) -> usize {
+        self.fill_while(|| Some(make()))
+    }
+
+    // 5.3.2. То же, но с досрочной остановкой, когда замыкание вернёт None
+    pub fn fill_while(&mut self, mut next: impl FnMut() -> Option<T>) -> usize {
+        let mut count = 0;
+        while !self.is_full() {
+            let Some(value) = next() else {
+                break; // Источник закончился
+            };
+            let _ = self.push_one(value); // Место есть, ошибки не будет
+            count += 1;
+        }
+        #[cfg(feature = "audit")]
+        self.audit(AuditOp::Fill, count);
+        count
+    }
+
+    // 5.3.3. Запись в хвост без учёта в счётчиках (место должно быть)
+    #[inline]
+    fn write_back(&mut self, value: T) {
+        self.put(self.tail, value);
+        count_moves(1);
+        self.tail = self.wrap(self.tail + 1);
+        self.size += 1;
+        self.debug_validate();
+    }
+
+    // 5.4. Вытеснение самого старого элемента при переполнении
+    fn evict(&mut self) -> Option<T> {
+        let value = self.take_front()?;
+        if let Some(stats) = &mut self.stats {
+            stats.overwritten += 1;
+        }
+        Some(value)
+    }
+
+    // 6. Чтение элемента
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        let value = self.pop_one()?;
+        #[cfg(feature = "audit")]
+        self.audit(AuditOp::Pop, 1);
+        Some(value)
+    }
+
+    // 6.0. Чтение без учёта в журнале (для составных операций)
+    #[inline]
+    fn pop_one(&mut self) -> Option<T> {
+        let value = self.take_front()?; // Возвращаем None если пуст
+        if let Some(stats) = &mut self.stats {
+            stats.pops += 1;
+        }
+        Some(value) // Возвращаем значение
+    }
+
+    // 6.0.1. Извлечение из головы без учёта в счётчиках
+    #[inline]
+    fn take_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None; // Возвращаем None если пуст
+        }
+
+        let value = self.take_slot(self.head); // Забираем значение из головы
+        count_moves(1);
+        self.head = self.wrap(self.head + 1); // Перемещаем голову
+        self.size -= 1; // Уменьшаем размер
+        self.popped += 1;
+        self.debug_validate();
+        value // Возвращаем значение
+    }
+
+    // 6.1. Чтение самого нового элемента (с хвоста)
+    pub fn pop_back(&mut self) -> Option<T> {
+        let value = self.pop_back_one()?;
+        #[cfg(feature = "audit")]
+        self.audit(AuditOp::PopBack, 1);
+        Some(value)
+    }
+
+    fn pop_back_one(&mut self) -> Option<T> {
+        let value = self.take_back()?; // Возвращаем None если пуст
+        if let Some(stats) = &mut self.stats {
+            stats.pops += 1;
+        }
+        Some(value)
+    }
+
+    // 6.1.1. Извлечение из хвоста без учёта в счётчиках
+    fn take_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None; // Возвращаем None если пуст
+        }
+
+        self.tail = self.wrap(self.tail + self.capacity - 1); // Отступаем хвостом назад
+        self.size -= 1; // Уменьшаем размер
+        self.generation += 1; // Смещение хвоста достанется следующей записи
+        let value = self.take_slot(self.tail); // Забираем значение из хвоста
+        count_moves(1);
+        self.debug_validate();
+        value
+    }
+
+    // 6.2. Доступ к элементу по логическому индексу (0 - самый старый)
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.size {
+            return None; // За пределами содержимого
+        }
+
+        self.slot(self.wrap(self.head + index))
+    }
+
+    // 6.3. Изменяемый доступ к элементу по логическому индексу
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.size {
+            return None; // За пределами содержимого
+        }
+
+        let slot = self.wrap(self.head + index);
+        self.slot_mut(slot)
+    }
+
+    // 6.3.1. Запись с дескриптором: смещение элемента от начала времён и поколение.
+    // Дескриптор перестаёт работать, когда элемент прочитан или вытеснен, а также
+    // после любого удаления с хвоста или из середины (смещения сдвигаются)
+    pub fn push_indexed(&mut self, value: T) -> Result<Handle, String> {
+        self.push(value)?;
+        Ok(Handle {
+            offset: self.popped + self.size as u64 - 1,
+            generation: self.generation,
+        })
+    }
+
+    // 6.3.2. Элемент по дескриптору; None, если его уже нет
+    pub fn get_by_handle(&self, handle: Handle) -> Option<&T> {
+        self.get(self.handle_index(handle)?)
+    }
+
+    pub fn get_mut_by_handle(&mut self, handle: Handle) -> Option<&mut T> {
+        self.get_mut(self.handle_index(handle)?)
+    }
+
+    // 6.3.3. Логический индекс элемента дескриптора (get проверит, что он не прочитан)
+    fn handle_index(&self, handle: Handle) -> Option<usize> {
+        if handle.generation != self.generation {
+            return None; // Смещения с тех пор сдвигались
+        }
+        usize::try_from(handle.offset.checked_sub(self.popped)?).ok()
+    }
+
+    // 6.3.4. Вставка по логическому индексу со сдвигом более новых к хвосту;
+    // заполненный буфер возвращает ошибку при любой политике (вытеснять некого)
+    pub fn insert(&mut self, index: usize, value: T) -> Result<(), String> {
+        assert!(index <= self.size, "Индекс за пределами содержимого!");
+        if self.is_full() {
+            return Err(full_error());
+        }
+
+        for i in (index..self.size).rev() {
+            // Сдвигаем каждый элемент от хвоста на одну ячейку вперёд
+            let (from, to) = (self.wrap(self.head + i), self.wrap(self.head + i + 1));
+            self.move_slot(from, to);
+        }
+        self.put(self.wrap(self.head + index), value);
+        count_moves(self.size - index + 1);
+        self.tail = self.wrap(self.tail + 1);
+        self.size += 1;
+        self.generation += 1; // Более новые элементы сдвинулись
+        if let Some(stats) = &mut self.stats {
+            stats.record_push(self.size);
+            if self.tail == 0 {
+                stats.laps += 1;
+            }
+        }
+        if let Some(advisor) = &mut self.advisor {
+            advisor.record(self.size);
+        }
+        self.debug_validate();
+        #[cfg(feature = "audit")]
+        self.audit(AuditOp::Insert, 1);
+        Ok(())
+    }
+
+    // 6.4. Удаление элемента по логическому индексу со сдвигом более новых
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.size {
+            return None; // За пределами содержимого
+        }
+
+        let slot = self.wrap(self.head + index);
+        let value = self.take_slot(slot);
+        for i in index..self.size - 1 {
+            // Сдвигаем каждый следующий элемент на освободившееся место
+            let (to, from) = (self.wrap(self.head + i), self.wrap(self.head + i + 1));
+            self.move_slot(from, to);
+        }
+        count_moves(self.size - index);
+        self.tail = self.wrap(self.tail + self.capacity - 1); // Хвост отступает назад
+        self.size -= 1;
+        self.generation += 1; // Более новые элементы сдвинулись
+        if let Some(stats) = &mut self.stats {
+            stats.pops += 1;
+        }
+        self.debug_validate();
+        #[cfg(feature = "audit")]
+        self.audit(AuditOp::Remove, 1);
+        value
+    }
+
+    // 6.4.1. Оставляем только элементы, для которых keep вернул true (порядок сохраняется).
+    // Если keep паникует, необработанные элементы остаются в буфере
+    pub fn retain(&mut self, mut keep: impl FnMut(&T) -> bool) {
+        self.retain_mut(|value| keep(value));
+    }
+
+    // 6.4.2. То же, но keep может изменить оставляемые элементы на месте
+    pub fn retain_mut(&mut self, mut keep: impl FnMut(&mut T) -> bool) {
+        let len = self.size;
+        let mut compact = Compact {
+            ring: self,
+            read: 0,
+            write: 0,
+            len,
+        };
+
+        while compact.read < compact.len {
+            let ring = &mut *compact.ring;
+            let from = ring.wrap(ring.head + compact.read);
+            let kept = ring.slot_mut(from).is_some_and(&mut keep);
+
+            let value = ring.take_slot(from);
+            count_moves(1);
+            compact.read += 1; // Элемент обработан до возможной паники в его drop
+            if kept {
+                let to = ring.wrap(ring.head + compact.write);
+                if let Some(value) = value {
+                    ring.put(to, value);
+                }
+                compact.write += 1;
+            } else {
+                if let Some(stats) = &mut ring.stats {
+                    stats.pops += 1;
+                }
+                drop(value);
+            }
+        }
+        drop(compact); // Уплотняем до записи в журнал
+        #[cfg(feature = "audit")]
+        self.audit(AuditOp::Retain, len - self.size);
+    }
+
+    // 6.4.3. Изменение всех элементов на месте, от старых к новым
+    pub fn for_each_mut(&mut self, mut f: impl FnMut(&mut T)) {
+        for index in 0..self.size {
+            let slot = self.wrap(self.head + index);
+            if let Some(value) = self.slot_mut(slot) {
+                f(value);
+            }
+        }
+    }
+
+    // 6.5. Обход элементов от самого старого к самому новому
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        (0..self.size).filter_map(move |index| self.get(index))
+    }
+
+    // 6.5.1. Перекрывающиеся окна длины n от старых элементов к новым (как slice::windows).
+    // Окно - лёгкое представление без копирования, шов массива ему не мешает
+    pub fn windows(&self, n: usize) -> impl Iterator<Item = Window<'_, T>> + '_ {
+        assert!(n > 0, "Окно не может быть пустым!");
+
+        (0..(self.size + 1).saturating_sub(n)).map(move |start| Window {
+            ring: self,
+            start,
+            len: n,
+        })
+    }
+
+    // 6.9. Закольцовывание индекса меньше 2 * capacity: вычитание вместо деления,
+    // компилятор превращает его в условную пересылку без ветвления
+    #[inline]
+    fn wrap(&self, index: usize) -> usize {
+        debug_assert!(index < 2 * self.capacity);
+        if index >= self.capacity {
+            index - self.capacity
+        } else {
+            index
+        }
+    }
+
+    // 6.9.1. Ячейки хранилища. Тип нулевого размера хранить не нужно: хранилище
+    // пустое, запись забывает значение, а извлечение создаёт его заново
+    const ZST: bool = core::mem::size_of::<T>() == 0;
+
+    fn slots(capacity: usize) -> Vec<Option<T>> {
+        if Self::ZST {
+            return Vec::new(); // Без выделения памяти
+        }
+        (0..capacity).map(|_| None).collect()
+    }
+
+    #[inline]
+    fn put(&mut self, slot: usize, value: T) {
+        if Self::ZST {
+            core::mem::forget(value); // Значение "хранится" в счётчике размера
+        } else {
+            self.buffer[slot] = Some(value);
+        }
+    }
+
+    // 6.9.2. Извлечение из ячейки; для типа нулевого размера ячейка должна быть занята
+    #[inline]
+    fn take_slot(&mut self, slot: usize) -> Option<T> {
+        if Self::ZST {
+            // SAFETY: у типа нулевого размера нет байт, а ранее записанное значение
+            // было забыто в put - создаём его заново ровно один раз
+            return Some(unsafe { core::ptr::NonNull::<T>::dangling().as_ptr().read() });
+        }
+        self.buffer[slot].take()
+    }
+
+    #[inline]
+    fn slot(&self, slot: usize) -> Option<&T> {
+        if Self::ZST {
+            // SAFETY: ссылка на тип нулевого размера не обращается к памяти
+            return Some(unsafe { core::ptr::NonNull::<T>::dangling().as_ref() });
+        }
+        self.buffer[slot].as_ref()
+    }
+
+    #[inline]
+    fn slot_mut(&mut self, slot: usize) -> Option<&mut T> {
+        if Self::ZST {
+            // SAFETY: см. slot
+            return Some(unsafe { core::ptr::NonNull::<T>::dangling().as_mut() });
+        }
+        self.buffer[slot].as_mut()
+    }
+
+    fn move_slot(&mut self, from: usize, to: usize) {
+        if !Self::ZST {
+            self.buffer[to] = self.buffer[from].take(); // Нулевой размер: переносить нечего
+        }
+    }
+
+    // 6.9.3. Занята ли ячейка: для типа нулевого размера - по голове и размеру
+    fn occupied(&self, slot: usize) -> bool {
+        if Self::ZST {
+            return (slot + self.capacity - self.head) % self.capacity < self.size;
+        }
+        self.buffer[slot].is_some()
+    }
+
+    // 6.9.4. Уничтожение всех элементов без записи в журнал
+    fn drop_all(&mut self) {
+        if Self::ZST {
+            for _ in 0..self.size {
+                drop(self.take_slot(0));
+            }
+        } else {
+            for slot in self.buffer.iter_mut() {
+                *slot = None;
+            }
+        }
+        self.head = 0;
+        self.tail = 0;
+        self.size = 0;
+    }
+
+    // 7.1. Полная проверка внутренних инвариантов (для тестов и отладки)
+    pub fn validate(&self) -> Result<(), String> {
+        let slots = if Self::ZST { 0 } else { self.capacity };
+        if self.size > self.capacity || self.buffer.len() != slots {
+            return Err("Размер больше ёмкости!".to_string());
+        }
+        if self.head >= self.capacity || self.tail >= self.capacity {
+            return Err("Индекс за пределами хранилища!".to_string());
+        }
+        if (self.head + self.size) % self.capacity != self.tail {
+            return Err("Голова, хвост и размер не согласованы!".to_string());
+        }
+
+        // Заняты ровно ячейки от головы до хвоста
+        for offset in 0..self.capacity {
+            let occupied = self.occupied((self.head + offset) % self.capacity);
+            if occupied != (offset < self.size) {
+                return Err("Занятость ячеек не совпадает с размером!".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    // 7.1.1. Физическое расположение содержимого (для обучения и поиска ошибок на шве)
+    pub fn layout(&self) -> Layout {
+        Layout {
+            head: self.head,
+            tail: self.tail,
+            len: self.size,
+            capacity: self.capacity,
+            wrap: (self.head + self.size > self.capacity).then(|| self.capacity - self.head),
+            #[cfg(debug_assertions)]
+            occupied: (0..self.capacity).map(|slot| self.occupied(slot)).collect(),
+        }
+    }
+
+    // 7.1.4. Состояние картинкой, например [..H###T.] 3/7
+    pub fn visualize(&self) -> String {
+        self.layout().visualize()
+    }
+
+    // 7.2. Быстрая проверка после каждого изменения в отладочной сборке:
+    // только O(1) условия, полный обход ячеек остаётся за validate()
+    #[inline]
+    fn debug_validate(&self) {
+        debug_assert!(self.size <= self.capacity, "Размер больше ёмкости!");
+        debug_assert_eq!(
+            (self.head + self.size) % self.capacity,
+            self.tail,
+            "Голова, хвост и размер не согласованы!"
+        );
+        debug_assert!(
+            self.size == self.capacity || !self.occupied(self.tail),
+            "Ячейка под хвостом занята!"
+        );
+        debug_assert!(
+            self.size == 0 || self.occupied(self.head),
+            "Ячейка под головой пуста!"
+        );
+    }
+
+    // 8. Чтение нескольких элементов
+    pub fn drain(&mut self, count: usize) -> Vec<T> {
+        let mut result = Vec::new();
+        for _ in 0..count {
+            match self.pop_one() {
+                // Пытаемся извлечь элемент
+                Some(byte) => result.push(byte), // Добавляем в результат
+                None => break,                   // Прерываем если буфер пуст
+            }
+        }
+        #[cfg(feature = "audit")]
+        self.audit(AuditOp::Drain, result.len());
+        result // Возвращаем прочитанные байты
+    }
+
+    // 8.0.1. Чтение до count элементов в замыкание без промежуточного Vec;
+    // ControlFlow::Break останавливает чтение (элемент, вернувший Break, уже прочитан).
+    // Возвращает, сколько элементов передано замыканию
+    pub fn pop_each(&mut self, count: usize, mut f: impl FnMut(T) -> ControlFlow<()>) -> usize {
+        let mut popped = 0;
+        while popped < count {
+            let Some(value) = self.pop_one() else {
+                break; // Буфер пуст
+            };
+            popped += 1;
+            if f(value).is_break() {
+                break;
+            }
+        }
+        #[cfg(feature = "audit")]
+        self.audit(AuditOp::Drain, popped);
+        popped
+    }
+
+    // 8.1. Пропуск count самых старых элементов без возврата (они уничтожаются)
+    pub fn skip(&mut self, count: usize) -> usize {
+        let mut skipped = 0;
+        while skipped < count && self.pop_one().is_some() {
+            skipped += 1;
+        }
+        #[cfg(feature = "audit")]
+        self.audit(AuditOp::Skip, skipped);
+        skipped // Сколько реально пропущено
+    }
+
+    // 8.2. Оставляем не больше n самых новых элементов (старые уничтожаются)
+    pub fn truncate_front(&mut self, n: usize) {
+        #[cfg(feature = "audit")]
+        let removed = self.size.saturating_sub(n);
+        while self.size > n {
+            self.pop_one();
+        }
+        #[cfg(feature = "audit")]
+        self.audit(AuditOp::Truncate, removed);
+    }
+
+    // 8.3. Оставляем не больше n самых старых элементов (новые уничтожаются), как Vec::truncate
+    pub fn truncate_back(&mut self, n: usize) {
+        #[cfg(feature = "audit")]
+        let removed = self.size.saturating_sub(n);
+        while self.size > n {
+            self.pop_back_one();
+        }
+        #[cfg(feature = "audit")]
+        self.audit(AuditOp::Truncate, removed);
+    }
+
+    // 9. Очистка буфера (все элементы уничтожаются)
+    pub fn clear(&mut self) {
+        #[cfg(feature = "audit")]
+        let removed = self.size;
+        self.popped += self.size as u64;
+        self.drop_all();
+        self.debug_validate();
+        #[cfg(feature = "audit")]
+        self.audit(AuditOp::Clear, removed);
+    }
+
+    // 9.1. Обмен содержимым с другим буфером (любой ёмкости) за O(1)
+    pub fn swap(&mut self, other: &mut Self) {
+        core::mem::swap(self, other);
+    }
+
+    // 10. Смена ёмкости; если элементов больше новой ёмкости, самые старые отбрасываются
+    pub fn resize(&mut self, new_capacity: usize) {
+        assert!(new_capacity > 0, "В буфере есть место!");
+
+        #[cfg(feature = "audit")]
+        let dropped = self.size.saturating_sub(new_capacity);
+        while self.size > new_capacity {
+            self.take_front(); // Отбрасываем лишние старые элементы
+        }
+
+        let len = self.size;
+        let mut buffer = Self::slots(new_capacity);
+        for slot in buffer.iter_mut().take(len) {
+            *slot = self.take_front(); // Переносим элементы в начало нового хранилища
+        }
+        count_moves(buffer.len().min(len)); // Записи в новое хранилище
+        self.popped -= buffer.len().min(len) as u64; // Перенос - не извлечение
+
+        self.buffer = buffer;
+        self.capacity = new_capacity;
+        self.head = 0;
+        self.tail = len % new_capacity;
+        self.size = len;
+        if let Some(advisor) = &mut self.advisor {
+            advisor.reset(new_capacity);
+        }
+        self.debug_validate();
+        #[cfg(feature = "audit")]
+        self.audit(AuditOp::Resize, dropped);
+    }
+
+    // 10.1. Освобождение памяти после всплеска: хранилище перевыделяется под
+    // max(len, capacity) ячеек (но не меньше одной); ёмкость только уменьшается,
+    // содержимое и порядок сохраняются
+    pub fn shrink_to(&mut self, capacity: usize) {
+        let target = capacity.max(self.size).max(1);
+        if target < self.capacity {
+            self.resize(target);
+        }
+    }
+
+    // 10.2. Ёмкость ровно под текущее содержимое
+    pub fn shrink_to_fit(&mut self) {
+        self.shrink_to(0);
+    }
+}
+
+// 6.10. Ошибка переполнения - редкий путь: не мешаем компилятору оптимизировать горячий
+#[cold]
+#[inline(never)]
+pub(crate) fn full_error() -> String {
+    "Буфер Заполнен!".to_string()
+}
+
+// 6.5.2. Окно из len подряд идущих элементов, начиная с логического индекса start
+#[derive(Debug)]
+pub struct Window<'a, T> {
+    ring: &'a RingBuffer<T>,
+    start: usize,
+    len: usize,
+}
+
+impl<'a, T> Window<'a, T> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Элемент окна по индексу внутри окна
+    pub fn get(&self, index: usize) -> Option<&'a T> {
+        if index >= self.len {
+            return None;
+        }
+        self.ring.get(self.start + index)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + '_ {
+        (0..self.len).filter_map(move |index| self.get(index))
+    }
+}
+
+// 6.5.3. Дескриптор записанного элемента (см. push_indexed)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Handle {
+    offset: u64,
+    generation: u64,
+}
+
+impl Handle {
+    // Смещение элемента от начала времён (как у EventRing)
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+}
+
+// 7.1.2. Снимок расположения; Display рисует ячейки: # - занята, . - свободна
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layout {
+    pub head: usize,         // Физический индекс самого старого элемента
+    pub tail: usize,         // Физический индекс следующей записи
+    pub len: usize,          // Количество элементов
+    pub capacity: usize,     // Количество ячеек
+    pub wrap: Option<usize>, // Логический индекс, с которого содержимое продолжается с нуля
+    #[cfg(debug_assertions)]
+    pub occupied: Vec<bool>, // Фактическая занятость ячеек (только в отладочной сборке)
+}
+
+impl Layout {
+    // Занятость ячейки: в отладке - фактическая, иначе - по голове и размеру
+    fn is_occupied(&self, index: usize) -> bool {
+        #[cfg(debug_assertions)]
+        {
+            self.occupied[index]
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            (index + self.capacity - self.head) % self.capacity < self.len
+        }
+    }
+}
+
+impl Layout {
+    // 7.1.3. Ячейки с метками: H - перед головой, T - перед хвостом, в конце -
+    // заполнение. Пустой и полный буфер различаются по ячейкам: [HT....] и [HT####]
+    pub fn visualize(&self) -> String {
+        let mut out = String::with_capacity(self.capacity + 16);
+        out.push('[');
+        for index in 0..self.capacity {
+            if index == self.head {
+                out.push('H');
+            }
+            if index == self.tail {
+                out.push('T');
+            }
+            out.push(if self.is_occupied(index) { '#' } else { '.' });
+        }
+        out.push_str("] ");
+        out.push_str(&self.len.to_string());
+        out.push('/');
+        out.push_str(&self.capacity.to_string());
+        out
+    }
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[")?;
+        for index in 0..self.capacity {
+            write!(f, "{}", if self.is_occupied(index) { '#' } else { '.' })?;
+        }
+        write!(f, "]")
+    }
+}
+
+// 6.6. Откат незафиксированных записей push_all (в том числе при панике)
+struct Rollback<'a, T> {
+    ring: &'a mut RingBuffer<T>,
+    pushed: usize,
+}
+
+impl<T> Drop for Rollback<'_, T> {
+    fn drop(&mut self) {
+        for _ in 0..self.pushed {
+            self.ring.take_back();
+        }
+    }
+}
+
+// 6.7. Уплотнение после retain: необработанные элементы сдвигаются вслед
+// за оставленными, даже если keep запаниковал на середине
+struct Compact<'a, T> {
+    ring: &'a mut RingBuffer<T>,
+    read: usize,  // Сколько элементов уже обработано
+    write: usize, // Сколько элементов оставлено
+    len: usize,   // Размер до начала retain
+}
+
+impl<T> Drop for Compact<'_, T> {
+    fn drop(&mut self) {
+        let ring = &mut *self.ring;
+        for read in self.read..self.len {
+            let (from, to) = (
+                ring.wrap(ring.head + read),
+                ring.wrap(ring.head + self.write),
+            );
+            ring.move_slot(from, to);
+            self.write += 1;
+        }
+        if self.write < self.len {
+            ring.generation += 1; // Оставленные элементы сдвинулись
+        }
+        ring.size = self.write;
+        ring.tail = ring.wrap(ring.head + ring.size);
+        ring.debug_validate();
+    }
+}
+
+// 2.3. Буфер на DEFAULT_BYTES байт, но не меньше чем на один элемент
+impl<T> Default for RingBuffer<T> {
+    fn default() -> Self {
+        Self::new(Self::capacity_for_bytes(DEFAULT_BYTES).max(1))
+    }
+}
+
+// 6.7.1. Элементы нулевого размера не лежат в хранилище - уничтожаем их сами
+impl<T> Drop for RingBuffer<T> {
+    fn drop(&mut self) {
+        if Self::ZST && core::mem::needs_drop::<T>() {
+            self.drop_all();
+        }
+    }
+}
+
+// 6.8. Копирование; clone_from переиспользует хранилище получателя
+impl<T: Clone> Clone for RingBuffer<T> {
+    fn clone(&self) -> Self {
+        self.clone_zst();
+        RingBuffer {
+            buffer: self.buffer.clone(),
+            capacity: self.capacity,
+            head: self.head,
+            tail: self.tail,
+            size: self.size,
+            popped: self.popped,
+            generation: self.generation,
+            policy: self.policy,
+            stats: self.stats,
+            advisor: self.advisor.clone(),
+            #[cfg(feature = "audit")]
+            audit: self.audit.clone(),
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        if Self::ZST {
+            self.drop_all(); // Прежние элементы не лежат в хранилище
+            source.clone_zst();
+        }
+        self.buffer.clone_from(&source.buffer); // Без выделения памяти, если места хватает
+        self.capacity = source.capacity;
+        self.head = source.head;
+        self.tail = source.tail;
+        self.size = source.size;
+        self.popped = source.popped;
+        self.generation = source.generation;
+        self.policy = source.policy;
+        self.stats = source.stats;
+        self.advisor.clone_from(&source.advisor);
+        #[cfg(feature = "audit")]
+        self.audit.clone_from(&source.audit);
+        self.debug_validate();
+    }
+}
+
+impl<T: Clone> RingBuffer<T> {
+    // 6.8.1. Копии элементов нулевого размера: clone вызывается для каждого,
+    // а сами копии забываются - их "хранит" размер копии буфера
+    fn clone_zst(&self) {
+        if Self::ZST {
+            for value in self.iter() {
+                core::mem::forget(value.clone());
+            }
+        }
+    }
+
+    // 7. Запись нескольких элементов
+    pub fn extend(&mut self, data: &[T]) -> usize {
+        let mut count = 0;
+        for byte in data {
+            if self.push_one(byte.clone()).is_err() {
+                // Пытаемся добавить каждый байт
+                break; // Прерываем если буфер полон
+            }
+            count += 1; // Считаем успешно добавленные
+        }
+        #[cfg(feature = "audit")]
+        self.audit(AuditOp::Extend, count);
+        count // Возвращаем количество добавленных
+    }
+
+    // 7.1. То же с подробным результатом: сколько осталось и почему остановились
+    pub fn extend_partial(&mut self, data: &[T]) -> PartialWrite {
+        PartialWrite::new(self.extend(data), data.len())
+    }
+}
+
+//  Модуль тестирования
+#[cfg(test)]
+mod tests {
+    use super::RingBuffer;
+
+    use super::MOVES;
+    use crate::OverflowPolicy;
+
+    // Сколько перемещений элементов сделала f
+    fn moves_of(f: impl FnOnce()) -> usize {
+        let before = MOVES.with(|moves| moves.get());
+        f();
+        MOVES.with(|moves| moves.get()) - before
+    }
+
+    #[test]
+    fn test_push_pop_move_once() {
+        let mut rb = RingBuffer::new(8);
+        for i in 0..100 {
+            assert_eq!(moves_of(|| rb.push(i).unwrap()), 1);
+            if i % 3 != 0 {
+                assert_eq!(moves_of(|| assert!(rb.pop().is_some())), 1);
+            }
+            if rb.is_full() {
+                assert_eq!(moves_of(|| assert!(rb.pop_back().is_some())), 1);
+            }
+        }
+
+        let mut rb = RingBuffer::builder()
+            .capacity(4)
+            .policy(OverflowPolicy::Overwrite)
+            .build();
+        for i in 0..100 {
+            assert!(moves_of(|| rb.push(i).unwrap()) <= 2); // С вытеснением - два
+        }
+    }
+
+    #[cfg(not(feature = "visualize"))] // Печать всего буфера после каждой записи - O(n^2)
+    #[test]
+    fn test_amortized_push_across_growth() {
+        let n = 100_000;
+        let mut rb = RingBuffer::builder().capacity(1).auto_grow(n).build();
+        let moves = moves_of(|| {
+            for i in 0..n {
+                rb.push(i).unwrap();
+            }
+        });
+        assert_eq!(rb.capacity(), n);
+        // Запись - одно перемещение, перенос - два (извлечение и запись); при росте
+        // на четверть переносится не больше 4n элементов, плюс округления малых ёмкостей
+        assert!(moves <= 12 * n, "{} перемещений на {} записей", moves, n);
+
+        let moves = moves_of(|| while rb.pop().is_some() {});
+        assert_eq!(moves, n);
+    }
+
+    #[test]
+    fn test_creation() {
+        let rb: RingBuffer<u8> = RingBuffer::new(5);
+        assert_eq!(rb.capacity, 5);
+        assert!(rb.is_empty());
+        assert_eq!(rb.len(), 0);
+    }
+
+    #[test]
+    fn test_layout() {
+        let mut rb = RingBuffer::new(7);
+        assert_eq!(rb.layout().to_string(), "[.......]");
+
+        rb.extend(&[1, 2, 3, 4, 5]);
+        rb.skip(2);
+        let layout = rb.layout();
+        assert_eq!((layout.head, layout.tail, layout.wrap), (2, 5, None));
+        assert_eq!(layout.to_string(), "[..###..]");
+
+        rb.extend(&[6, 7, 8]); // Содержимое проходит через шов
+        let layout = rb.layout();
+        assert_eq!((layout.head, layout.tail, layout.len), (2, 1, 6));
+        assert_eq!(layout.wrap, Some(5)); // Элемент 8 лежит в ячейке 0
+        assert_eq!(layout.to_string(), "[#.#####]");
+        assert_eq!(rb.visualize(), "[#T.H#####] 6/7");
+
+        rb.extend(&[9]);
+        assert_eq!(rb.visualize(), "[##HT#####] 7/7"); // Полный: голова и хвост вместе
+        rb.clear();
+        assert_eq!(rb.visualize(), "[HT.......] 0/7");
+    }
+
+    #[test]
+    fn test_extend_from_iter() {
+        let mut rb = RingBuffer::builder().capacity(4).with_stats().build();
+        assert_eq!(rb.extend_from_iter(0..3), 3); // Точная длина - быстрый путь
+        assert_eq!(rb.extend_from_iter((3..10).filter(|_| true)), 1); // Длина неизвестна
+        assert_eq!(rb.drain(4), [0, 1, 2, 3]);
+
+        rb.extend(&[7, 7]);
+        rb.skip(2);
+        assert_eq!(rb.extend_from_iter(vec![4, 5, 6, 7, 8]), 4); // Через шов
+        assert_eq!(rb.drain(4), [4, 5, 6, 7]);
+        assert_eq!(rb.stats().map(|s| (s.pushes, s.laps)), Some((10, 2)));
+        rb.validate().unwrap();
+
+        let mut rb = RingBuffer::builder()
+            .capacity(2)
+            .policy(crate::OverflowPolicy::Overwrite)
+            .build();
+        assert_eq!(rb.extend_from_iter([1, 2, 3]), 3); // Лишнее вытесняет старое
+        assert_eq!(rb.drain(2), [2, 3]);
+    }
+
+    #[test]
+    fn test_basic_operations() {
+        let mut rb = RingBuffer::new(3);
+
+        // Тест записи
+        assert!(rb.push(1).is_ok());
+        assert!(rb.push(2).is_ok());
+        assert_eq!(rb.len(), 2);
+        assert!(!rb.is_empty());
+        assert!(!rb.is_full());
+
+        // Тест чтения
+        assert_eq!(rb.pop(), Some(1));
+        assert_eq!(rb.pop(), Some(2));
+        assert_eq!(rb.pop(), None);
+        assert!(rb.is_empty());
+    }
+
+    #[test]
+    fn test_capacity_from_bytes() {
+        assert_eq!(RingBuffer::<u64>::with_capacity_bytes(1024).capacity(), 64); // Option<u64> - 16 байт
+        assert_eq!(RingBuffer::<u32>::capacity_for_bytes(100), 12);
+        assert_eq!(RingBuffer::<Box<u8>>::capacity_for_bytes(64), 8); // None без отдельного флага
+
+        let rb: RingBuffer<u8> = RingBuffer::default();
+        assert_eq!(rb.capacity(), super::DEFAULT_BYTES / 2);
+        let big: RingBuffer<[u8; 8192]> = RingBuffer::default();
+        assert_eq!(big.capacity(), 1); // Элемент больше бюджета
+    }
+
+    #[test]
+    fn test_heap_bytes() {
+        use crate::CapacityAdvisor;
+        use core::mem::size_of;
+
+        let plain = RingBuffer::<u64>::new(8);
+        assert_eq!(plain.heap_bytes(), 8 * 16);
+        let with_stats = RingBuffer::<u64>::builder()
+            .capacity(8)
+            .with_stats()
+            .build();
+        assert_eq!(with_stats.heap_bytes(), 8 * 16); // Счётчики не в куче
+
+        let advised = RingBuffer::<u64>::builder()
+            .capacity(8)
+            .with_advisor()
+            .build();
+        assert_eq!(advised.heap_bytes(), 8 * 16 + size_of::<CapacityAdvisor>());
+        assert_eq!(RingBuffer::<()>::new(100).heap_bytes(), 0);
+
+        #[cfg(feature = "audit")]
+        {
+            use crate::{AuditEntry, AuditLog};
+            let audited = RingBuffer::<u64>::builder()
+                .capacity(8)
+                .with_audit(4)
+                .build();
+            let log = size_of::<AuditLog>() + 4 * size_of::<Option<AuditEntry>>();
+            assert_eq!(audited.heap_bytes(), 8 * 16 + log);
+        }
+    }
+
+    #[test]
+    fn test_shrink() {
+        let mut rb = RingBuffer::new(64);
+        for i in 0..64 {
+            rb.push(i).unwrap();
+        }
+        rb.skip(60); // После всплеска осталось 4 элемента, новый ляжет через шов
+        rb.push(64).unwrap();
+        let before = rb.heap_bytes();
+
+        rb.shrink_to(10);
+        assert_eq!(rb.capacity(), 10);
+        rb.shrink_to(20); // Не растёт
+        assert_eq!(rb.capacity(), 10);
+
+        rb.shrink_to_fit();
+        assert_eq!(rb.capacity(), 5);
+        assert!(rb.heap_bytes() < before / 10);
+        assert_eq!(rb.iter().copied().collect::<Vec<_>>(), [60, 61, 62, 63, 64]);
+        assert_eq!(rb.validate(), Ok(()));
+
+        rb.clear();
+        rb.shrink_to_fit(); // Пустой буфер - одна ячейка
+        assert_eq!(rb.capacity(), 1);
+    }
+
+    #[test]
+    fn test_handles() {
+        let mut rb = RingBuffer::builder()
+            .capacity(3)
+            .policy(OverflowPolicy::Overwrite)
+            .build();
+        let a = rb.push_indexed("a").unwrap();
+        let b = rb.push_indexed("b").unwrap();
+        assert_eq!((a.offset(), b.offset()), (0, 1));
+        assert_eq!(rb.get_by_handle(b), Some(&"b"));
+
+        assert_eq!(rb.pop(), Some("a"));
+        assert_eq!(rb.get_by_handle(a), None); // Прочитан
+        *rb.get_mut_by_handle(b).unwrap() = "B";
+
+        let c = rb.push_indexed("c").unwrap();
+        rb.push_indexed("d").unwrap();
+        rb.push_indexed("e").unwrap(); // Вытесняет "B"
+        assert_eq!(rb.get_by_handle(b), None);
+        assert_eq!(rb.get_by_handle(c), Some(&"c"));
+
+        rb.resize(5); // Перенос не меняет смещений
+        assert_eq!(rb.get_by_handle(c), Some(&"c"));
+
+        // Смещение хвоста переиспользуется - прежний дескриптор не должен найти новый элемент
+        assert_eq!(rb.pop_back(), Some("e"));
+        let f = rb.push_indexed("f").unwrap();
+        assert_eq!(rb.get_by_handle(c), None);
+        assert_eq!(rb.get_by_handle(f), Some(&"f"));
+
+        rb.clear();
+        assert_eq!(rb.get_by_handle(f), None);
+        assert_eq!(rb.push_indexed("g").unwrap().offset(), 5);
+    }
+
+    #[test]
+    fn test_pop_each() {
+        use core::ops::ControlFlow;
+
+        let mut rb = RingBuffer::new(8);
+        for i in 0..6 {
+            rb.push(i).unwrap();
+        }
+
+        let mut sum = 0;
+        assert_eq!(
+            rb.pop_each(2, |i| {
+                sum += i;
+                ControlFlow::Continue(())
+            }),
+            2
+        );
+        assert_eq!(sum, 1);
+
+        // Останавливаемся на первом нечётном: он прочитан, следующие - нет
+        let mut seen = Vec::new();
+        let stop = |i| {
+            seen.push(i);
+            if i % 2 == 1 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        };
+        assert_eq!(rb.pop_each(10, stop), 2);
+        assert_eq!(seen, [2, 3]);
+        assert_eq!(rb.pop_each(10, |_| ControlFlow::Continue(())), 2); // Только сколько есть
+        assert!(rb.is_empty());
+    }
+
+    #[test]
+    fn test_mutate_in_place() {
+        let mut rb = RingBuffer::new(3);
+        for word in ["ключ=1", "пароль=2", "ключ=3"] {
+            rb.push(word.to_string()).unwrap();
+        }
+        rb.pop();
+        rb.push("пароль=4".to_string()).unwrap(); // Содержимое проходит через шов
+
+        rb.for_each_mut(|line| line.push(';'));
+        // Пароли скрываем, последний ключ выбрасываем
+        rb.retain_mut(|line| {
+            if line.starts_with("пароль") {
+                *line = "пароль=***;".to_string();
+            }
+            line != "ключ=3;"
+        });
+        assert_eq!(
+            rb.iter().collect::<Vec<_>>(),
+            ["пароль=***;", "пароль=***;"]
+        );
+        assert_eq!(rb.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_full_behavior() {
+        let mut rb = RingBuffer::new(2);
+
+        assert!(rb.push(1).is_ok());
+        assert!(rb.push(2).is_ok());
+        assert!(rb.push(3).is_err()); // Должно вернуть ошибку
+
+        assert_eq!(rb.drain(3), vec![1, 2]); // Читаем больше чем есть
+    }
+
+    #[test]
+    fn test_wrap_around() {
+        let mut rb = RingBuffer::new(3);
+
+        // Заполняем и частично освобождаем
+        assert_eq!(rb.extend(&[1, 2, 3]), 3);
+        assert_eq!(rb.drain(2), vec![1, 2]);
+
+        // Должны записаться в начало
+        assert_eq!(rb.extend(&[4, 5]), 2);
+        assert_eq!(rb.drain(3), vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        let mut rb = RingBuffer::new(1);
+
+        // Граничный случай с буфером размером 1
+        assert!(rb.push(1).is_ok());
+        assert!(rb.is_full());
+        assert!(rb.push(2).is_err());
+
+        assert_eq!(rb.pop(), Some(1));
+        assert!(rb.is_empty());
+    }
+
+    #[test]
+    fn test_push_overwrite_and_get() {
+        let mut rb = RingBuffer::new(2);
+
+        assert_eq!(rb.push_overwrite("a"), None);
+        assert_eq!(rb.push_overwrite("b"), None);
+        assert_eq!(rb.push_overwrite("c"), Some("a")); // Вытесняем самый старый
+
+        assert_eq!(rb.get(0), Some(&"b"));
+        assert_eq!(rb.get(1), Some(&"c"));
+        assert_eq!(rb.get(2), None);
+    }
+
+    #[test]
+    fn test_pop_back() {
+        let mut rb = RingBuffer::new(3);
+
+        // Хвост находится в начале массива после закольцовывания
+        assert_eq!(rb.extend(&[1, 2, 3]), 3);
+        assert_eq!(rb.pop(), Some(1));
+        assert!(rb.push(4).is_ok());
+
+        assert_eq!(rb.pop_back(), Some(4));
+        assert_eq!(rb.pop_back(), Some(3));
+        assert_eq!(rb.pop(), Some(2));
+        assert_eq!(rb.pop_back(), None);
+    }
+
+    #[test]
+    fn test_get_mut_and_iter() {
+        let mut rb = RingBuffer::new(3);
+        assert_eq!(rb.extend(&[1, 2, 3]), 3);
+        assert_eq!(rb.pop(), Some(1));
+        assert!(rb.push(4).is_ok());
+
+        *rb.get_mut(0).unwrap() = 20;
+        assert_eq!(rb.iter().copied().collect::<Vec<_>>(), vec![20, 3, 4]);
+        assert!(rb.get_mut(3).is_none());
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut rb = RingBuffer::new(4);
+        assert_eq!(rb.extend(&[0, 1, 2, 3]), 4);
+        assert_eq!(rb.drain(2), vec![0, 1]);
+        assert_eq!(rb.extend(&[4, 5]), 2); // Содержимое проходит через границу массива
+
+        assert_eq!(rb.remove(1), Some(3));
+        assert_eq!(rb.remove(5), None);
+        assert!(rb.push(6).is_ok());
+        assert_eq!(rb.drain(4), vec![2, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut rb = RingBuffer::new(5);
+        assert_eq!(rb.extend(&[0, 0, 1, 3]), 4);
+        assert_eq!(rb.drain(2), vec![0, 0]);
+        assert_eq!(rb.extend(&[4, 5]), 2); // Содержимое проходит через границу массива
+
+        assert!(rb.insert(1, 2).is_ok());
+        assert!(rb.insert(0, 9).is_err()); // Полон
+        assert_eq!(rb.validate(), Ok(()));
+        assert_eq!(rb.drain(5), vec![1, 2, 3, 4, 5]);
+        assert!(rb.insert(0, 7).is_ok());
+        assert_eq!(rb.pop(), Some(7));
+    }
+
+    #[test]
+    fn test_retain_and_push_all() {
+        let mut rb = RingBuffer::builder().capacity(4).with_stats().build();
+        assert_eq!(rb.push_all([1, 2, 3, 4]), Ok(4));
+
+        rb.retain(|&v| v % 2 == 0);
+        assert!(rb.iter().eq([2, 4].iter()));
+        assert_eq!(rb.validate(), Ok(()));
+
+        assert!(rb.push_all([5, 6, 7]).is_err()); // Места только на два
+        assert!(rb.iter().eq([2, 4].iter())); // Ничего не записано
+        assert_eq!(rb.push_with(|| 8), Ok(()));
+
+        let stats = rb.stats().unwrap();
+        assert_eq!(stats.pushes, 5);
+        assert_eq!(stats.pops, 2);
+    }
+
+    #[test]
+    fn test_skip() {
+        let mut rb = RingBuffer::new(4);
+        rb.extend(&[1, 2, 3]);
+
+        assert_eq!(rb.skip(2), 2);
+        assert_eq!(rb.pop(), Some(3));
+        assert_eq!(rb.skip(5), 0); // Пропускать нечего
+    }
+
+    #[test]
+    fn test_swap_and_clone_from() {
+        let mut front = RingBuffer::new(2);
+        let mut back = RingBuffer::new(5);
+        front.extend(&[1, 2]);
+        back.extend(&[7]);
+
+        front.swap(&mut back);
+        assert_eq!((front.capacity(), front.pop()), (5, Some(7)));
+        assert_eq!((back.capacity(), back.pop()), (2, Some(1)));
+
+        // clone_from между буферами одной ёмкости не выделяет память заново
+        let mut source = RingBuffer::new(4);
+        source.extend(&[3, 4, 5]);
+        let mut target = RingBuffer::new(4);
+        target.push(9).unwrap();
+        let storage = target.buffer.as_ptr();
+
+        target.clone_from(&source);
+        assert_eq!(target.buffer.as_ptr(), storage);
+        assert!(target.iter().eq(source.iter()));
+        assert_eq!(target.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_fill_with() {
+        let mut rb = RingBuffer::new(4);
+        rb.push(7).unwrap();
+        assert_eq!(rb.fill_with(|| 0), 3); // Только до заполнения
+        assert!(rb.iter().eq([7, 0, 0, 0].iter()));
+
+        let mut rb = RingBuffer::new(4);
+        let mut source = 10..12;
+        assert_eq!(rb.fill_while(|| source.next()), 2); // Источник кончился раньше
+        assert_eq!(rb.len(), 2);
+    }
+
+    #[test]
+    fn test_windows() {
+        let mut rb = RingBuffer::new(4);
+        rb.extend(&[0, 0, 1]);
+        rb.skip(2);
+        rb.extend(&[2, 3, 4]); // Содержимое 1..=4 проходит через границу массива
+
+        let sums: Vec<i32> = rb.windows(2).map(|w| w.iter().sum()).collect();
+        assert_eq!(sums, [3, 5, 7]);
+        assert_eq!(rb.windows(4).count(), 1);
+        assert_eq!(rb.windows(5).count(), 0); // Окно длиннее содержимого
+
+        let last = rb.windows(3).last().unwrap();
+        assert_eq!((last.len(), last.get(0), last.get(3)), (3, Some(&2), None));
+    }
+
+    #[test]
+    fn test_truncate() {
+        let mut rb = RingBuffer::new(5);
+        rb.extend(&[1, 2, 3, 4, 5]);
+
+        rb.truncate_front(3); // Остаются самые новые
+        assert!(rb.iter().eq([3, 4, 5].iter()));
+        rb.truncate_back(2); // Остаются самые старые
+        assert!(rb.iter().eq([3, 4].iter()));
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut rb = RingBuffer::new(3);
+        assert!(rb.validate().is_ok());
+
+        rb.extend(&[1, 2, 3]);
+        rb.pop();
+        rb.push(4).unwrap();
+        assert!(rb.validate().is_ok());
+
+        rb.buffer[rb.tail] = None; // Портим хранилище вручную
+        assert!(rb.validate().is_err());
+    }
+
+    #[test]
+    fn test_clear_and_resize() {
+        let mut rb = RingBuffer::new(3);
+        rb.extend(&[1, 2, 3]);
+        rb.pop();
+        rb.push(4).unwrap();
+
+        rb.resize(5); // Содержимое переносится с сохранением порядка
+        assert_eq!(rb.extend(&[5, 6, 7]), 2);
+        assert_eq!(rb.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4, 5, 6]);
+
+        rb.resize(2); // Остаются самые новые
+        assert_eq!(rb.iter().copied().collect::<Vec<_>>(), vec![5, 6]);
+
+        rb.clear();
+        assert!(rb.is_empty());
+        assert_eq!(rb.capacity(), 2);
+    }
+}
+
+// Модельные тесты: случайные последовательности операций сравниваются с VecDeque
+#[cfg(test)]
+mod model_tests {
+    use super::RingBuffer;
+    use proptest::prelude::*;
+    use std::collections::VecDeque;
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        Push(u8),
+        PushOverwrite(u8),
+        Pop,
+        PopBack,
+        Extend(Vec<u8>),
+        Drain(usize),
+        Clear,
+        Resize(usize),
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            any::<u8>().prop_map(Op::Push),
+            any::<u8>().prop_map(Op::PushOverwrite),
+            Just(Op::Pop),
+            Just(Op::PopBack),
+            prop::collection::vec(any::<u8>(), 0..12).prop_map(Op::Extend),
+            (0usize..12).prop_map(Op::Drain),
+            Just(Op::Clear),
+            (1usize..12).prop_map(Op::Resize),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_vecdeque(capacity in 1usize..10, ops in prop::collection::vec(op(), 0..200)) {
+            let mut ring = RingBuffer::new(capacity);
+            let mut model: VecDeque<u8> = VecDeque::new();
+            let mut model_capacity = capacity;
+
+            for op in ops {
+                match op {
+                    Op::Push(v) => {
+                        let accepted = model.len() < model_capacity;
+                        if accepted {
+                            model.push_back(v);
+                        }
+                        prop_assert_eq!(ring.push(v).is_ok(), accepted);
+                    }
+                    Op::PushOverwrite(v) => {
+                        let evicted = if model.len() == model_capacity { model.pop_front() } else { None };
+                        model.push_back(v);
+                        prop_assert_eq!(ring.push_overwrite(v), evicted);
+                    }
+                    Op::Pop => prop_assert_eq!(ring.pop(), model.pop_front()),
+                    Op::PopBack => prop_assert_eq!(ring.pop_back(), model.pop_back()),
+                    Op::Extend(data) => {
+                        let accepted = data.len().min(model_capacity - model.len());
+                        model.extend(&data[..accepted]);
+                        prop_assert_eq!(ring.extend(&data), accepted);
+                    }
+                    Op::Drain(n) => {
+                        let expected: Vec<u8> = model.drain(..n.min(model.len())).collect();
+                        prop_assert_eq!(ring.drain(n), expected);
+                    }
+                    Op::Clear => {
+                        model.clear();
+                        ring.clear();
+                    }
+                    Op::Resize(n) => {
+                        while model.len() > n {
+                            model.pop_front();
+                        }
+                        model_capacity = n;
+                        ring.resize(n);
+                    }
+                }
+
+                // Наблюдаемое состояние совпадает с моделью после каждой операции
+                prop_assert_eq!(ring.len(), model.len());
+                prop_assert_eq!(ring.capacity(), model_capacity);
+                prop_assert_eq!(ring.is_empty(), model.is_empty());
+                prop_assert_eq!(ring.is_full(), model.len() == model_capacity);
+                prop_assert!(ring.iter().eq(model.iter()));
+                prop_assert_eq!(ring.validate(), Ok(()));
+            }
+        }
+    }
+}
+
+// Каждый элемент должен уничтожаться ровно один раз
+#[cfg(test)]
+mod drop_tests {
+    use super::RingBuffer;
+    use std::cell::Cell;
+    use std::panic::{self, AssertUnwindSafe};
+    use std::rc::Rc;
+
+    // Элемент, который считает свои уничтожения
+    struct Counted(Rc<Cell<usize>>);
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    fn filled(capacity: usize, count: usize, drops: &Rc<Cell<usize>>) -> RingBuffer<Counted> {
+        let mut rb = RingBuffer::new(capacity);
+        for _ in 0..count {
+            rb.push_overwrite(Counted(Rc::clone(drops)));
+        }
+        rb
+    }
+
+    #[test]
+    fn test_drop_remaining() {
+        let drops = Rc::new(Cell::new(0));
+        let rb = filled(4, 3, &drops);
+        assert_eq!(drops.get(), 0);
+
+        drop(rb);
+        assert_eq!(drops.get(), 3); // Остаток уничтожен вместе с буфером
+    }
+
+    #[test]
+    fn test_drop_overwritten() {
+        let drops = Rc::new(Cell::new(0));
+        let mut rb = filled(3, 3, &drops);
+
+        // Вытесненный элемент возвращается и уничтожается у вызывающего
+        let evicted = rb.push_overwrite(Counted(Rc::clone(&drops)));
+        assert_eq!(drops.get(), 0);
+        drop(evicted);
+        assert_eq!(drops.get(), 1);
+
+        drop(rb);
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn test_drop_clear() {
+        let drops = Rc::new(Cell::new(0));
+        let mut rb = filled(3, 5, &drops); // Два вытеснены при заполнении
+        assert_eq!(drops.get(), 2);
+
+        rb.clear();
+        assert_eq!(drops.get(), 5);
+
+        drop(rb);
+        assert_eq!(drops.get(), 5); // Повторно ничего не уничтожается
+    }
+
+    #[test]
+    fn test_panic_in_retain() {
+        let drops = Rc::new(Cell::new(0));
+        let mut rb = filled(5, 5, &drops);
+        let mut seen = 0;
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            rb.retain(|_| {
+                seen += 1;
+                if seen == 4 {
+                    panic!("keep");
+                }
+                seen % 2 == 1 // Оставляем 1-й и 3-й, 2-й удаляем
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(rb.validate(), Ok(()));
+        assert_eq!(rb.len(), 4); // Удалён только обработанный 2-й
+        assert_eq!(drops.get(), 1);
+
+        drop(rb);
+        assert_eq!(drops.get(), 5); // Никто не уничтожен дважды
+    }
+
+    #[test]
+    fn test_panic_in_push_all() {
+        let drops = Rc::new(Cell::new(0));
+        let mut rb = filled(6, 2, &drops);
+
+        let source = Rc::clone(&drops);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            rb.push_all((0..3).map(|i| {
+                if i == 2 {
+                    panic!("source");
+                }
+                Counted(Rc::clone(&source))
+            }))
+        }));
+        assert!(result.is_err());
+        assert_eq!(rb.validate(), Ok(()));
+        assert_eq!(rb.len(), 2); // Частичная запись откачена
+        assert_eq!(drops.get(), 2);
+
+        drop(rb);
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn test_panic_in_push_with() {
+        let drops = Rc::new(Cell::new(0));
+        let mut rb = filled(3, 1, &drops);
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| rb.push_with(|| panic!("make"))));
+        assert!(result.is_err());
+        assert_eq!(rb.validate(), Ok(()));
+        assert_eq!(rb.len(), 1);
+
+        assert!(rb.push_with(|| Counted(Rc::clone(&drops))).is_ok());
+        assert_eq!(rb.len(), 2);
+    }
+
+    #[test]
+    fn test_drop_truncate() {
+        let drops = Rc::new(Cell::new(0));
+        let mut rb = filled(6, 6, &drops);
+
+        rb.truncate_front(4);
+        assert_eq!(drops.get(), 2);
+        rb.truncate_back(1);
+        assert_eq!(drops.get(), 5);
+        rb.truncate_back(3); // Уже меньше - ничего не происходит
+        assert_eq!(rb.len(), 1);
+
+        drop(rb);
+        assert_eq!(drops.get(), 6);
+    }
+
+    #[test]
+    fn test_drop_skip() {
+        let drops = Rc::new(Cell::new(0));
+        let mut rb = filled(4, 4, &drops);
+
+        assert_eq!(rb.skip(3), 3);
+        assert_eq!(drops.get(), 3);
+
+        drop(rb);
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn test_zero_sized() {
+        let mut rb = RingBuffer::new(3);
+        assert_eq!(rb.buffer.capacity(), 0); // Хранилище не выделено
+
+        assert!(rb.push(()).is_ok());
+        assert!(rb.push(()).is_ok());
+        assert_eq!(rb.pop(), Some(()));
+        assert!(rb.push(()).is_ok());
+        assert!(rb.push(()).is_ok()); // Через границу массива
+        assert!(rb.push(()).is_err());
+        assert_eq!(rb.get(2), Some(&()));
+        assert_eq!(rb.get(3), None);
+        assert_eq!(rb.visualize(), "[#HT##] 3/3");
+
+        assert_eq!(rb.remove(0), Some(()));
+        rb.retain(|_| true);
+        assert_eq!(rb.iter().count(), 2);
+        rb.resize(5);
+        assert_eq!((rb.len(), rb.capacity()), (2, 5));
+        assert_eq!(rb.validate(), Ok(()));
+        assert_eq!(rb.drain(5), vec![(), ()]);
+    }
+
+    #[test]
+    fn test_zero_sized_drops() {
+        std::thread_local! {
+            static LIVE: Cell<isize> = const { Cell::new(0) };
+        }
+
+        // Билет нулевого размера: живые экземпляры считаются глобально
+        #[derive(Debug)]
+        struct Ticket;
+
+        impl Ticket {
+            fn new() -> Self {
+                LIVE.with(|live| live.set(live.get() + 1));
+                Ticket
+            }
+        }
+
+        impl Clone for Ticket {
+            fn clone(&self) -> Self {
+                Ticket::new()
+            }
+        }
+
+        impl Drop for Ticket {
+            fn drop(&mut self) {
+                LIVE.with(|live| live.set(live.get() - 1));
+            }
+        }
+
+        let live = || LIVE.with(Cell::get);
+        let mut rb = RingBuffer::new(4);
+        for _ in 0..3 {
+            rb.push(Ticket::new()).unwrap();
+        }
+        drop(rb.pop());
+        assert_eq!(live(), 2);
+
+        let mut copy = rb.clone();
+        assert_eq!(live(), 4);
+        copy.clone_from(&rb);
+        assert_eq!(live(), 4); // Прежние копии уничтожены
+
+        rb.clear();
+        assert_eq!(live(), 2);
+        drop(copy);
+        assert_eq!(live(), 0);
+    }
+}
@@ -0,0 +1,163 @@
+// Кольцо с кодированием повторов (RLE) для медленно меняющихся значений
+// (показания датчиков): подряд идущие равные значения хранятся одной серией,
+// поэтому в той же памяти помещается намного более длинная история. Снаружи
+// кольцо выглядит как последовательность отдельных элементов
+
+use crate::RingBuffer;
+
+// 1. Серия: значение и сколько раз подряд оно записано
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run<T> {
+    pub value: T,
+    pub count: usize,
+}
+
+// 2. Серии от старых к новым и общее число элементов в них
+#[derive(Debug, Clone)]
+pub struct RleRing<T> {
+    runs: RingBuffer<Run<T>>,
+    len: usize,
+}
+
+impl<T: PartialEq> RleRing<T> {
+    // 2.1. Кольцо на runs серий (элементов в них может быть сколько угодно)
+    pub fn new(runs: usize) -> Self {
+        RleRing {
+            runs: RingBuffer::new(runs),
+            len: 0,
+        }
+    }
+
+    // 3. Запись: равное последнему значение удлиняет его серию, иначе начинается
+    // новая; если серий уже capacity, самая старая серия вытесняется целиком
+    pub fn push(&mut self, value: T) -> Option<Run<T>> {
+        self.len += 1;
+        let last = self.runs.len().checked_sub(1);
+        if let Some(run) = last.and_then(|last| self.runs.get_mut(last)) {
+            if run.value == value {
+                run.count += 1;
+                return None;
+            }
+        }
+
+        let evicted = self.runs.push_overwrite(Run { value, count: 1 });
+        if let Some(run) = &evicted {
+            self.len -= run.count;
+        }
+        evicted
+    }
+
+    // 4. Элемент по логическому индексу (0 - самый старый); O(число серий)
+    pub fn get(&self, index: usize) -> Option<&T> {
+        let mut start = 0;
+        for run in self.runs.iter() {
+            if index < start + run.count {
+                return Some(&run.value);
+            }
+            start += run.count;
+        }
+        None
+    }
+
+    // 5. Элементы от старых к новым (каждая серия разворачивается)
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.runs
+            .iter()
+            .flat_map(|run| core::iter::repeat_n(&run.value, run.count))
+    }
+
+    // 5.1. Сами серии
+    pub fn runs(&self) -> impl Iterator<Item = &Run<T>> + '_ {
+        self.runs.iter()
+    }
+
+    // 6. Размеры: элементы, серии и предел серий
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn run_count(&self) -> usize {
+        self.runs.len()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.runs.capacity()
+    }
+
+    pub fn clear(&mut self) {
+        self.runs.clear();
+        self.len = 0;
+    }
+}
+
+impl<T: PartialEq + Clone> RleRing<T> {
+    // 7. Чтение самого старого элемента: серия укорачивается, последний - уходит
+    pub fn pop(&mut self) -> Option<T> {
+        let run = self.runs.get_mut(0)?;
+        self.len -= 1;
+        if run.count > 1 {
+            run.count -= 1;
+            return Some(run.value.clone());
+        }
+        self.runs.pop().map(|run| run.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RleRing, Run};
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_runs() {
+        let mut ring = RleRing::new(2);
+        for reading in [20, 20, 20, 21, 21] {
+            assert_eq!(ring.push(reading), None);
+        }
+        assert_eq!((ring.len(), ring.run_count()), (5, 2));
+        assert_eq!(ring.get(2), Some(&20));
+        assert_eq!(ring.get(3), Some(&21));
+        assert_eq!(ring.get(5), None);
+
+        // Третья серия вытесняет первую целиком
+        assert_eq!(
+            ring.push(22),
+            Some(Run {
+                value: 20,
+                count: 3
+            })
+        );
+        assert_eq!(ring.iter().copied().collect::<Vec<_>>(), [21, 21, 22]);
+    }
+
+    #[test]
+    fn test_pop() {
+        let mut ring = RleRing::new(4);
+        for reading in [1, 1, 2] {
+            ring.push(reading);
+        }
+
+        assert_eq!(ring.pop(), Some(1));
+        assert_eq!(ring.run_count(), 2); // Серия укоротилась
+        assert_eq!(ring.pop(), Some(1));
+        assert_eq!(ring.run_count(), 1);
+        assert_eq!(ring.pop(), Some(2));
+        assert_eq!(ring.pop(), None);
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    fn test_longer_history() {
+        // Медленный датчик: значение меняется раз в 100 отсчётов
+        let mut ring = RleRing::new(16);
+        for i in 0..1000 {
+            ring.push(i / 100);
+        }
+        assert_eq!(ring.len(), 1000); // 1000 элементов в 16 сериях
+        assert_eq!(ring.runs().map(|run| run.count).sum::<usize>(), 1000);
+    }
+}
@@ -0,0 +1,42 @@
+// Минимальный источник случайных чисел без внешних зависимостей
+
+// 1. Всё, что умеет выдавать случайные 64 бита
+pub trait RandomSource {
+    fn next_u64(&mut self) -> u64;
+
+    // 1.1. Случайный индекс в диапазоне 0..bound (bound > 0)
+    fn gen_index(&mut self, bound: usize) -> usize {
+        // Умножение с расширением вместо % - без заметного смещения распределения
+        ((self.next_u64() as u128 * bound as u128) >> 64) as usize
+    }
+}
+
+// 2. Простой генератор xorshift64 (не для криптографии)
+#[derive(Debug, Clone)]
+pub struct XorShift64 {
+    state: u64,
+}
+
+impl XorShift64 {
+    // 2.1. Создаём генератор из зерна (нулевое состояние недопустимо)
+    pub fn new(seed: u64) -> Self {
+        XorShift64 {
+            state: if seed == 0 {
+                0x9E37_79B9_7F4A_7C15
+            } else {
+                seed
+            },
+        }
+    }
+}
+
+impl RandomSource for XorShift64 {
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+}
@@ -0,0 +1,113 @@
+// Сериализация RingBuffer: ёмкость и элементы от самого старого к самому новому
+// (политика переполнения и счётчики - настройки времени выполнения, не сохраняются);
+// снимок счётчиков StatsSnapshot только записывается - для мониторинга
+
+use alloc::vec::Vec;
+
+use serde::de::Error;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{RingBuffer, StatsSnapshot};
+
+// 1. Представление буфера при записи и при чтении
+#[derive(Serialize)]
+#[serde(rename = "RingBuffer")]
+struct RingRef<'a, T> {
+    capacity: usize,
+    items: Vec<&'a T>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "RingBuffer")]
+struct RingOwned<T> {
+    capacity: usize,
+    items: Vec<T>,
+}
+
+// 2. Запись
+impl<T: Serialize> Serialize for RingBuffer<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RingRef {
+            capacity: self.capacity(),
+            items: self.iter().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+// 3. Чтение с проверкой, что элементы помещаются в ёмкость
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for RingBuffer<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let owned = RingOwned::<T>::deserialize(deserializer)?;
+        if owned.capacity == 0 {
+            return Err(D::Error::custom("Ёмкость буфера должна быть больше нуля!"));
+        }
+        if owned.items.len() > owned.capacity {
+            return Err(D::Error::custom("Элементов больше, чем ёмкость!"));
+        }
+
+        let mut ring = RingBuffer::new(owned.capacity);
+        for item in owned.items {
+            let _ = ring.push(item); // Место проверено выше
+        }
+        Ok(ring)
+    }
+}
+
+// 4. Снимок счётчиков
+impl Serialize for StatsSnapshot {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("StatsSnapshot", 7)?;
+        state.serialize_field("len", &self.len)?;
+        state.serialize_field("capacity", &self.capacity)?;
+        state.serialize_field("high_water", &self.high_water)?;
+        state.serialize_field("pushes", &self.pushes)?;
+        state.serialize_field("pops", &self.pops)?;
+        state.serialize_field("overwritten", &self.overwritten)?;
+        state.serialize_field("laps", &self.laps)?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RingBuffer;
+
+    #[test]
+    fn test_roundtrip_keeps_order() {
+        let mut rb = RingBuffer::new(3);
+        rb.extend(&[1, 2, 3]);
+        rb.pop();
+        rb.push(4).unwrap(); // Содержимое проходит через границу массива
+
+        let json = serde_json::to_string(&rb).unwrap();
+        assert_eq!(json, r#"{"capacity":3,"items":[2,3,4]}"#);
+
+        let mut back: RingBuffer<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.capacity(), 3);
+        assert_eq!(back.drain(3), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_stats_snapshot() {
+        let mut rb = RingBuffer::builder().capacity(2).with_stats().build();
+        rb.extend(&[1, 2]);
+        rb.pop();
+
+        let json = serde_json::to_string(&rb.stats_snapshot().unwrap()).unwrap();
+        assert_eq!(
+            json,
+            r#"{"len":1,"capacity":2,"high_water":2,"pushes":2,"pops":1,"overwritten":0,"laps":1}"#
+        );
+    }
+
+    #[test]
+    fn test_rejects_overfull() {
+        let result = serde_json::from_str::<RingBuffer<u8>>(r#"{"capacity":1,"items":[1,2]}"#);
+        assert!(result.is_err());
+
+        let result = serde_json::from_str::<RingBuffer<u8>>(r#"{"capacity":0,"items":[]}"#);
+        assert!(result.is_err());
+    }
+}
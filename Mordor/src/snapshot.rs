@@ -0,0 +1,211 @@
+// Снимки ByteRing для сохранения на диск (бортовой самописец)
+//
+// Формат: "MRDR", версия (1 байт), сжатие (1 байт), ёмкость и длина (u64 LE),
+// затем логическое содержимое - как есть или сжатое deflate (возможность deflate)
+
+use std::io::{self, Read, Write};
+
+use crate::ByteRing;
+
+// 1. Заголовок снимка
+pub const MAGIC: &[u8; 4] = b"MRDR";
+pub const VERSION: u8 = 1;
+
+// 1.1. Способ хранения содержимого
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    Deflate,
+}
+
+impl Compression {
+    // 1.1.1. Способ, выбранный при сборке
+    pub const fn current() -> Self {
+        if cfg!(feature = "deflate") {
+            Compression::Deflate
+        } else {
+            Compression::None
+        }
+    }
+
+    fn from_byte(byte: u8) -> io::Result<Self> {
+        match byte {
+            0 => Ok(Compression::None),
+            1 => Ok(Compression::Deflate),
+            _ => Err(invalid("Неизвестный способ сжатия!")),
+        }
+    }
+
+    fn to_byte(self) -> u8 {
+        match self {
+            Compression::None => 0,
+            Compression::Deflate => 1,
+        }
+    }
+}
+
+// 1.2. Разобранный заголовок (без содержимого)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Header {
+    pub compression: Compression,
+    pub capacity: u64,
+    pub len: u64,
+}
+
+impl Header {
+    // 1.2.1. Чтение и проверка заголовка
+    pub fn read_from(r: &mut impl Read) -> io::Result<Self> {
+        let mut fixed = [0; 22];
+        r.read_exact(&mut fixed)?;
+        if &fixed[..4] != MAGIC {
+            return Err(invalid("Это не снимок кольца!"));
+        }
+        if fixed[4] != VERSION {
+            return Err(invalid("Неподдерживаемая версия снимка!"));
+        }
+
+        let header = Header {
+            compression: Compression::from_byte(fixed[5])?,
+            capacity: u64::from_le_bytes(fixed[6..14].try_into().unwrap()),
+            len: u64::from_le_bytes(fixed[14..22].try_into().unwrap()),
+        };
+        if header.capacity == 0 || header.len > header.capacity {
+            return Err(invalid("Размер больше ёмкости!"));
+        }
+        Ok(header)
+    }
+
+    fn write_to(&self, w: &mut impl Write) -> io::Result<()> {
+        w.write_all(MAGIC)?;
+        w.write_all(&[VERSION, self.compression.to_byte()])?;
+        w.write_all(&self.capacity.to_le_bytes())?;
+        w.write_all(&self.len.to_le_bytes())
+    }
+}
+
+// 2. Сохранение и восстановление
+impl ByteRing {
+    // 2.1. Запись снимка; содержимое сжимается, если собрано с возможностью deflate
+    pub fn save_snapshot(&self, w: &mut impl Write) -> io::Result<()> {
+        let header = Header {
+            compression: Compression::current(),
+            capacity: self.capacity() as u64,
+            len: self.len() as u64,
+        };
+        header.write_to(w)?;
+
+        let (first, second) = self.as_slices();
+        match header.compression {
+            Compression::None => {
+                w.write_all(first)?;
+                w.write_all(second)
+            }
+            Compression::Deflate => write_deflate(w, first, second),
+        }
+    }
+
+    // 2.2. Восстановление кольца из снимка (читает и несжатые, и сжатые снимки)
+    pub fn load_snapshot(r: &mut impl Read) -> io::Result<ByteRing> {
+        let header = Header::read_from(r)?;
+        let capacity =
+            usize::try_from(header.capacity).map_err(|_| invalid("Слишком большая ёмкость!"))?;
+        let len = header.len as usize; // Не больше ёмкости, уже проверено
+
+        // Ёмкость из файла не доверенная: испорченный снимок не должен ронять процесс
+        let mut ring =
+            ByteRing::try_new(capacity).ok_or_else(|| invalid("Слишком большая ёмкость!"))?;
+        let read = match header.compression {
+            Compression::None => ring.extend_from_reader_exact(r, len)?,
+            Compression::Deflate => read_deflate(r, &mut ring, len)?,
+        };
+        if read < len {
+            return Err(io::ErrorKind::UnexpectedEof.into()); // Снимок обрезан
+        }
+        Ok(ring)
+    }
+}
+
+#[cfg(feature = "deflate")]
+fn write_deflate(w: &mut impl Write, first: &[u8], second: &[u8]) -> io::Result<()> {
+    let mut encoder = flate2::write::DeflateEncoder::new(w, flate2::Compression::default());
+    encoder.write_all(first)?;
+    encoder.write_all(second)?;
+    encoder.finish()?;
+    Ok(())
+}
+
+#[cfg(not(feature = "deflate"))]
+fn write_deflate(_: &mut impl Write, _: &[u8], _: &[u8]) -> io::Result<()> {
+    unreachable!("Сжатие выбирается только при сборке с deflate")
+}
+
+#[cfg(feature = "deflate")]
+fn read_deflate(r: &mut impl Read, ring: &mut ByteRing, len: usize) -> io::Result<usize> {
+    ring.extend_from_reader_exact(&mut flate2::read::DeflateDecoder::new(r), len)
+}
+
+#[cfg(not(feature = "deflate"))]
+fn read_deflate(_: &mut impl Read, _: &mut ByteRing, _: usize) -> io::Result<usize> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Снимок сжат, а возможность deflate не включена!",
+    ))
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Compression, Header};
+    use crate::ByteRing;
+
+    #[test]
+    fn test_round_trip() {
+        let mut ring = ByteRing::new(64);
+        ring.extend_from_slice(&[0; 40]);
+        ring.drain_into(&mut [0; 40]);
+        ring.extend_from_slice(&[7; 50]); // Содержимое проходит через границу массива
+
+        let mut file = Vec::new();
+        ring.save_snapshot(&mut file).unwrap();
+
+        let header = Header::read_from(&mut &file[..]).unwrap();
+        assert_eq!(header.compression, Compression::current());
+        assert_eq!((header.capacity, header.len), (64, 50));
+
+        let restored = ByteRing::load_snapshot(&mut &file[..]).unwrap();
+        assert_eq!(restored.capacity(), 64);
+        assert_eq!(restored.checksum(), ring.checksum());
+    }
+
+    #[test]
+    fn test_rejects_garbage() {
+        assert!(ByteRing::load_snapshot(&mut &b"not a snapshot at all!"[..]).is_err());
+
+        let mut file = Vec::new();
+        ByteRing::new(4).save_snapshot(&mut file).unwrap();
+        file[14] = 9; // Длина больше ёмкости
+        assert!(ByteRing::load_snapshot(&mut &file[..]).is_err());
+
+        file[14] = 0;
+        file[6..14].copy_from_slice(&(u64::MAX >> 1).to_le_bytes()); // Ёмкость не выделить
+        let err = ByteRing::load_snapshot(&mut &file[..]).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[cfg(feature = "deflate")]
+    #[test]
+    fn test_deflate_shrinks_repetitive_data() {
+        let mut ring = ByteRing::new(4096);
+        ring.extend_from_slice(&[b'.'; 4096]);
+
+        let mut file = Vec::new();
+        ring.save_snapshot(&mut file).unwrap();
+        assert!(file.len() < 200); // Заголовок и несколько байт сжатых данных
+
+        let restored = ByteRing::load_snapshot(&mut &file[..]).unwrap();
+        assert_eq!(restored.len(), 4096);
+    }
+}
@@ -0,0 +1,141 @@
+// Ограниченная очередь с приоритетом (top-K) на хранилище RingBuffer:
+// элементы лежат по возрастанию, запись вставляет на своё место, а при
+// заполнении вытесняется наименьший
+
+use crate::RingBuffer;
+
+// 1. Кольцо, упорядоченное от наименьшего (голова) к наибольшему (хвост)
+#[derive(Debug, Clone)]
+pub struct SortedRing<T> {
+    ring: RingBuffer<T>,
+}
+
+impl<T: Ord> SortedRing<T> {
+    // 1.1. Создаём кольцо на capacity наибольших элементов
+    pub fn new(capacity: usize) -> Self {
+        SortedRing {
+            ring: RingBuffer::new(capacity),
+        }
+    }
+
+    // 2. Запись на своё место (равные - после уже записанных); при заполненном
+    // кольце возвращается вытесненный наименьший элемент - или сам value,
+    // если он не больше всех хранимых
+    pub fn push(&mut self, value: T) -> Option<T> {
+        let mut evicted = None;
+        if self.ring.is_full() {
+            if self.ring.get(0).is_some_and(|min| value <= *min) {
+                return Some(value); // Не входит в top-K
+            }
+            evicted = self.ring.pop();
+        }
+
+        let index = self.position(&value);
+        let _ = self.ring.insert(index, value); // Место есть, ошибки не будет
+        evicted
+    }
+
+    // 2.1. Первый индекс с элементом больше value (двоичный поиск)
+    fn position(&self, value: &T) -> usize {
+        let (mut low, mut high) = (0, self.ring.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match self.ring.get(mid) {
+                Some(item) if item <= value => low = mid + 1,
+                _ => high = mid,
+            }
+        }
+        low
+    }
+
+    // 3. Наименьший и наибольший из хранимых
+    pub fn peek_min(&self) -> Option<&T> {
+        self.ring.get(0)
+    }
+
+    pub fn peek_max(&self) -> Option<&T> {
+        self.ring
+            .len()
+            .checked_sub(1)
+            .and_then(|last| self.ring.get(last))
+    }
+
+    // 4. Извлечение с любого конца
+    pub fn pop_min(&mut self) -> Option<T> {
+        self.ring.pop()
+    }
+
+    pub fn pop_max(&mut self) -> Option<T> {
+        self.ring.pop_back()
+    }
+
+    // 5. Обход по возрастанию
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.ring.iter()
+    }
+
+    // 6. Размеры
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ring.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.ring.is_full()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.ring.capacity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SortedRing;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_top_k() {
+        let mut top = SortedRing::new(3);
+        for latency in [40, 10, 70, 20, 90, 70, 5] {
+            top.push(latency);
+        }
+
+        assert_eq!(top.iter().copied().collect::<Vec<_>>(), [70, 70, 90]);
+        assert_eq!(top.push(1), Some(1)); // Меньше всех хранимых
+        assert_eq!(top.push(80), Some(70)); // Вытесняет наименьший
+        assert_eq!(top.peek_min(), Some(&70));
+        assert_eq!(top.peek_max(), Some(&90));
+    }
+
+    #[test]
+    fn test_stable_and_pops() {
+        // Равные по ключу элементы остаются в порядке записи
+        #[derive(Debug, PartialEq, Eq)]
+        struct Job(u8, &'static str);
+
+        impl PartialOrd for Job {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for Job {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        let mut jobs = SortedRing::new(4);
+        jobs.push(Job(2, "a"));
+        jobs.push(Job(1, "b"));
+        jobs.push(Job(2, "c"));
+        assert_eq!(jobs.pop_max(), Some(Job(2, "c")));
+        assert_eq!(jobs.pop_min(), Some(Job(1, "b")));
+        assert_eq!(jobs.len(), 1);
+        assert!(!jobs.is_full());
+    }
+}
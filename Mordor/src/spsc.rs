@@ -0,0 +1,1006 @@
+// Кольцо без блокировок для одного писателя и одного читателя (SPSC)
+
+use alloc::boxed::Box;
+use core::mem::MaybeUninit;
+
+#[cfg(feature = "std")]
+use core::task::{Context, Poll, Waker};
+
+#[cfg(feature = "std")]
+use crate::shim::{fence, Mutex};
+use crate::shim::{Arc, AtomicUsize, Ordering, UnsafeCell};
+use crate::stats::{Stats, StatsSnapshot};
+#[cfg(feature = "std")]
+use crate::BlockingError;
+
+// 1. Общая часть: ячейки и две позиции. Позиции идут по кругу в [0, 2 * capacity):
+// так пустое (head == tail) и полное кольцо различимы при любой ёмкости, а счётчики
+// не переполняются и на 32-битных целях
+struct Shared<T> {
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
+    head: AtomicUsize, // Позиция чтения (меняет только читатель)
+    tail: AtomicUsize, // Позиция записи (меняет только писатель)
+    #[cfg(feature = "std")]
+    readable: WakerSlot, // Асинхронный читатель ждёт данных
+    #[cfg(feature = "std")]
+    writable: WakerSlot, // Асинхронный писатель ждёт места
+    #[cfg(feature = "std")]
+    gone: AtomicUsize, // 1 - одна из сторон уничтожена
+}
+
+// Ячейки между head и tail принадлежат читателю, остальные - писателю
+unsafe impl<T: Send> Send for Shared<T> {}
+unsafe impl<T: Send> Sync for Shared<T> {}
+
+impl<T> Shared<T> {
+    fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    // 1.1. Ячейка позиции
+    fn slot(&self, position: usize) -> &UnsafeCell<MaybeUninit<T>> {
+        let capacity = self.capacity();
+        &self.slots[if position >= capacity {
+            position - capacity
+        } else {
+            position
+        }]
+    }
+
+    // 1.2. Следующая позиция по кругу
+    fn next(&self, position: usize) -> usize {
+        if position + 1 == 2 * self.capacity() {
+            0
+        } else {
+            position + 1
+        }
+    }
+
+    // 1.3. Сколько элементов между head и tail
+    fn distance(&self, head: usize, tail: usize) -> usize {
+        if tail >= head {
+            tail - head
+        } else {
+            tail + 2 * self.capacity() - head
+        }
+    }
+}
+
+impl<T> Drop for Shared<T> {
+    fn drop(&mut self) {
+        // Обе половины уже уничтожены, уничтожаем непрочитанные элементы
+        let mut position = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Relaxed);
+        while position != tail {
+            let slot = self.slot(position);
+            slot.with_mut(|ptr| unsafe { (*ptr).assume_init_drop() });
+            position = self.next(position);
+        }
+    }
+}
+
+// 2. Создаём кольцо и сразу делим его на писателя и читателя
+pub fn new<T>(capacity: usize) -> (Producer<T>, Consumer<T>) {
+    assert!(capacity > 0, "В буфере есть место!");
+    assert!(capacity <= usize::MAX / 2, "Позиции не помещаются в usize!");
+
+    let shared = Arc::new(Shared {
+        slots: (0..capacity)
+            .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+            .collect(),
+        head: AtomicUsize::new(0),
+        tail: AtomicUsize::new(0),
+        #[cfg(feature = "std")]
+        readable: WakerSlot::new(),
+        #[cfg(feature = "std")]
+        writable: WakerSlot::new(),
+        #[cfg(feature = "std")]
+        gone: AtomicUsize::new(0),
+    });
+    (
+        Producer {
+            shared: Arc::clone(&shared),
+            stats: Stats::default(),
+        },
+        Consumer {
+            shared,
+            stats: Stats::default(),
+        },
+    )
+}
+
+// 3. Писатель
+pub struct Producer<T> {
+    shared: Arc<Shared<T>>,
+    stats: Stats, // Свои счётчики: общие атомарные замедлили бы обе стороны
+}
+
+impl<T> Producer<T> {
+    // 3.1. Запись без ожидания; при заполненном кольце значение возвращается
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        let tail = self.shared.tail.load(Ordering::Relaxed); // Свою позицию знаем точно
+        let head = self.shared.head.load(Ordering::Acquire); // Видим освобождённые читателем ячейки
+        if self.shared.distance(head, tail) == self.shared.capacity() {
+            return Err(value);
+        }
+
+        let slot = self.shared.slot(tail);
+        slot.with_mut(|ptr| unsafe { (*ptr).write(value) });
+        let next = self.shared.next(tail);
+        self.shared.tail.store(next, Ordering::Release); // Публикуем записанное
+        #[cfg(feature = "std")]
+        self.shared.readable.wake();
+
+        self.stats.record_push(self.shared.distance(head, next));
+        if next == 0 || next == self.shared.capacity() {
+            self.stats.laps += 1; // Запись прошла через конец массива
+        }
+        Ok(())
+    }
+
+    // 3.2. Запись с ожиданием места по выбранной стратегии;
+    // если читатель уже уничтожен, значение возвращается
+    pub fn push_wait(&mut self, value: T, strategy: &impl WaitStrategy) -> Result<(), T> {
+        let mut value = value;
+        let mut attempt = 0;
+        loop {
+            match self.push(value) {
+                Ok(()) => return Ok(()),
+                Err(back) if Arc::strong_count(&self.shared) == 1 => return Err(back), // Читать некому
+                Err(back) => value = back,
+            }
+            strategy.wait(attempt);
+            attempt = attempt.saturating_add(1);
+        }
+    }
+
+    // 3.2.1. Асинхронное ожидание места: Ok - место есть (писатель один, поэтому
+    // следующий push пройдёт), Err(Closed) - читатель уничтожен
+    #[cfg(feature = "std")]
+    pub fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), BlockingError>> {
+        for attempt in 0..2 {
+            if self.shared.gone.load(Ordering::Acquire) == 1 {
+                return Poll::Ready(Err(BlockingError::Closed));
+            }
+            if !self.is_full() {
+                return Poll::Ready(Ok(()));
+            }
+            if attempt == 0 {
+                // Регистрируемся и проверяем снова: читатель мог освободить место между делом
+                self.shared.writable.register(cx.waker());
+            }
+        }
+        Poll::Pending
+    }
+
+    // 3.2.2. Запись с асинхронным ожиданием места; читатель уничтожен - значение возвращается
+    #[cfg(feature = "std")]
+    pub async fn push_async(&mut self, value: T) -> Result<(), T> {
+        match core::future::poll_fn(|cx| self.poll_ready(cx)).await {
+            Ok(()) => self.push(value),
+            Err(_) => Err(value),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        let tail = self.shared.tail.load(Ordering::Relaxed);
+        self.shared
+            .distance(self.shared.head.load(Ordering::Acquire), tail)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len() == self.shared.capacity()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity()
+    }
+
+    // 3.3. Счётчики со стороны писателя: записи, пик и круги точные, прочитанное -
+    // записанное минус то, что ещё в кольце. Вытеснений в SPSC не бывает
+    pub fn stats(&self) -> StatsSnapshot {
+        let len = self.len();
+        let mut stats = self.stats;
+        stats.pops = stats.pushes - len as u64;
+        stats.snapshot(len, self.capacity())
+    }
+}
+
+// 4. Читатель
+pub struct Consumer<T> {
+    shared: Arc<Shared<T>>,
+    stats: Stats,
+}
+
+impl<T> Consumer<T> {
+    // 4.1. Чтение без ожидания
+    pub fn pop(&mut self) -> Option<T> {
+        let head = self.shared.head.load(Ordering::Relaxed); // Свою позицию знаем точно
+        let tail = self.shared.tail.load(Ordering::Acquire); // Видим опубликованные писателем ячейки
+        if head == tail {
+            return None;
+        }
+
+        let slot = self.shared.slot(head);
+        let value = slot.with(|ptr| unsafe { (*ptr).assume_init_read() });
+        let next = self.shared.next(head);
+        self.shared.head.store(next, Ordering::Release); // Отдаём ячейку писателю
+        #[cfg(feature = "std")]
+        self.shared.writable.wake();
+
+        self.stats.pops += 1;
+        self.stats.high_water = self.stats.high_water.max(self.shared.distance(head, tail));
+        Some(value)
+    }
+
+    // 4.2. Чтение с ожиданием данных по выбранной стратегии;
+    // None - кольцо пусто, а писатель уже уничтожен
+    pub fn pop_wait(&mut self, strategy: &impl WaitStrategy) -> Option<T> {
+        let mut attempt = 0;
+        loop {
+            if let Some(value) = self.pop() {
+                return Some(value);
+            }
+            if Arc::strong_count(&self.shared) == 1 {
+                return self.pop(); // Писатель мог успеть записать перед уничтожением
+            }
+            strategy.wait(attempt);
+            attempt = attempt.saturating_add(1);
+        }
+    }
+
+    // 4.2.1. Короткое ожидание вращением с экспоненциальной паузой (1, 2, 4 ... 64
+    // spin_loop между попытками), не больше max_spins пауз; ядро не уступается,
+    // поэтому подходит и без std. None - данных так и не появилось
+    pub fn pop_spin(&mut self, max_spins: u32) -> Option<T> {
+        let backoff = Backoff::default();
+        for attempt in 0..max_spins {
+            if let Some(value) = self.pop() {
+                return Some(value);
+            }
+            if Arc::strong_count(&self.shared) == 1 {
+                break; // Писатель уничтожен - ждать нечего
+            }
+            backoff.spin(attempt);
+        }
+        self.pop()
+    }
+
+    // 4.2.2. Ожидание до данных или ухода писателя: экспоненциальное вращение,
+    // затем уступка ядра планировщику
+    #[cfg(feature = "std")]
+    pub fn pop_spin_yield(&mut self) -> Option<T> {
+        self.pop_wait(&Backoff::default())
+    }
+
+    // 4.2.3. Асинхронное чтение: None - кольцо пусто, а писатель уничтожен
+    #[cfg(feature = "std")]
+    pub fn poll_pop(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        if let Some(value) = self.pop() {
+            return Poll::Ready(Some(value));
+        }
+        // Регистрируемся и проверяем снова: писатель мог опубликовать между делом
+        self.shared.readable.register(cx.waker());
+        if let Some(value) = self.pop() {
+            return Poll::Ready(Some(value));
+        }
+        if self.shared.gone.load(Ordering::Acquire) == 1 {
+            return Poll::Ready(self.pop()); // Последние записи писателя видны после флага
+        }
+        Poll::Pending
+    }
+
+    #[cfg(feature = "std")]
+    pub async fn pop_async(&mut self) -> Option<T> {
+        core::future::poll_fn(|cx| self.poll_pop(cx)).await
+    }
+
+    // 4.3. Итератор без ожидания: отдаёт то, что уже записано к моменту вызова, и заканчивается.
+    // Записанное во время обхода остаётся до следующего раза, так что обход не затягивается
+    pub fn try_iter(&mut self) -> impl Iterator<Item = T> + '_ {
+        let available = self.len();
+        (0..available).map_while(move |_| self.pop())
+    }
+
+    pub fn len(&self) -> usize {
+        let head = self.shared.head.load(Ordering::Relaxed);
+        self.shared
+            .distance(head, self.shared.tail.load(Ordering::Acquire))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity()
+    }
+
+    // 4.4. Счётчики со стороны читателя: чтения точные, записанное - прочитанное
+    // плюс то, что в кольце. Пик - наибольшее заполнение, которое видел читатель
+    // (писатель мог заполнить кольцо сильнее между чтениями)
+    pub fn stats(&self) -> StatsSnapshot {
+        let len = self.len();
+        let mut stats = self.stats;
+        stats.pushes = stats.pops + len as u64;
+        stats.high_water = stats.high_water.max(len);
+        stats.snapshot(len, self.capacity())
+    }
+}
+
+// 4.5. Уход одной стороны будит другую, чтобы асинхронное ожидание не зависло
+#[cfg(feature = "std")]
+impl<T> Drop for Producer<T> {
+    fn drop(&mut self) {
+        self.shared.gone.store(1, Ordering::Release);
+        self.shared.readable.wake();
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Drop for Consumer<T> {
+    fn drop(&mut self) {
+        self.shared.gone.store(1, Ordering::Release);
+        self.shared.writable.wake();
+    }
+}
+
+// 4.6. Место для waker ждущей стороны. Флаг waiting избавляет запись и чтение от
+// блокировки, когда никто не ждёт; пара барьеров SeqCst (после регистрации у ждущего
+// и после публикации у другой стороны) гарантирует, что либо ждущий увидит новые
+// данные при повторной проверке, либо другая сторона увидит флаг
+#[cfg(feature = "std")]
+struct WakerSlot {
+    waiting: AtomicUsize,
+    waker: Mutex<Option<Waker>>,
+}
+
+#[cfg(feature = "std")]
+impl WakerSlot {
+    fn new() -> Self {
+        WakerSlot {
+            waiting: AtomicUsize::new(0),
+            waker: Mutex::new(None),
+        }
+    }
+
+    fn register(&self, waker: &Waker) {
+        {
+            let mut slot = self.waker.lock().unwrap_or_else(|e| e.into_inner());
+            if !slot.as_ref().is_some_and(|old| old.will_wake(waker)) {
+                *slot = Some(waker.clone());
+            }
+            self.waiting.store(1, Ordering::Relaxed);
+        }
+        fence(Ordering::SeqCst);
+    }
+
+    fn wake(&self) {
+        fence(Ordering::SeqCst);
+        if self.waiting.load(Ordering::Relaxed) == 0 {
+            return; // Никто не ждёт - без блокировки
+        }
+        let waker = {
+            let mut slot = self.waker.lock().unwrap_or_else(|e| e.into_inner());
+            self.waiting.store(0, Ordering::Relaxed);
+            slot.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+// 5. Стратегии ожидания: чем платить за ожидание - процессором или задержкой
+pub trait WaitStrategy {
+    // Вызывается после каждой неудачной попытки; attempt - сколько их было до этой подряд
+    fn wait(&self, attempt: u32);
+}
+
+// 5.1. Чистое вращение: минимальная задержка, ядро занято полностью
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Spin;
+
+impl WaitStrategy for Spin {
+    fn wait(&self, _attempt: u32) {
+        core::hint::spin_loop();
+    }
+}
+
+// 5.1.1. Экспоненциальная пауза: 2^attempt вращений за попытку, пока attempt не
+// превысит spin_limit; дальше (std) уступка ядра, без std - самая длинная пауза
+#[derive(Debug, Clone, Copy)]
+pub struct Backoff {
+    pub spin_limit: u32,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Backoff { spin_limit: 6 } // До 64 вращений за попытку
+    }
+}
+
+impl Backoff {
+    fn spin(&self, attempt: u32) {
+        for _ in 0..1u32 << attempt.min(self.spin_limit) {
+            core::hint::spin_loop();
+        }
+    }
+}
+
+impl WaitStrategy for Backoff {
+    fn wait(&self, attempt: u32) {
+        #[cfg(feature = "std")]
+        if attempt > self.spin_limit {
+            std::thread::yield_now();
+            return;
+        }
+        self.spin(attempt);
+    }
+}
+
+// 5.2. Вращение, затем уступка ядра планировщику
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy)]
+pub struct SpinThenYield {
+    pub spins: u32, // Сколько попыток вращаемся перед первой уступкой
+}
+
+#[cfg(feature = "std")]
+impl Default for SpinThenYield {
+    fn default() -> Self {
+        SpinThenYield { spins: 64 }
+    }
+}
+
+#[cfg(feature = "std")]
+impl WaitStrategy for SpinThenYield {
+    fn wait(&self, attempt: u32) {
+        if attempt < self.spins {
+            core::hint::spin_loop();
+        } else {
+            std::thread::yield_now();
+        }
+    }
+}
+
+// 5.3. Вращение, затем сон потока с тайм-аутом: почти не тратит процессор,
+// но задержка доходит до timeout
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy)]
+pub struct Park {
+    pub spins: u32,
+    pub timeout: std::time::Duration,
+}
+
+#[cfg(feature = "std")]
+impl Default for Park {
+    fn default() -> Self {
+        Park {
+            spins: 64,
+            timeout: std::time::Duration::from_micros(50),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl WaitStrategy for Park {
+    fn wait(&self, attempt: u32) {
+        if attempt < self.spins {
+            core::hint::spin_loop();
+        } else {
+            std::thread::park_timeout(self.timeout);
+        }
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::Spin;
+    use std::thread;
+
+    #[test]
+    fn test_basic_operations() {
+        let (mut tx, mut rx) = super::new(2);
+
+        assert!(tx.push(1).is_ok());
+        assert!(tx.push(2).is_ok());
+        assert_eq!(tx.push(3), Err(3)); // Значение не теряется
+        assert!(tx.is_full());
+        assert_eq!(rx.pop(), Some(1));
+        assert!(tx.push(3).is_ok()); // Запись через границу массива
+        assert_eq!(rx.pop(), Some(2));
+        assert_eq!(rx.pop(), Some(3));
+        assert_eq!(rx.pop(), None);
+    }
+
+    #[test]
+    fn test_positions_wrap() {
+        // Ёмкость не степень двойки: позиции много раз проходят круг [0, 6)
+        let (mut tx, mut rx) = super::new(3);
+        for i in 0..20 {
+            assert!(tx.push(i).is_ok());
+            assert!(tx.push(i + 100).is_ok());
+            assert_eq!(rx.pop(), Some(i));
+            assert_eq!(rx.pop(), Some(i + 100));
+        }
+
+        let counter = std::rc::Rc::new(());
+        let (mut tx, mut rx) = super::new(3);
+        for _ in 0..5 {
+            assert!(tx.push(std::rc::Rc::clone(&counter)).is_ok());
+            drop(rx.pop());
+        }
+        for _ in 0..3 {
+            assert!(tx.push(std::rc::Rc::clone(&counter)).is_ok()); // Через шов позиций
+        }
+        assert_eq!(tx.len(), 3);
+        drop((tx, rx));
+        assert_eq!(std::rc::Rc::strong_count(&counter), 1); // Непрочитанные уничтожены
+    }
+
+    #[test]
+    fn test_stats_from_both_sides() {
+        let (mut tx, mut rx) = super::new(4);
+        for i in 0..3 {
+            assert!(tx.push(2 * i).is_ok());
+            assert!(tx.push(2 * i + 1).is_ok());
+            assert_eq!(rx.pop(), Some(i));
+        }
+        assert!(tx.push(6).is_ok());
+        assert_eq!(tx.push(7), Err(7)); // Неудачная запись не считается
+
+        let from_tx = tx.stats();
+        assert_eq!((from_tx.pushes, from_tx.pops, from_tx.len), (7, 3, 4));
+        assert_eq!((from_tx.high_water, from_tx.laps), (4, 1));
+
+        let from_rx = rx.stats();
+        assert_eq!((from_rx.pushes, from_rx.pops, from_rx.len), (7, 3, 4));
+        assert_eq!((from_rx.high_water, from_rx.capacity), (4, 4));
+        assert_eq!(from_rx.overwritten, 0);
+    }
+
+    #[test]
+    fn test_try_iter() {
+        let (mut tx, mut rx) = super::new(4);
+        assert_eq!(rx.try_iter().next(), None);
+
+        for i in 0..3 {
+            assert!(tx.push(i).is_ok());
+        }
+        let mut iter = rx.try_iter();
+        assert_eq!(iter.next(), Some(0));
+        assert!(tx.push(3).is_ok()); // Записано во время обхода - достанется следующему
+        assert_eq!(iter.collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), [3]);
+    }
+
+    #[test]
+    fn test_threads_keep_order() {
+        let (mut tx, mut rx) = super::new(4);
+        let producer = thread::spawn(move || {
+            for i in 0..10_000 {
+                let mut value = i;
+                while let Err(back) = tx.push(value) {
+                    value = back; // Кольцо полно - пробуем снова
+                    thread::yield_now();
+                }
+            }
+        });
+
+        let mut expected = 0;
+        while expected < 10_000 {
+            match rx.pop() {
+                Some(value) => {
+                    assert_eq!(value, expected);
+                    expected += 1;
+                }
+                None => thread::yield_now(), // Даём писателю ядро
+            }
+        }
+        producer.join().unwrap();
+    }
+
+    #[cfg(feature = "std")]
+    fn transfer(strategy: impl super::WaitStrategy + Send + 'static) {
+        let (mut tx, mut rx) = super::new(8);
+        let producer = thread::spawn(move || {
+            for i in 0..500 {
+                tx.push_wait(i, &strategy).unwrap();
+            }
+        });
+
+        let mut expected = 0;
+        // Уступаем ядро: на одноядерной машине чистое вращение обеих сторон очень медленное
+        while let Some(value) = rx.pop_wait(&super::SpinThenYield::default()) {
+            assert_eq!(value, expected);
+            expected += 1;
+        }
+        producer.join().unwrap();
+        assert_eq!(expected, 500); // Писатель ушёл - ожидание закончилось
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_async_wait() {
+        use std::future::Future;
+        use std::pin::pin;
+        use std::sync::Arc;
+        use std::task::{Context, Poll, Wake, Waker};
+
+        // Простейший исполнитель: паркуем поток до пробуждения
+        struct Unpark(thread::Thread);
+
+        impl Wake for Unpark {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+
+        fn block_on<F: Future>(future: F) -> F::Output {
+            let mut future = pin!(future);
+            let waker = Waker::from(Arc::new(Unpark(thread::current())));
+            let mut cx = Context::from_waker(&waker);
+            loop {
+                if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                    return output;
+                }
+                thread::park();
+            }
+        }
+
+        let (mut tx, mut rx) = super::new(2);
+        let producer = thread::spawn(move || {
+            block_on(async {
+                for i in 0..1000 {
+                    tx.push_async(i).await.unwrap(); // Кольцо мало: писатель часто ждёт места
+                }
+            })
+        });
+        let received = block_on(async {
+            let mut received = Vec::new();
+            while let Some(value) = rx.pop_async().await {
+                received.push(value);
+            }
+            received
+        });
+        producer.join().unwrap();
+        assert_eq!(received, (0..1000).collect::<Vec<_>>()); // None - только после ухода писателя
+
+        let (mut tx, rx) = super::new(1);
+        tx.push(1).unwrap();
+        drop(rx);
+        assert_eq!(block_on(tx.push_async(2)), Err(2)); // Читателя нет - не ждём
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_wait_strategies() {
+        use super::{Park, SpinThenYield};
+
+        transfer(Spin);
+        transfer(SpinThenYield::default());
+        transfer(Park::default());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_pop_spin() {
+        let (mut tx, mut rx) = super::new(2);
+        assert_eq!(rx.pop_spin(5), None); // Пауз не больше пяти
+        tx.push(1).unwrap();
+        assert_eq!(rx.pop_spin(0), Some(1)); // Одна попытка без пауз
+
+        let producer = thread::spawn(move || {
+            for i in 0..100 {
+                tx.push_wait(i, &super::Backoff::default()).unwrap();
+            }
+        });
+        let mut expected = 0;
+        while expected < 100 {
+            // На одноядерной машине писателю нужно ядро, поэтому без лимита вращений - с уступкой
+            if let Some(value) = rx.pop_spin(4).or_else(|| rx.pop_spin_yield()) {
+                assert_eq!(value, expected);
+                expected += 1;
+            }
+        }
+        producer.join().unwrap();
+        assert_eq!(rx.pop_spin_yield(), None); // Писатель ушёл
+        assert_eq!(rx.pop_spin(u32::MAX), None);
+    }
+
+    #[test]
+    fn test_push_wait_without_reader() {
+        let (mut tx, rx) = super::new(1);
+        tx.push(1).unwrap();
+        drop(rx);
+
+        assert_eq!(tx.push_wait(2, &Spin), Err(2)); // Не висим вечно
+    }
+
+    #[test]
+    fn test_drops_remaining() {
+        use std::rc::Rc;
+
+        let marker = Rc::new(());
+        {
+            let (mut tx, rx) = super::new(3);
+            tx.push(Rc::clone(&marker)).unwrap();
+            tx.push(Rc::clone(&marker)).unwrap();
+            drop(rx);
+            assert_eq!(Rc::strong_count(&marker), 3);
+        }
+        assert_eq!(Rc::strong_count(&marker), 1); // Непрочитанные уничтожены
+    }
+}
+
+// Проверка всех чередований записи и чтения: RUSTFLAGS="--cfg loom" cargo test --release spsc
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use loom::thread;
+
+    #[test]
+    fn test_push_pop_interleavings() {
+        loom::model(|| {
+            let (mut tx, mut rx) = super::new(2);
+            let producer = thread::spawn(move || {
+                for i in 0..3 {
+                    let mut value = i;
+                    while let Err(back) = tx.push(value) {
+                        value = back;
+                        thread::yield_now();
+                    }
+                }
+            });
+
+            let mut received = Vec::new();
+            while received.len() < 3 {
+                match rx.pop() {
+                    Some(value) => received.push(value),
+                    None => thread::yield_now(),
+                }
+            }
+            producer.join().unwrap();
+            assert_eq!(received, vec![0, 1, 2]);
+        });
+    }
+
+    #[test]
+    fn test_drop_with_items_in_flight() {
+        loom::model(|| {
+            let (mut tx, mut rx) = super::new::<Box<u8>>(1);
+            let producer = thread::spawn(move || {
+                let _ = tx.push(Box::new(1));
+            });
+            let _ = rx.pop(); // Может успеть, а может и нет - утечек быть не должно
+            producer.join().unwrap();
+        });
+    }
+}
+
+// Модель протокола счётчиков для проверки stateright: каждый шаг писателя и
+// читателя (чтение чужого счётчика, работа с ячейкой, публикация своего) - отдельное
+// действие, проверяются все порядки шагов. Порядки памяти проверяет loom, здесь -
+// сам протокол: не затираются непрочитанные ячейки, не читаются пустые, порядок FIFO
+#[cfg(all(test, not(loom)))]
+mod model_tests {
+    use stateright::{Checker, Model, Property};
+
+    // Шаг, на котором стоит участник
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum Step {
+        Load,    // Читает счётчик другой стороны
+        Access,  // Пишет или читает ячейку
+        Publish, // Сдвигает свой счётчик
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum Action {
+        Producer,
+        Consumer,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    struct State {
+        head: usize,
+        tail: usize,
+        slots: Vec<Option<usize>>,
+        producer: Step,
+        head_seen: usize, // Снимок head у писателя
+        next: usize,      // Следующее значение для записи
+        consumer: Step,
+        tail_seen: usize, // Снимок tail у читателя
+        received: Vec<usize>,
+        overwritten: bool, // Писатель затёр непрочитанную ячейку
+        read_empty: bool,  // Читатель прочитал пустую ячейку
+        wrapped: bool,     // Позиция записи прошла круг [0, 2 * capacity)
+    }
+
+    // items значений через кольцо на capacity ячеек; early_release - заведомо
+    // ошибочный читатель, отдающий ячейку до того, как прочитал её
+    struct Spsc {
+        capacity: usize,
+        items: usize,
+        early_release: bool,
+    }
+
+    // Позиции - как в Shared: по кругу [0, 2 * capacity), ячейка - позиция по модулю ёмкости
+    impl Spsc {
+        fn slot(&self, position: usize) -> usize {
+            if position >= self.capacity {
+                position - self.capacity
+            } else {
+                position
+            }
+        }
+
+        fn next(&self, position: usize) -> usize {
+            if position + 1 == 2 * self.capacity {
+                0
+            } else {
+                position + 1
+            }
+        }
+
+        fn prev(&self, position: usize) -> usize {
+            if position == 0 {
+                2 * self.capacity - 1
+            } else {
+                position - 1
+            }
+        }
+
+        fn distance(&self, head: usize, tail: usize) -> usize {
+            if tail >= head {
+                tail - head
+            } else {
+                tail + 2 * self.capacity - head
+            }
+        }
+    }
+
+    impl Model for Spsc {
+        type State = State;
+        type Action = Action;
+
+        fn init_states(&self) -> Vec<State> {
+            vec![State {
+                head: 0,
+                tail: 0,
+                slots: vec![None; self.capacity],
+                producer: Step::Load,
+                head_seen: 0,
+                next: 0,
+                consumer: Step::Load,
+                tail_seen: 0,
+                received: Vec::new(),
+                overwritten: false,
+                read_empty: false,
+                wrapped: false,
+            }]
+        }
+
+        fn actions(&self, state: &State, actions: &mut Vec<Action>) {
+            if state.next < self.items {
+                actions.push(Action::Producer);
+            }
+            if state.received.len() < self.items {
+                actions.push(Action::Consumer);
+            }
+        }
+
+        fn next_state(&self, last: &State, action: Action) -> Option<State> {
+            let mut state = last.clone();
+            match (action, state.producer, state.consumer) {
+                // Писатель: снимок head, при заполненном кольце - повтор
+                (Action::Producer, Step::Load, _) => {
+                    state.head_seen = state.head;
+                    if self.distance(state.head_seen, state.tail) < self.capacity {
+                        state.producer = Step::Access;
+                    }
+                }
+                (Action::Producer, Step::Access, _) => {
+                    let slot = &mut state.slots[self.slot(state.tail)];
+                    state.overwritten |= slot.is_some();
+                    *slot = Some(state.next);
+                    state.producer = Step::Publish;
+                }
+                (Action::Producer, Step::Publish, _) => {
+                    state.tail = self.next(state.tail);
+                    state.wrapped |= state.tail == 0;
+                    state.next += 1;
+                    state.producer = Step::Load;
+                }
+
+                // Читатель: снимок tail, при пустом кольце - повтор
+                (Action::Consumer, _, Step::Load) => {
+                    state.tail_seen = state.tail;
+                    if state.head != state.tail_seen {
+                        state.consumer = if self.early_release {
+                            Step::Publish
+                        } else {
+                            Step::Access
+                        };
+                    }
+                }
+                (Action::Consumer, _, Step::Access) => {
+                    let position = if self.early_release {
+                        self.prev(state.head)
+                    } else {
+                        state.head
+                    };
+                    match state.slots[self.slot(position)].take() {
+                        Some(value) => state.received.push(value),
+                        None => state.read_empty = true,
+                    }
+                    state.consumer = if self.early_release {
+                        Step::Load
+                    } else {
+                        Step::Publish
+                    };
+                }
+                (Action::Consumer, _, Step::Publish) => {
+                    state.head = self.next(state.head);
+                    state.consumer = if self.early_release {
+                        Step::Access
+                    } else {
+                        Step::Load
+                    };
+                }
+            }
+            Some(state)
+        }
+
+        fn properties(&self) -> Vec<Property<Self>> {
+            vec![
+                Property::always("no overwrite", |_, state: &State| !state.overwritten),
+                Property::always("no empty read", |_, state: &State| !state.read_empty),
+                Property::always("fifo", |_, state: &State| {
+                    state
+                        .received
+                        .iter()
+                        .enumerate()
+                        .all(|(i, &value)| i == value)
+                }),
+                Property::eventually("all delivered", |model: &Spsc, state: &State| {
+                    state.received.len() == model.items
+                }),
+                Property::sometimes("ring full", |model: &Spsc, state: &State| {
+                    model.distance(state.head, state.tail) == model.capacity
+                }),
+                Property::sometimes("positions wrap", |_, state: &State| state.wrapped),
+            ]
+        }
+    }
+
+    #[test]
+    fn test_protocol_holds() {
+        let checker = Spsc {
+            capacity: 2,
+            items: 6, // Больше 2 * capacity: позиции проходят круг
+            early_release: false,
+        }
+        .checker()
+        .spawn_bfs()
+        .join();
+        checker.assert_properties();
+        assert!(checker.unique_state_count() > 50); // Перебор не вырожденный
+    }
+
+    #[test]
+    fn test_early_release_caught() {
+        let checker = Spsc {
+            capacity: 2,
+            items: 6, // Больше 2 * capacity: позиции проходят круг
+            early_release: true,
+        }
+        .checker()
+        .spawn_bfs()
+        .join();
+        checker.assert_any_discovery("no overwrite");
+    }
+}
@@ -0,0 +1,85 @@
+// Счётчики операций кольцевого буфера
+
+use core::fmt;
+
+// 1. Сколько раз что происходило с момента создания буфера
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub pushes: u64,       // Успешные записи
+    pub pops: u64,         // Прочитанные элементы
+    pub overwritten: u64,  // Элементы, вытесненные при переполнении
+    pub high_water: usize, // Наибольшее заполнение за всё время
+    pub laps: u64,         // Сколько раз запись прошла через конец массива
+}
+
+impl Stats {
+    // 2. Учёт записи с текущим размером после неё
+    pub(crate) fn record_push(&mut self, size: usize) {
+        self.pushes += 1;
+        self.high_water = self.high_water.max(size);
+    }
+
+    // 2.1. Снимок счётчиков вместе с текущими размерами
+    pub(crate) fn snapshot(&self, len: usize, capacity: usize) -> StatsSnapshot {
+        StatsSnapshot {
+            len,
+            capacity,
+            high_water: self.high_water,
+            pushes: self.pushes,
+            pops: self.pops,
+            overwritten: self.overwritten,
+            laps: self.laps,
+        }
+    }
+}
+
+// 3. Всё о буфере одним значением: для логов (Display) и мониторинга (serde)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatsSnapshot {
+    pub len: usize,
+    pub capacity: usize,
+    pub high_water: usize,
+    pub pushes: u64,
+    pub pops: u64,
+    pub overwritten: u64,
+    pub laps: u64,
+}
+
+impl fmt::Display for StatsSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "заполнено {}/{} (пик {}), записей {}, чтений {}, вытеснено {}, кругов {}",
+            self.len,
+            self.capacity,
+            self.high_water,
+            self.pushes,
+            self.pops,
+            self.overwritten,
+            self.laps
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RingBuffer;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_snapshot() {
+        let mut rb = RingBuffer::builder().capacity(4).with_stats().build();
+        assert!(RingBuffer::<u8>::new(4).stats_snapshot().is_none());
+
+        rb.extend(&[1, 2, 3]);
+        rb.skip(2);
+        assert_eq!(rb.push_all([4, 5]), Ok(2)); // Запись проходит через конец массива
+        let snapshot = rb.stats_snapshot().unwrap();
+        assert_eq!((snapshot.len, snapshot.capacity), (3, 4));
+        assert_eq!((snapshot.pushes, snapshot.pops, snapshot.laps), (5, 2, 1));
+        assert_eq!(
+            snapshot.to_string(),
+            "заполнено 3/4 (пик 3), записей 5, чтений 2, вытеснено 0, кругов 1"
+        );
+    }
+}
@@ -0,0 +1,150 @@
+// Строковый кольцевой буфер, не разрывающий символы UTF-8
+
+use alloc::string::{String, ToString};
+
+use crate::RingBuffer;
+
+// 1. Байты текста; в буфер попадают только целые символы
+#[derive(Debug)]
+pub struct StrRingBuffer {
+    bytes: RingBuffer<u8>,
+}
+
+impl StrRingBuffer {
+    // 1.1. Создаём буфер на capacity байт
+    pub fn new(capacity: usize) -> Self {
+        StrRingBuffer {
+            bytes: RingBuffer::new(capacity),
+        }
+    }
+
+    // 2. Запись одного символа целиком
+    pub fn push_char(&mut self, c: char) -> Result<(), String> {
+        if c.len_utf8() > self.free() {
+            return Err("Буфер Заполнен!".to_string()); // Половину символа не пишем
+        }
+
+        let mut encoded = [0; 4];
+        self.bytes.extend(c.encode_utf8(&mut encoded).as_bytes());
+        Ok(())
+    }
+
+    // 3. Запись строки: столько целых символов, сколько помещается; возвращает число байт
+    pub fn push_str(&mut self, s: &str) -> usize {
+        let mut written = 0;
+        for c in s.chars() {
+            if self.push_char(c).is_err() {
+                break; // Следующий символ уже не помещается
+            }
+            written += c.len_utf8();
+        }
+        written
+    }
+
+    // 4. Чтение одного символа
+    pub fn pop_char(&mut self) -> Option<char> {
+        let width = utf8_width(*self.bytes.get(0)?);
+        let mut encoded = [0; 4];
+        for byte in encoded.iter_mut().take(width) {
+            *byte = self.bytes.pop()?;
+        }
+
+        // В буфере только целые символы, поэтому декодирование не ошибается
+        core::str::from_utf8(&encoded[..width]).ok()?.chars().next()
+    }
+
+    // 5. Чтение не более max_bytes байт, только целыми символами
+    pub fn drain_str(&mut self, max_bytes: usize) -> String {
+        let mut result = String::new();
+        while let Some(&first) = self.bytes.get(0) {
+            if result.len() + utf8_width(first) > max_bytes {
+                break; // Следующий символ не влезает в лимит
+            }
+            if let Some(c) = self.pop_char() {
+                result.push(c);
+            }
+        }
+        result
+    }
+
+    // 6. Чтение всего содержимого
+    pub fn drain_all(&mut self) -> String {
+        self.drain_str(self.bytes.len())
+    }
+
+    // 7. Размеры в байтах
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.bytes.capacity()
+    }
+
+    pub fn free(&self) -> usize {
+        self.bytes.capacity() - self.bytes.len()
+    }
+}
+
+// 8. Длина символа UTF-8 по первому байту
+fn utf8_width(first: u8) -> usize {
+    match first {
+        0x00..=0x7F => 1,
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        _ => 4,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StrRingBuffer;
+
+    #[test]
+    fn test_push_and_pop_chars() {
+        let mut sb = StrRingBuffer::new(16);
+        assert_eq!(sb.push_str("aж€😀"), 10);
+
+        assert_eq!(sb.pop_char(), Some('a'));
+        assert_eq!(sb.pop_char(), Some('ж'));
+        assert_eq!(sb.pop_char(), Some('€'));
+        assert_eq!(sb.pop_char(), Some('😀'));
+        assert_eq!(sb.pop_char(), None);
+    }
+
+    #[test]
+    fn test_never_splits_codepoint() {
+        let mut sb = StrRingBuffer::new(5);
+
+        // "ab" + "ж" = 4 байта, следующий "ж" (2 байта) уже не помещается
+        assert_eq!(sb.push_str("abжж"), 4);
+        assert_eq!(sb.len(), 4);
+        assert!(sb.push_char('ж').is_err());
+        assert!(sb.push_char('c').is_ok());
+    }
+
+    #[test]
+    fn test_drain_str_limit() {
+        let mut sb = StrRingBuffer::new(8);
+        sb.push_str("жжж");
+
+        assert_eq!(sb.drain_str(3), "ж"); // Второй символ не влезает в 3 байта
+        assert_eq!(sb.drain_all(), "жж");
+        assert!(sb.is_empty());
+    }
+
+    #[test]
+    fn test_wrap_around() {
+        let mut sb = StrRingBuffer::new(6);
+        sb.push_str("abcd");
+        assert_eq!(sb.drain_str(3), "abc");
+
+        // Символ ложится через границу массива
+        assert_eq!(sb.push_str("привет"), 4);
+        assert_eq!(sb.drain_all(), "dпр");
+    }
+}
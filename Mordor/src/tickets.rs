@@ -0,0 +1,155 @@
+// Ограниченный семафор на кольце билетов: кольцо из элементов нулевого размера
+// (память под них не выделяется) изначально заполнено, acquire забирает билет,
+// а разрешение при уничтожении возвращает его обратно
+
+use std::time::{Duration, Instant};
+
+use crate::{BlockingError, BlockingRing};
+
+// 1. Билеты: не больше capacity выданных разрешений одновременно
+#[derive(Debug)]
+pub struct Tickets {
+    ring: BlockingRing<()>,
+}
+
+impl Tickets {
+    // 1.1. Создаём семафор, все capacity билетов свободны
+    pub fn new(capacity: usize) -> Self {
+        let ring = BlockingRing::new(capacity);
+        for _ in 0..capacity {
+            let _ = ring.try_push(()); // Место есть, ошибки не будет
+        }
+        Tickets { ring }
+    }
+
+    // 2. Получение билета с ожиданием, пока его кто-нибудь не вернёт
+    pub fn acquire(&self) -> Result<Permit<'_>, BlockingError> {
+        self.permit(self.ring.pop())
+    }
+
+    // 2.1. Получение с ожиданием не дольше timeout; по сроку - Timeout.
+    // Срок за пределами часов (например, Duration::MAX) - ожидание без срока
+    pub fn acquire_timeout(&self, timeout: Duration) -> Result<Permit<'_>, BlockingError> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.permit(self.ring.pop_deadline(deadline)),
+            None => self.acquire(),
+        }
+    }
+
+    // 2.2. Получение без ожидания; все билеты выданы - Full
+    pub fn try_acquire(&self) -> Result<Permit<'_>, BlockingError> {
+        match self.ring.try_pop()? {
+            Some(()) => Ok(Permit { tickets: self }),
+            None => Err(BlockingError::Full),
+        }
+    }
+
+    // 2.3. Кольцо билетов не закрывается, поэтому пустым pop не возвращается
+    fn permit(
+        &self,
+        taken: Result<Option<()>, BlockingError>,
+    ) -> Result<Permit<'_>, BlockingError> {
+        match taken? {
+            Some(()) => Ok(Permit { tickets: self }),
+            None => Err(BlockingError::Closed),
+        }
+    }
+
+    // 3. Сколько билетов свободно сейчас
+    pub fn available(&self) -> usize {
+        self.ring.len()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.ring.capacity()
+    }
+
+    // 3.1. Сколько разрешений выдано
+    pub fn in_use(&self) -> usize {
+        self.capacity() - self.available()
+    }
+}
+
+// 4. Разрешение: пока оно живо, билет занят
+#[derive(Debug)]
+pub struct Permit<'a> {
+    tickets: &'a Tickets,
+}
+
+impl Permit<'_> {
+    // 4.1. Явный возврат билета (то же, что drop)
+    pub fn release(self) {}
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        // Выдано не больше capacity билетов - место для возврата всегда есть
+        let _ = self.tickets.ring.try_push(());
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::Tickets;
+    use crate::BlockingError;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_acquire_release() {
+        let tickets = Tickets::new(2);
+        let first = tickets.acquire().unwrap();
+        let second = tickets.try_acquire().unwrap();
+        assert_eq!(tickets.in_use(), 2);
+        assert_eq!(tickets.try_acquire().unwrap_err(), BlockingError::Full);
+        assert_eq!(
+            tickets
+                .acquire_timeout(Duration::from_millis(10))
+                .unwrap_err(),
+            BlockingError::Timeout
+        );
+
+        first.release();
+        assert_eq!(tickets.available(), 1);
+        drop(second);
+        assert_eq!(tickets.available(), 2);
+        assert_eq!(tickets.capacity(), 2);
+    }
+
+    #[test]
+    fn test_unbounded_timeout() {
+        let tickets = Tickets::new(1);
+        let held = tickets.acquire().unwrap();
+
+        thread::scope(|scope| {
+            let waiter = scope.spawn(|| tickets.acquire_timeout(Duration::MAX).map(drop));
+            thread::sleep(Duration::from_millis(10));
+            held.release(); // Срок за пределами часов - ждём без срока, без паники
+            assert!(waiter.join().unwrap().is_ok());
+        });
+        assert_eq!(tickets.available(), 1);
+    }
+
+    #[test]
+    fn test_limits_concurrency() {
+        let tickets = Tickets::new(3);
+        let (running, peak) = (AtomicUsize::new(0), AtomicUsize::new(0));
+
+        thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    for _ in 0..20 {
+                        let _permit = tickets.acquire().unwrap();
+                        let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                        peak.fetch_max(now, Ordering::SeqCst);
+                        thread::yield_now();
+                        running.fetch_sub(1, Ordering::SeqCst);
+                    }
+                });
+            }
+        });
+        assert!(peak.load(Ordering::SeqCst) <= 3);
+        assert_eq!(tickets.available(), 3);
+    }
+}
@@ -0,0 +1,138 @@
+// Мост к tokio-util: ByteRing как bytes::Buf/BufMut и наши декодеры внутри Framed
+
+use std::io;
+
+use bytes::buf::UninitSlice;
+use bytes::{Buf, BufMut, BytesMut};
+use tokio_util::codec::Decoder;
+
+use crate::codec::Decode;
+use crate::ByteRing;
+
+// 1. Чтение из кольца через bytes::Buf: куски идут до шва и после него
+impl Buf for ByteRing {
+    fn remaining(&self) -> usize {
+        self.len()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        self.as_slices().0
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        assert!(cnt <= self.len(), "Сдвиг дальше содержимого!");
+        self.discard(cnt);
+    }
+}
+
+// 2. Запись в кольцо через bytes::BufMut: отдаём свободное место до шва
+unsafe impl BufMut for ByteRing {
+    fn remaining_mut(&self) -> usize {
+        self.free()
+    }
+
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        assert!(cnt <= self.free(), "Фиксация больше свободного места!");
+        self.commit(cnt); // Байты уже записаны вызывающим в chunk_mut
+    }
+
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        UninitSlice::uninit(self.write_chunk_uninit(usize::MAX))
+    }
+}
+
+// 3. Ошибка моста: ошибка нашего декодера или ввода-вывода потока
+#[derive(Debug)]
+pub enum FramedError<E> {
+    Decode(E),
+    Io(io::Error),
+}
+
+impl<E> From<io::Error> for FramedError<E> {
+    fn from(e: io::Error) -> Self {
+        FramedError::Io(e)
+    }
+}
+
+// 4. Декодер для Framed/FramedRead: байты из BytesMut перекладываются в кольцо,
+// а кадры выделяет любой наш Decode
+#[derive(Debug)]
+pub struct RingCodec<D> {
+    decoder: D,
+    ring: ByteRing,
+}
+
+impl<D: Decode> RingCodec<D> {
+    pub fn new(decoder: D, capacity: usize) -> Self {
+        RingCodec {
+            decoder,
+            ring: ByteRing::new(capacity),
+        }
+    }
+}
+
+impl<D: Decode> Decoder for RingCodec<D> {
+    type Item = D::Item;
+    type Error = FramedError<D::Error>;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<D::Item>, Self::Error> {
+        let moved = self.ring.extend_from_slice(src); // Сколько влезло, остальное подождёт
+        src.advance(moved);
+        self.decoder
+            .decode(&mut self.ring)
+            .map_err(FramedError::Decode)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<D::Item>, Self::Error> {
+        match self.decode(src)? {
+            Some(item) => Ok(Some(item)),
+            None if self.ring.is_empty() && src.is_empty() => Ok(None),
+            None => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "Поток оборвался на середине кадра",
+            )
+            .into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FramedError, RingCodec};
+    use crate::{ByteRing, Delimited};
+    use bytes::{Buf, BufMut, BytesMut};
+    use tokio_util::codec::Decoder;
+
+    #[test]
+    fn test_buf_and_buf_mut() {
+        let mut ring = ByteRing::new(6);
+        ring.put_slice(b"abcd");
+        ring.advance(3);
+        ring.put_slice(b"efgh"); // Запись через шов
+
+        assert_eq!(ring.remaining(), 5);
+        assert_eq!(ring.chunk(), b"def");
+        let mut out = [0; 5];
+        ring.copy_to_slice(&mut out);
+        assert_eq!(&out, b"defgh");
+    }
+
+    #[test]
+    fn test_ring_codec() {
+        let mut codec = RingCodec::new(Delimited::new(b"\n", 64), 16);
+        let mut src = BytesMut::from(&b"one\ntw"[..]);
+
+        assert_eq!(codec.decode(&mut src).unwrap(), Some(b"one".to_vec()));
+        assert_eq!(codec.decode(&mut src).unwrap(), None);
+
+        src.extend_from_slice(b"o\n");
+        assert_eq!(codec.decode_eof(&mut src).unwrap(), Some(b"two".to_vec()));
+        assert_eq!(codec.decode_eof(&mut src).unwrap(), None);
+
+        src.extend_from_slice(b"cut");
+        assert!(matches!(
+            codec.decode_eof(&mut src),
+            Err(FramedError::Io(_))
+        ));
+    }
+}
@@ -0,0 +1,328 @@
+// Запись и воспроизведение последовательностей операций: трасса из работающей
+// программы превращается в регрессионный тест для изменённой реализации
+//
+// Формат файла - по шагу на строку, значение всегда последнее (Display/FromStr, без переводов строк):
+//   push ok <значение> | push full <значение>
+//   pop [<значение>] | pop_back [<значение>]
+//   drain <запрошено> <получено>
+//   clear
+//   resize <ёмкость>
+
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+use crate::RingBuffer;
+
+// 1. Шаг трассы: операция и то, чем она закончилась
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Step<T> {
+    Push(T, bool), // Значение и удалось ли записать
+    Pop(Option<T>),
+    PopBack(Option<T>),
+    Drain(usize, usize), // Сколько просили и сколько получили
+    Clear,
+    Resize(usize),
+}
+
+// 2. Что умеет реализация, на которой воспроизводят трассу
+pub trait TraceTarget<T> {
+    fn push(&mut self, value: T) -> bool;
+    fn pop(&mut self) -> Option<T>;
+    fn pop_back(&mut self) -> Option<T>;
+    fn drain(&mut self, count: usize) -> usize;
+    fn clear(&mut self);
+    fn resize(&mut self, capacity: usize);
+}
+
+impl<T> TraceTarget<T> for RingBuffer<T> {
+    fn push(&mut self, value: T) -> bool {
+        RingBuffer::push(self, value).is_ok()
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        RingBuffer::pop(self)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        RingBuffer::pop_back(self)
+    }
+
+    fn drain(&mut self, count: usize) -> usize {
+        RingBuffer::drain(self, count).len()
+    }
+
+    fn clear(&mut self) {
+        RingBuffer::clear(self)
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        RingBuffer::resize(self, capacity)
+    }
+}
+
+// 3. Запись: обёртка над буфером, которая запоминает каждый шаг
+#[derive(Debug)]
+pub struct Recorder<T> {
+    ring: RingBuffer<T>,
+    steps: Vec<Step<T>>,
+}
+
+impl<T: Clone> Recorder<T> {
+    pub fn new(ring: RingBuffer<T>) -> Self {
+        Recorder {
+            ring,
+            steps: Vec::new(),
+        }
+    }
+
+    // 3.1. Операции буфера с записью шага
+    pub fn push(&mut self, value: T) -> Result<(), String> {
+        let result = self.ring.push(value.clone());
+        self.steps.push(Step::Push(value, result.is_ok()));
+        result
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let value = self.ring.pop();
+        self.steps.push(Step::Pop(value.clone()));
+        value
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        let value = self.ring.pop_back();
+        self.steps.push(Step::PopBack(value.clone()));
+        value
+    }
+
+    pub fn drain(&mut self, count: usize) -> Vec<T> {
+        let values = self.ring.drain(count);
+        self.steps.push(Step::Drain(count, values.len()));
+        values
+    }
+
+    pub fn clear(&mut self) {
+        self.ring.clear();
+        self.steps.push(Step::Clear);
+    }
+
+    pub fn resize(&mut self, capacity: usize) {
+        self.ring.resize(capacity);
+        self.steps.push(Step::Resize(capacity));
+    }
+
+    // 3.2. Доступ к буферу и записанным шагам
+    pub fn ring(&self) -> &RingBuffer<T> {
+        &self.ring
+    }
+
+    pub fn steps(&self) -> &[Step<T>] {
+        &self.steps
+    }
+
+    pub fn into_replayer(self) -> Replayer<T> {
+        Replayer { steps: self.steps }
+    }
+}
+
+impl<T: Display> Recorder<T> {
+    // 3.3. Сохранение трассы в текстовом формате
+    pub fn write_to(&self, w: &mut impl Write) -> io::Result<()> {
+        for step in &self.steps {
+            match step {
+                Step::Push(value, true) => writeln!(w, "push ok {}", value)?,
+                Step::Push(value, false) => writeln!(w, "push full {}", value)?,
+                Step::Pop(Some(value)) => writeln!(w, "pop {}", value)?,
+                Step::Pop(None) => writeln!(w, "pop")?,
+                Step::PopBack(Some(value)) => writeln!(w, "pop_back {}", value)?,
+                Step::PopBack(None) => writeln!(w, "pop_back")?,
+                Step::Drain(asked, got) => writeln!(w, "drain {} {}", asked, got)?,
+                Step::Clear => writeln!(w, "clear")?,
+                Step::Resize(capacity) => writeln!(w, "resize {}", capacity)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// 4. Воспроизведение записанной трассы
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replayer<T> {
+    steps: Vec<Step<T>>,
+}
+
+impl<T> Replayer<T> {
+    pub fn new(steps: Vec<Step<T>>) -> Self {
+        Replayer { steps }
+    }
+
+    pub fn steps(&self) -> &[Step<T>] {
+        &self.steps
+    }
+}
+
+impl<T: FromStr> Replayer<T> {
+    // 4.1. Чтение трассы; пустые строки пропускаются
+    pub fn read_from(r: impl BufRead) -> io::Result<Self> {
+        let mut steps = Vec::new();
+        for (number, line) in r.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let step = parse_step(&line).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Непонятный шаг в строке {}: {}", number + 1, line),
+                )
+            })?;
+            steps.push(step);
+        }
+        Ok(Replayer { steps })
+    }
+}
+
+impl<T: Clone + PartialEq> Replayer<T> {
+    // 4.2. Проигрываем шаги на target и сверяем исходы;
+    // Err - номер первого шага, на котором реализация разошлась с записью
+    pub fn replay(&self, target: &mut impl TraceTarget<T>) -> Result<(), usize> {
+        for (index, step) in self.steps.iter().enumerate() {
+            let same = match step {
+                Step::Push(value, pushed) => target.push(value.clone()) == *pushed,
+                Step::Pop(value) => target.pop() == *value,
+                Step::PopBack(value) => target.pop_back() == *value,
+                Step::Drain(asked, got) => target.drain(*asked) == *got,
+                Step::Clear => {
+                    target.clear();
+                    true
+                }
+                Step::Resize(capacity) => {
+                    target.resize(*capacity);
+                    true
+                }
+            };
+            if !same {
+                return Err(index);
+            }
+        }
+        Ok(())
+    }
+}
+
+// 4.3. Разбор одной строки трассы
+fn parse_step<T: FromStr>(line: &str) -> Option<Step<T>> {
+    let (word, rest) = line.split_once(' ').unwrap_or((line, ""));
+    let value = |text: &str| text.parse().ok();
+    let optional = |text: &str| {
+        if text.is_empty() {
+            Some(None)
+        } else {
+            text.parse().ok().map(Some)
+        }
+    };
+
+    match word {
+        "push" => {
+            let (outcome, text) = rest.split_once(' ')?;
+            let pushed = match outcome {
+                "ok" => true,
+                "full" => false,
+                _ => return None,
+            };
+            Some(Step::Push(value(text)?, pushed))
+        }
+        "pop" => Some(Step::Pop(optional(rest)?)),
+        "pop_back" => Some(Step::PopBack(optional(rest)?)),
+        "drain" => {
+            let (asked, got) = rest.split_once(' ')?;
+            Some(Step::Drain(asked.parse().ok()?, got.parse().ok()?))
+        }
+        "clear" if rest.is_empty() => Some(Step::Clear),
+        "resize" => Some(Step::Resize(rest.parse().ok()?)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Recorder, Replayer, Step, TraceTarget};
+    use crate::RingBuffer;
+
+    fn record() -> Recorder<String> {
+        let mut rec = Recorder::new(RingBuffer::new(2));
+        for word in ["alpha", "beta", "gamma delta"] {
+            let _ = rec.push(word.to_string());
+        }
+        rec.pop();
+        rec.resize(3);
+        assert!(rec.push("gamma delta".to_string()).is_ok());
+        rec.drain(5);
+        rec.pop_back();
+        rec
+    }
+
+    #[test]
+    fn test_file_round_trip() {
+        let rec = record();
+        let mut file = Vec::new();
+        rec.write_to(&mut file).unwrap();
+
+        let text = String::from_utf8(file.clone()).unwrap();
+        assert!(text.starts_with("push ok alpha\npush ok beta\npush full gamma delta\n"));
+        assert!(text.ends_with("drain 5 2\npop_back\n"));
+
+        let replayer = Replayer::<String>::read_from(&file[..]).unwrap();
+        assert_eq!(replayer.steps(), rec.steps());
+        assert_eq!(replayer.replay(&mut RingBuffer::new(2)), Ok(()));
+    }
+
+    // Изменённая реализация с ошибкой: pop_back читает из головы
+    struct Broken(RingBuffer<u32>);
+
+    impl TraceTarget<u32> for Broken {
+        fn push(&mut self, value: u32) -> bool {
+            self.0.push(value).is_ok()
+        }
+        fn pop(&mut self) -> Option<u32> {
+            self.0.pop()
+        }
+        fn pop_back(&mut self) -> Option<u32> {
+            self.0.pop()
+        }
+        fn drain(&mut self, count: usize) -> usize {
+            self.0.drain(count).len()
+        }
+        fn clear(&mut self) {
+            self.0.clear()
+        }
+        fn resize(&mut self, capacity: usize) {
+            self.0.resize(capacity)
+        }
+    }
+
+    #[test]
+    fn test_replay_finds_divergence() {
+        let mut rec = Recorder::new(RingBuffer::new(4));
+        for i in 0..3 {
+            assert!(rec.push(i).is_ok());
+        }
+        assert_eq!(rec.pop_back(), Some(2));
+        let replayer = rec.into_replayer();
+
+        assert_eq!(replayer.replay(&mut RingBuffer::new(4)), Ok(()));
+        assert_eq!(replayer.replay(&mut Broken(RingBuffer::new(4))), Err(3));
+        assert_eq!(replayer.replay(&mut RingBuffer::new(2)), Err(2)); // Другая ёмкость
+    }
+
+    #[test]
+    fn test_rejects_garbage() {
+        assert!(Replayer::<u8>::read_from(&b"push ok 1\njump 3\n"[..]).is_err());
+        assert!(Replayer::<u8>::read_from(&b"push ok 300\n"[..]).is_err());
+        assert_eq!(
+            Replayer::<u8>::read_from(&b"pop\n\nclear\n"[..])
+                .unwrap()
+                .steps(),
+            [Step::Pop(None), Step::Clear]
+        );
+    }
+}
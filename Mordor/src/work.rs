@@ -0,0 +1,179 @@
+// Ограниченная очередь задач с пулом рабочих потоков
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+use crate::blocking::{BlockingError, BlockingRing};
+
+// 1. Задача - любое замыкание, которое можно отправить в другой поток
+pub type Job = Box<dyn FnOnce() + Send + 'static>;
+
+// 2. Очередь задач и запущенные рабочие потоки
+pub struct WorkQueue {
+    jobs: Arc<BlockingRing<Job>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl WorkQueue {
+    // 2.1. Создаём очередь на capacity ожидающих задач
+    pub fn new(capacity: usize) -> Self {
+        WorkQueue {
+            jobs: Arc::new(BlockingRing::new(capacity)),
+            workers: Vec::new(),
+        }
+    }
+
+    // 3. Запуск n рабочих потоков, выполняющих задачи из очереди
+    pub fn spawn_workers(&mut self, n: usize) {
+        for _ in 0..n {
+            let jobs = Arc::clone(&self.jobs);
+            self.workers.push(thread::spawn(move || {
+                // Выходим, когда очередь закрыта и пуста (или отравлена)
+                while let Ok(Some(job)) = jobs.pop() {
+                    run(job);
+                }
+            }));
+        }
+    }
+
+    // 4. Постановка задачи (ждёт, если очередь заполнена)
+    pub fn submit(&self, job: impl FnOnce() + Send + 'static) -> Result<(), BlockingError> {
+        self.jobs.push(Box::new(job))
+    }
+
+    // 5. Постановка задачи без ожидания
+    pub fn try_submit(&self, job: impl FnOnce() + Send + 'static) -> Result<(), BlockingError> {
+        self.jobs.try_push(Box::new(job))
+    }
+
+    // 6. Сколько задач ждут выполнения
+    pub fn pending(&self) -> usize {
+        self.jobs.len()
+    }
+
+    pub fn worker_count(&self) -> usize {
+        self.workers.len()
+    }
+
+    // 7. Плавная остановка: новые задачи не принимаются, оставшиеся выполняются
+    // (если рабочих нет - в вызывающем потоке)
+    pub fn shutdown(mut self) {
+        self.stop();
+    }
+
+    fn stop(&mut self) {
+        self.jobs.close();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+        // Рабочих не запускали (или они не успели всё разобрать) - дорабатываем сами
+        while let Ok(Some(job)) = self.jobs.pop() {
+            run(job);
+        }
+    }
+}
+
+// 7.1. Выполнение задачи: паника остаётся внутри задачи, поток продолжает разбирать очередь
+fn run(job: Job) {
+    let _ = panic::catch_unwind(AssertUnwindSafe(job));
+}
+
+impl Drop for WorkQueue {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::WorkQueue;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_executes_all_jobs() {
+        let done = Arc::new(AtomicUsize::new(0));
+        let mut queue = WorkQueue::new(4);
+        queue.spawn_workers(3);
+
+        for _ in 0..50 {
+            let done = Arc::clone(&done);
+            queue
+                .submit(move || {
+                    done.fetch_add(1, Ordering::SeqCst);
+                })
+                .unwrap();
+        }
+        queue.shutdown();
+
+        assert_eq!(done.load(Ordering::SeqCst), 50);
+    }
+
+    #[test]
+    fn test_shutdown_drains_remaining() {
+        let done = Arc::new(AtomicUsize::new(0));
+        let mut queue = WorkQueue::new(8);
+
+        // Задачи ставятся до запуска рабочих и должны выполниться при остановке
+        for _ in 0..8 {
+            let done = Arc::clone(&done);
+            queue
+                .try_submit(move || {
+                    done.fetch_add(1, Ordering::SeqCst);
+                })
+                .unwrap();
+        }
+        assert_eq!(queue.pending(), 8);
+        queue.spawn_workers(2);
+        queue.shutdown();
+
+        assert_eq!(done.load(Ordering::SeqCst), 8);
+    }
+
+    #[test]
+    fn test_panicking_job_keeps_worker() {
+        let done = Arc::new(AtomicUsize::new(0));
+        let mut queue = WorkQueue::new(2);
+        queue.spawn_workers(1);
+
+        queue.submit(|| panic!("Задача упала")).unwrap();
+        for _ in 0..10 {
+            let done = Arc::clone(&done);
+            queue
+                .submit(move || {
+                    done.fetch_add(1, Ordering::SeqCst);
+                })
+                .unwrap(); // Единственный рабочий жив, очередь не встаёт
+        }
+        queue.shutdown();
+
+        assert_eq!(done.load(Ordering::SeqCst), 10);
+    }
+
+    #[test]
+    fn test_shutdown_without_workers_runs_inline() {
+        let done = Arc::new(AtomicUsize::new(0));
+        let queue = WorkQueue::new(4);
+        for _ in 0..3 {
+            let done = Arc::clone(&done);
+            queue
+                .try_submit(move || {
+                    done.fetch_add(1, Ordering::SeqCst);
+                })
+                .unwrap();
+        }
+        queue.shutdown();
+
+        assert_eq!(done.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_full_queue_rejects_try_submit() {
+        let queue = WorkQueue::new(1);
+
+        assert!(queue.try_submit(|| {}).is_ok());
+        assert!(queue.try_submit(|| {}).is_err()); // Рабочих нет, место занято
+        assert_eq!(queue.worker_count(), 0);
+    }
+}